@@ -0,0 +1,558 @@
+use crate::{
+    canvas::{Canvas, color::Color},
+    world::World,
+    ray::Ray,
+    math::{
+        point::Point,
+        vector::Vector,
+        matrix::{Matrix, transformation::view_transform},
+    },
+};
+
+/// A Camera maps the three-dimensional scene onto a two-dimensional canvas.
+///
+/// The camera is located at the origin and looks towards negative z.
+/// The canvas is always one unit in front of the camera. The camera
+/// can be moved around by assigning a view transformation to it.
+///
+/// # Properties
+///
+/// * `hsize` - The horizontal size (in pixels) of the canvas
+/// * `vsize` - The vertical size (in pixels) of the canvas
+/// * `field_of_view` - The angle (in radians) of what the camera can see
+/// * `transform` - The orientation of the world relative to the camera
+#[derive(Debug, Clone)]
+pub struct Camera {
+    hsize: usize,
+    vsize: usize,
+    field_of_view: f64,
+    transform: Matrix,
+    inverse: Matrix,
+    half_width: f64,
+    half_height: f64,
+    pixel_size: f64,
+}
+
+impl Camera {
+    /// Create a new camera.
+    ///
+    /// The transformation of a new camera is the identity matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `hsize` - The horizontal size (in pixels) of the canvas
+    /// * `vsize` - The vertical size (in pixels) of the canvas
+    /// * `field_of_view` - The angle (in radians) of what the camera can see
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::camera::Camera;
+    ///
+    /// // The pixel size for a horizontal canvas
+    /// let c = Camera::new(200, 125, std::f64::consts::PI / 2.0);
+    ///
+    /// assert!((0.01 - c.pixel_size()).abs() < 0.00001);
+    /// ```
+    pub fn new(hsize: usize, vsize: usize, field_of_view: f64) -> Self {
+        let half_view = (field_of_view / 2.0).tan();
+        let aspect = hsize as f64 / vsize as f64;
+
+        let (half_width, half_height) = if aspect >= 1.0 {
+            (half_view, half_view / aspect)
+        } else {
+            (half_view * aspect, half_view)
+        };
+
+        Self {
+            hsize,
+            vsize,
+            field_of_view,
+            transform: Matrix::identity(),
+            inverse: Matrix::identity(),
+            half_width,
+            half_height,
+            pixel_size: (half_width * 2.0) / hsize as f64,
+        }
+    }
+
+    /// Get the horizontal size (in pixels) of the canvas.
+    pub fn hsize(&self) -> usize {
+        self.hsize
+    }
+
+    /// Get the vertical size (in pixels) of the canvas.
+    pub fn vsize(&self) -> usize {
+        self.vsize
+    }
+
+    /// Get the angle (in radians) of what the camera can see.
+    pub fn field_of_view(&self) -> f64 {
+        self.field_of_view
+    }
+
+    /// Get the ratio of the horizontal to the vertical size of the canvas.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::camera::Camera;
+    ///
+    /// let c = Camera::new(160, 120, std::f64::consts::PI / 2.0);
+    ///
+    /// assert!((4.0 / 3.0 - c.aspect_ratio()).abs() < 0.00001);
+    /// ```
+    pub fn aspect_ratio(&self) -> f64 {
+        self.hsize as f64 / self.vsize as f64
+    }
+
+    /// Get the view transformation of the camera.
+    pub fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    /// Assign a new view transformation to the camera.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The view transformation, see [`view_transform`]
+    ///
+    /// # Panics
+    ///
+    /// Panics if `m` isn't invertible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     camera::Camera,
+    ///     math::{point::Point, vector::Vector, matrix::transformation::view_transform},
+    /// };
+    ///
+    /// let mut c = Camera::new(201, 101, std::f64::consts::PI / 2.0);
+    /// c.set_transform(view_transform(&Point::new(0.0, 0.0, -5.0),
+    ///                                &Point::new(0.0, 0.0, 0.0),
+    ///                                &Vector::new(0.0, 1.0, 0.0)));
+    ///
+    /// assert_eq!(Point::new(0.0, 0.0, -5.0), *c.ray_for_pixel(100, 50).origin());
+    /// ```
+    pub fn set_transform(&mut self, m: Matrix) {
+        self.inverse = m.inverse().expect("the view transformation of a camera must be invertible");
+        self.transform = m;
+    }
+
+    /// Get the size of a single pixel (on the canvas one unit in front of the camera).
+    pub fn pixel_size(&self) -> f64 {
+        self.pixel_size
+    }
+
+    /// Create a ray that starts at the camera and passes through
+    /// the center of the given pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `px` - The x coordinate of the pixel
+    /// * `py` - The y coordinate of the pixel
+    pub fn ray_for_pixel(&self, px: usize, py: usize) -> Ray {
+        // The offset from the edge of the canvas to the pixel's center
+        let xoffset = (px as f64 + 0.5) * self.pixel_size;
+        let yoffset = (py as f64 + 0.5) * self.pixel_size;
+
+        // The untransformed coordinates of the pixel in world space.
+        // The camera looks toward -z, so +x is to the left.
+        let world_x = self.half_width - xoffset;
+        let world_y = self.half_height - yoffset;
+
+        // Transform the canvas point and the origin, then compute
+        // the ray's direction (the canvas is at z = -1).
+        let pixel = self.inverse.mul_point(&Point::new(world_x, world_y, -1.0));
+        let origin = self.inverse.mul_point(&Point::origin());
+
+        Ray::new(origin, (pixel - origin).norm_cpy())
+    }
+
+    /// Render an image of the given world.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to render
+    pub fn render(&self, world: &World) -> Canvas {
+        let mut image = Canvas::new(self.hsize, self.vsize);
+
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                let ray = self.ray_for_pixel(x, y);
+                image.write_pixel(x, y, world.trace(&ray));
+            }
+        }
+
+        image
+    }
+
+    /// Render the depth of the given world.
+    ///
+    /// Returns the distance `t` from the camera to the closest hit for each
+    /// pixel (indexed by row, then column). Pixels whose ray doesn't hit
+    /// anything have a depth of `f64::INFINITY`.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to render
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{camera::Camera, world::World};
+    ///
+    /// let c = Camera::new(4, 2, std::f64::consts::PI / 2.0);
+    /// let depth = c.render_depth(&World::new());
+    ///
+    /// assert_eq!(2, depth.len());
+    /// assert_eq!(4, depth[0].len());
+    /// assert!(depth.iter().flatten().all(|t| t.is_infinite()));
+    /// ```
+    pub fn render_depth(&self, world: &World) -> Vec<Vec<f64>> {
+        (0..self.vsize).map(|y| {
+            (0..self.hsize).map(|x| {
+                // The direction of the ray is normalized, so `t` is the distance.
+                match world.intersect(&self.ray_for_pixel(x, y)).hit() {
+                    Some(hit) => hit.t(),
+                    None => f64::INFINITY,
+                }
+            }).collect()
+        }).collect()
+    }
+
+    /// Render the surface normals of the given world.
+    ///
+    /// Each component of the (world space) normal at the closest hit
+    /// is mapped from `[-1, 1]` to `[0, 1]` and used as red, green and
+    /// blue channel respectively. Pixels whose ray doesn't hit anything
+    /// are black.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to render
+    pub fn render_normals(&self, world: &World) -> Canvas {
+        let mut image = Canvas::new(self.hsize, self.vsize);
+
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                let ray = self.ray_for_pixel(x, y);
+
+                if let Some(hit) = world.intersect(&ray).hit() {
+                    let n = hit.obj().normal_at(&ray.position(hit.t()));
+                    image.write_pixel(x, y, Color::new(((n.x() + 1.0) / 2.0) as f32,
+                                                       ((n.y() + 1.0) / 2.0) as f32,
+                                                       ((n.z() + 1.0) / 2.0) as f32));
+                }
+            }
+        }
+
+        image
+    }
+
+    /// Position the camera so that the whole world is in view.
+    ///
+    /// The camera looks along the z axis at the center of the
+    /// world's bounds from a distance at which a sphere around the
+    /// bounds fits into the field of view. An empty world leaves
+    /// the camera unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to frame
+    pub fn look_at_scene(&mut self, world: &World) {
+        let center = match world_center(world) {
+            Some(center) => center,
+            None => return,
+        };
+        let radius = center.distance(world.bounds().max());
+
+        // The narrower side of the view decides how far away the camera has to be.
+        let half_angle = self.half_width.min(self.half_height).atan();
+        let distance = radius / half_angle.sin();
+
+        let from = center - Vector::new(0.0, 0.0, distance);
+        self.set_transform(view_transform(&from, &center, &Vector::new(0.0, 1.0, 0.0)));
+    }
+
+    /// Render a turntable animation of the world.
+    ///
+    /// The camera orbits the center of the world's bounds (the origin
+    /// for an empty world) in the horizontal plane, always looking at
+    /// the center. The first frame is taken from `orbit_radius` units in
+    /// front of the center (towards negative z) and each following frame
+    /// moves the camera by `360° / frames` around the y axis. The current
+    /// transformation of the camera is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to render
+    /// * `frames` - The number of frames (canvases) to render
+    /// * `orbit_radius` - The distance between the camera and the center
+    pub fn render_sequence(&self, world: &World, frames: usize, orbit_radius: f64) -> Vec<Canvas> {
+        let center = world_center(world).unwrap_or_else(Point::origin);
+        let up = Vector::new(0.0, 1.0, 0.0);
+        let mut camera = self.clone();
+
+        (0..frames).map(|i| {
+            let angle = 2.0 * std::f64::consts::PI * i as f64 / frames as f64;
+            let from = center + Vector::new(orbit_radius * angle.sin(), 0.0, -orbit_radius * angle.cos());
+
+            camera.set_transform(view_transform(&from, &center, &up));
+            camera.render(world)
+        }).collect()
+    }
+}
+
+/// The center of the world's bounds or None if the world is empty.
+fn world_center(world: &World) -> Option<Point> {
+    let bounds = world.bounds();
+    let (min, max) = (bounds.min(), bounds.max());
+
+    if min.x() > max.x() || min.y() > max.y() || min.z() > max.z() {
+        return None;
+    }
+
+    Some(Point::new((min.x() + max.x()) / 2.0,
+                    (min.y() + max.y()) / 2.0,
+                    (min.z() + max.z()) / 2.0))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        camera::Camera,
+        world::World,
+        shapes::Sphere,
+        light::PointLight,
+        materials::Material,
+        canvas::color::Color,
+        math::{
+            point::Point,
+            vector::Vector,
+            matrix::{Matrix, transformation::*},
+        },
+    };
+
+    use std::f64::consts::PI;
+
+    fn assert_vector_eq(expected: Vector, actual: Vector) {
+        assert!((expected - actual).mag() < 0.00001, "expected {:?} but got {:?}", expected, actual);
+    }
+
+    #[test]
+    fn constructing_a_camera() {
+        let c = Camera::new(160, 120, PI / 2.0);
+
+        assert_eq!(160, c.hsize());
+        assert_eq!(120, c.vsize());
+        assert_eq!(PI / 2.0, c.field_of_view());
+        assert_eq!(Matrix::identity(), *c.transform());
+    }
+
+    #[test]
+    fn the_pixel_size_for_a_vertical_canvas() {
+        let c = Camera::new(125, 200, PI / 2.0);
+        assert!((0.01 - c.pixel_size()).abs() < 0.00001);
+    }
+
+    #[test]
+    fn constructing_a_ray_through_the_center_of_the_canvas() {
+        let c = Camera::new(201, 101, PI / 2.0);
+        let r = c.ray_for_pixel(100, 50);
+
+        assert_eq!(Point::new(0.0, 0.0, 0.0), *r.origin());
+        assert_vector_eq(Vector::new(0.0, 0.0, -1.0), *r.direction());
+    }
+
+    #[test]
+    fn constructing_a_ray_through_a_corner_of_the_canvas() {
+        let c = Camera::new(201, 101, PI / 2.0);
+        let r = c.ray_for_pixel(0, 0);
+
+        assert_eq!(Point::new(0.0, 0.0, 0.0), *r.origin());
+        assert_vector_eq(Vector::new(0.66519, 0.33259, -0.66851), *r.direction());
+    }
+
+    #[test]
+    fn constructing_a_ray_when_the_camera_is_transformed() {
+        let mut c = Camera::new(201, 101, PI / 2.0);
+        c.set_transform(rotation_rad_y(PI / 4.0) * translation(0.0, -2.0, 5.0));
+        let r = c.ray_for_pixel(100, 50);
+
+        assert!(Point::new(0.0, 2.0, -5.0).distance(r.origin()) < 0.00001);
+        assert_vector_eq(Vector::new(2.0_f64.sqrt() / 2.0, 0.0, -(2.0_f64.sqrt()) / 2.0), *r.direction());
+    }
+
+    #[test]
+    fn rendering_a_world_with_a_camera() {
+        let mut s1 = Sphere::new();
+        s1.set_material(Material::new(Color::new(0.8, 1.0, 0.6), 0.1, 0.7, 0.2, 200.0));
+        let mut s2 = Sphere::new();
+        s2.set_transform(scaling(0.5, 0.5, 0.5));
+        let mut w = World::new();
+        w.add_object(Box::new(s1))
+         .add_object(Box::new(s2))
+         .add_light(Box::new(PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(-10.0, 10.0, -10.0))));
+
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        c.set_transform(view_transform(&Point::new(0.0, 0.0, -5.0),
+                                       &Point::new(0.0, 0.0, 0.0),
+                                       &Vector::new(0.0, 1.0, 0.0)));
+        let image = c.render(&w);
+        let pixel = image.pixel_at(5, 5);
+
+        assert!((pixel.r() - 0.38066).abs() < 0.0001);
+        assert!((pixel.g() - 0.47583).abs() < 0.0001);
+        assert!((pixel.b() - 0.2855).abs() < 0.0001);
+    }
+
+    #[test]
+    fn looking_at_a_scene_centers_the_scene() {
+        let mut s1 = Sphere::new();
+        s1.set_transform(translation(4.0, 1.0, 0.0));
+        let mut s2 = Sphere::new();
+        s2.set_transform(translation(-2.0, 3.0, 2.0) * scaling(2.0, 2.0, 2.0));
+        let mut w = World::new();
+        w.add_object(Box::new(s1))
+         .add_object(Box::new(s2));
+
+        let mut c = Camera::new(100, 50, PI / 3.0);
+        c.look_at_scene(&w);
+
+        // bounds: (-4, 0, -1) to (5, 5, 4)
+        let center = Point::new(0.5, 2.5, 1.5);
+        let in_view = c.transform.mul_point(&center);
+        assert!(in_view.x().abs() < 0.00001);
+        assert!(in_view.y().abs() < 0.00001);
+        assert!(in_view.z() < 0.0);
+
+        // The ray through the middle of the canvas passes the center.
+        let r = c.ray_for_pixel(50, 25);
+        let to_center = (center - *r.origin()).norm_cpy();
+        assert!(r.direction().dot(&to_center) > 0.9999);
+
+        // Every corner of the bounds is in front of the camera and within the view.
+        for corner in [Point::new(-4.0, 0.0, -1.0), Point::new(5.0, 5.0, 4.0)].iter() {
+            let p = c.transform.mul_point(corner);
+            assert!(p.z() < 0.0);
+            assert!((p.x() / -p.z()).abs() <= c.half_width);
+            assert!((p.y() / -p.z()).abs() <= c.half_height);
+        }
+    }
+
+    #[test]
+    fn looking_at_an_empty_scene_leaves_the_camera_unchanged() {
+        let mut c = Camera::new(100, 50, PI / 3.0);
+        c.look_at_scene(&World::new());
+
+        assert_eq!(Matrix::identity(), c.transform);
+    }
+
+    #[test]
+    fn the_aspect_ratio_of_a_camera() {
+        assert_eq!(2.0, Camera::new(200, 100, PI / 2.0).aspect_ratio());
+        assert_eq!(0.5, Camera::new(100, 200, PI / 2.0).aspect_ratio());
+    }
+
+    #[test]
+    fn changing_the_transform_changes_the_rays() {
+        let mut c = Camera::new(201, 101, PI / 2.0);
+        let before = c.ray_for_pixel(100, 50);
+        c.set_transform(translation(0.0, 0.0, 5.0));
+        let after = c.ray_for_pixel(100, 50);
+
+        assert_eq!(translation(0.0, 0.0, 5.0), *c.transform());
+        assert_eq!(Point::new(0.0, 0.0, 0.0), *before.origin());
+        assert_eq!(Point::new(0.0, 0.0, -5.0), *after.origin());
+        assert_vector_eq(*before.direction(), *after.direction());
+
+        c.set_transform(rotation_rad_y(PI / 2.0));
+        let rotated = c.ray_for_pixel(100, 50);
+        assert_vector_eq(Vector::new(1.0, 0.0, 0.0), *rotated.direction());
+    }
+
+    #[test]
+    #[should_panic]
+    fn assigning_a_non_invertible_transform_panics() {
+        let mut c = Camera::new(201, 101, PI / 2.0);
+        c.set_transform(scaling(0.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn rendering_the_depth_of_a_single_sphere() {
+        let mut w = World::new();
+        w.add_object(Box::new(Sphere::new()));
+        let mut c = Camera::new(11, 11, PI / 3.0);
+        c.set_transform(view_transform(&Point::new(0.0, 0.0, -5.0),
+                                       &Point::new(0.0, 0.0, 0.0),
+                                       &Vector::new(0.0, 1.0, 0.0)));
+        let depth = c.render_depth(&w);
+        let center = depth[5][5];
+
+        assert_eq!(11, depth.len());
+        assert!((center - 4.0).abs() < 0.00001);
+        assert!(depth[5][4].is_finite() && center < depth[5][4]);
+        assert!(center < depth[4][5]);
+        for (x, y) in [(0, 0), (10, 0), (0, 10), (10, 10), (0, 5), (5, 0)].iter() {
+            assert!(center < depth[*y][*x]);
+        }
+    }
+
+    #[test]
+    fn rendering_the_normals_of_a_single_sphere() {
+        let mut w = World::new();
+        w.add_object(Box::new(Sphere::new()));
+        let mut c = Camera::new(11, 11, PI / 3.0);
+        c.set_transform(view_transform(&Point::new(0.0, 0.0, -5.0),
+                                       &Point::new(0.0, 0.0, 0.0),
+                                       &Vector::new(0.0, 1.0, 0.0)));
+        let image = c.render_normals(&w);
+
+        // The normal in the center faces the camera, i.e. it's (0, 0, -1).
+        let center = image.pixel_at(5, 5);
+        assert!((center.r() - 0.5).abs() < 0.0001);
+        assert!((center.g() - 0.5).abs() < 0.0001);
+        assert!(center.b().abs() < 0.0001);
+
+        // The normal above the center points upwards (more green).
+        assert!(image.pixel_at(5, 4).g() > center.g());
+
+        // Misses are black.
+        assert_eq!(Color::new(0.0, 0.0, 0.0), image.pixel_at(0, 0));
+    }
+
+    #[test]
+    fn a_single_frame_sequence_matches_a_single_render() {
+        let mut s = Sphere::new();
+        s.set_transform(translation(1.0, 0.0, 2.0));
+        let mut w = World::new();
+        w.add_object(Box::new(s))
+         .add_light(Box::new(PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(-10.0, 10.0, -10.0))));
+        let mut c = Camera::new(11, 11, PI / 3.0);
+
+        let frames = c.render_sequence(&w, 1, 5.0);
+
+        c.set_transform(view_transform(&Point::new(1.0, 0.0, -3.0),
+                                       &Point::new(1.0, 0.0, 2.0),
+                                       &Vector::new(0.0, 1.0, 0.0)));
+        assert_eq!(1, frames.len());
+        assert_eq!(c.render(&w), frames[0]);
+    }
+
+    #[test]
+    fn a_sequence_has_one_canvas_per_frame() {
+        let mut w = World::new();
+        w.add_object(Box::new(Sphere::new()))
+         .add_light(Box::new(PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(-10.0, 10.0, -10.0))));
+        let c = Camera::new(5, 5, PI / 3.0);
+        let frames = c.render_sequence(&w, 4, 5.0);
+
+        assert_eq!(4, frames.len());
+        assert!(c.render_sequence(&w, 0, 5.0).is_empty());
+        // The camera always looks at the sphere in the center.
+        assert!(frames.iter().all(|f| f.pixel_at(2, 2) != Color::new(0.0, 0.0, 0.0)));
+    }
+}
@@ -3,7 +3,7 @@ pub mod color;
 use self::color::Color;
 use super::ppm::{Ppm, PpmColor};
 
-use std::cmp;
+use std::{cmp, io};
 
 
 #[derive(Debug)]
@@ -14,6 +14,18 @@ pub struct Canvas {
 }
 
 impl Canvas {
+    /** The largest difference per color channel for which two colors
+     * are considered equal by [`Canvas::flood_fill`].
+     */
+    pub const FLOOD_FILL_TOLERANCE: f32 = 0.001;
+
+    /** The largest number of pixels [`Canvas::from_ppm`] accepts.
+     *
+     * The header of a PPM is read before the pixels, so without a limit
+     * a malformed header could make it allocate an arbitrary amount of memory.
+     */
+    pub const MAX_PPM_PIXELS: usize = 1 << 26;
+
     /** Create a new Canvas with width and height.
      *
      * All pixels are initialized to black (0, 0, 0).
@@ -43,35 +55,290 @@ impl Canvas {
 
         self.pixels[height][width]
     }
-}
+    
+    /** Fill a rectangle with the given color.
+     *
+     * (x, y) is the top left corner of the rectangle with
+     * width `w` and height `h`. The parts of the rectangle that
+     * lie outside of the canvas are ignored (clipped).
+     */
+    pub fn write_rect(&mut self, x: usize, y: usize, w: usize, h: usize, color: Color) {
+        let x_end = x.saturating_add(w).min(self.width);
+        let y_end = y.saturating_add(h).min(self.height);
 
-impl Ppm for Canvas {
-    fn to_ppm(&self) -> String {
-        const PIXELS_PER_LINE: u32 = 5;
-        let mut pixelcount: u32 = PIXELS_PER_LINE;
+        for row in self.pixels.iter_mut().take(y_end).skip(y) {
+            for pixel in row.iter_mut().take(x_end).skip(x) {
+                *pixel = color;
+            }
+        }
+    }
+    
+    /** Draw a line from (x0, y0) to (x1, y1) with the given color.
+     *
+     * The line is rasterized using Bresenham's line algorithm.
+     * Both end points are included. Pixels outside of the canvas
+     * are ignored (clipped), i.e. the coordinates may also be negative.
+     */
+    pub fn draw_line(&mut self, x0: isize, y0: isize, x1: isize, y1: isize, color: Color) {
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let sx = if x0 < x1 { 1 } else { -1 };
+        let sy = if y0 < y1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        let (mut x, mut y) = (x0, y0);
 
-        let mut ppm = format!("P3\n{} {}\n255\n", self.width, self.height);
-        
-        for rows in &self.pixels {
-            for pixel in rows {
-                
-                pixelcount -= 1;
-                ppm.push_str(&pixel.to_ppm_color()); // convert pixel to a (r, g, b) color string
-
-                if pixelcount == 0 {
-                    pixelcount = PIXELS_PER_LINE;
-                    ppm.push_str("\n");
-                } else {
-                    ppm.push_str(" ");
+        loop {
+            if x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height {
+                self.pixels[y as usize][x as usize] = color;
+            }
+
+            if x == x1 && y == y1 {
+                break;
+            }
+
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+        }
+    }
+    
+    /** Replace the connected region around (x, y) with the given color.
+     *
+     * All pixels that can be reached from the starting pixel via their
+     * four direct neighbours (up, down, left, right) and whose color
+     * matches the color of the starting pixel are set to `fill`. Two
+     * colors match if no channel differs by more than
+     * [`Canvas::FLOOD_FILL_TOLERANCE`].
+     */
+    pub fn flood_fill(&mut self, x: usize, y: usize, fill: Color) {
+        assert!(y < self.height);
+        assert!(x < self.width);
+
+        let target = self.pixels[y][x];
+        let matches = |c: &Color| {
+            (c.r() - target.r()).abs() <= Canvas::FLOOD_FILL_TOLERANCE &&
+            (c.g() - target.g()).abs() <= Canvas::FLOOD_FILL_TOLERANCE &&
+            (c.b() - target.b()).abs() <= Canvas::FLOOD_FILL_TOLERANCE
+        };
+
+        // Remember the visited pixels, otherwise a fill color that
+        // matches the target color would loop forever.
+        let mut visited = vec![vec![false; self.width]; self.height];
+        let mut stack = vec![(x, y)];
+
+        while let Some((x, y)) = stack.pop() {
+            if visited[y][x] || !matches(&self.pixels[y][x]) {
+                continue;
+            }
+            visited[y][x] = true;
+            self.pixels[y][x] = fill;
+
+            if x > 0 { stack.push((x - 1, y)); }
+            if x + 1 < self.width { stack.push((x + 1, y)); }
+            if y > 0 { stack.push((x, y - 1)); }
+            if y + 1 < self.height { stack.push((x, y + 1)); }
+        }
+    }
+    
+    /** Copy the pixels of `top` onto this canvas where the mask is set.
+     *
+     * The mask is given row by row, i.e. `mask[y][x]` decides whether
+     * the pixel (x, y) of `top` replaces the pixel of this canvas.
+     * Both canvases and the mask must have the same size.
+     */
+    pub fn composite_over(&mut self, top: &Canvas, mask: &[Vec<bool>]) {
+        assert!(top.width == self.width && top.height == self.height,
+                "the canvases must have the same size");
+        assert!(mask.len() == self.height && mask.iter().all(|row| row.len() == self.width),
+                "the mask must have the same size as the canvas");
+
+        for ((row, top_row), mask_row) in self.pixels.iter_mut().zip(top.pixels.iter()).zip(mask.iter()) {
+            for ((pixel, top_pixel), set) in row.iter_mut().zip(top_row.iter()).zip(mask_row.iter()) {
+                if *set {
+                    *pixel = *top_pixel;
                 }
             }
         }
-        
-        // last element hast to be a new line
-        ppm.pop();  // removes either newline or space
-        ppm.push('\n');
+    }
+    
+    /** Get the width of the canvas.
+     */
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    
+    /** Get the height of the canvas.
+     */
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    
+    /** Create a canvas from a string in the (plain) Portable Pixmap (PPM) format.
+     *
+     * Lines starting with `#` are treated as comments. Each color
+     * value is scaled by the maximum color value given in the header,
+     * i.e. every channel ends up between 0 and 1.
+     *
+     * Returns None if the string isn't a valid P3 PPM or if it has
+     * more than [`Canvas::MAX_PPM_PIXELS`] pixels.
+     */
+    pub fn from_ppm(ppm: &str) -> Option<Self> {
+        let mut tokens = ppm.lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .flat_map(|line| line.split_whitespace());
+
+        if tokens.next()? != "P3" {
+            return None;
+        }
 
-        ppm
+        let width: usize = tokens.next()?.parse().ok()?;
+        let height: usize = tokens.next()?.parse().ok()?;
+        let max: f32 = tokens.next()?.parse().ok()?;
+
+        if max <= 0.0 {
+            return None;
+        }
+
+        if width.checked_mul(height)? > Self::MAX_PPM_PIXELS {
+            return None;
+        }
+
+        let mut canvas = Canvas::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let r: f32 = tokens.next()?.parse().ok()?;
+                let g: f32 = tokens.next()?.parse().ok()?;
+                let b: f32 = tokens.next()?.parse().ok()?;
+
+                canvas.write_pixel(x, y, Color::new(r / max, g / max, b / max));
+            }
+        }
+
+        Some(canvas)
+    }
+    
+    /** Write the canvas in the (plain) Portable Pixmap (PPM) format.
+     *
+     * The pixels are streamed row by row into the writer, i.e. the
+     * image is never built in memory as a whole. The output is the same
+     * as the one of `to_ppm`. Wrap files into a `BufWriter` to avoid
+     * many small writes.
+     */
+    pub fn write_ppm<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        const PIXELS_PER_LINE: usize = 5;
+        let pixels = self.width * self.height;
+
+        write!(w, "P3\n{} {}\n255\n", self.width, self.height)?;
+
+        for (i, pixel) in self.pixels.iter().flatten().enumerate() {
+            // convert pixel to a (r, g, b) color string
+            w.write_all(pixel.to_ppm_color().as_bytes())?;
+
+            // the last element has to be followed by a new line
+            if (i + 1) % PIXELS_PER_LINE == 0 || i + 1 == pixels {
+                w.write_all(b"\n")?;
+            } else {
+                w.write_all(b" ")?;
+            }
+        }
+
+        Ok(())
+    }
+    
+    /** Create a resized copy of the canvas with the new width and height.
+     *
+     * Nearest-neighbor sampling is used, i.e. each pixel of the new
+     * canvas takes the color of the closest pixel of the original one.
+     * The corner pixels of both canvases are mapped onto each other.
+     * Resizing an empty canvas yields a black canvas.
+     */
+    pub fn resize(&self, new_w: usize, new_h: usize) -> Canvas {
+        let mut canvas = Canvas::new(new_w, new_h);
+
+        if self.width == 0 || self.height == 0 {
+            return canvas;
+        }
+
+        // Map the first and last index of the new canvas onto the first and
+        // last index of the original one (rounded to the nearest index).
+        // A single row/column maps to the first.
+        let source = |i: usize, old: usize, new: usize| {
+            if new == 1 { 0 } else { (i * (old - 1) + (new - 1) / 2) / (new - 1) }
+        };
+
+        for y in 0..new_h {
+            let src_y = source(y, self.height, new_h);
+            for x in 0..new_w {
+                let src_x = source(x, self.width, new_w);
+                canvas.pixels[y][x] = self.pixels[src_y][src_x];
+            }
+        }
+
+        canvas
+    }
+    
+    /** Create a textual preview of the canvas (e.g. for a terminal).
+     *
+     * The canvas is downsampled to `cols` characters per line. Because
+     * characters are about twice as high as wide, each character covers
+     * a block twice as high as wide. The average luminance of a block is
+     * mapped to a character of the ramp `" .:-=+*#%@"`, i.e. black becomes
+     * a space and white becomes `@`. Every line ends with a newline.
+     *
+     * An empty canvas or `cols == 0` yields an empty string.
+     */
+    pub fn to_ascii(&self, cols: usize) -> String {
+        const RAMP: &[u8] = b" .:-=+*#%@";
+
+        let mut ascii = String::new();
+        if cols == 0 || self.width == 0 || self.height == 0 {
+            return ascii;
+        }
+
+        let rows = ((self.height * cols) / (self.width * 2)).max(1);
+        // The (half-open) range of pixels covered by block `i` of `n` blocks.
+        let block = |i: usize, n: usize, size: usize| {
+            let start = i * size / n;
+            (start, ((i + 1) * size / n).max(start + 1).min(size))
+        };
+
+        for row in 0..rows {
+            let (y0, y1) = block(row, rows, self.height);
+            for col in 0..cols {
+                let (x0, x1) = block(col, cols, self.width);
+
+                let mut sum = 0.0;
+                for pixel_row in &self.pixels[y0..y1] {
+                    for c in &pixel_row[x0..x1] {
+                        sum += c.clamp01().luminance();
+                    }
+                }
+                let luminance = sum / ((y1 - y0) * (x1 - x0)) as f32;
+
+                let i = (luminance.clamp(0.0, 1.0) * (RAMP.len() - 1) as f32).round() as usize;
+                ascii.push(RAMP[i] as char);
+            }
+            ascii.push('\n');
+        }
+
+        ascii
+    }
+}
+
+impl Ppm for Canvas {
+    fn to_ppm(&self) -> String {
+        let mut buf = Vec::new();
+        
+        // Writing into a Vec can't fail and the output is plain ASCII.
+        self.write_ppm(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
     }
 }
 
@@ -98,7 +365,7 @@ impl cmp::PartialEq for Canvas {
             }
         }
 
-        return true;
+        true
     }
 }
 
@@ -189,4 +456,296 @@ mod tests {
     fn ends_with_new_line() {
         assert_eq!('\n', Canvas::new(5,3).to_ppm().pop().unwrap()); 
     }
+
+    #[test]
+    fn reading_a_canvas_from_a_ppm() {
+        let ppm = "P3\n# a comment\n2 2\n255\n255 0 0 0 255 0\n0 0 255 255 255 255\n";
+        let c = Canvas::from_ppm(ppm).unwrap();
+
+        assert_eq!(2, c.width());
+        assert_eq!(2, c.height());
+        assert_eq!(Color::new(1.0, 0.0, 0.0), c.pixel_at(0, 0));
+        assert_eq!(Color::new(0.0, 1.0, 0.0), c.pixel_at(1, 0));
+        assert_eq!(Color::new(0.0, 0.0, 1.0), c.pixel_at(0, 1));
+        assert_eq!(Color::new(1.0, 1.0, 1.0), c.pixel_at(1, 1));
+    }
+
+    #[test]
+    fn reading_a_ppm_respects_the_maximum_color_value() {
+        let c = Canvas::from_ppm("P3 1 1 100 50 0 100").unwrap();
+
+        assert_eq!(Color::new(0.5, 0.0, 1.0), c.pixel_at(0, 0));
+    }
+
+    #[test]
+    fn reading_an_invalid_ppm() {
+        assert!(Canvas::from_ppm("P32\n1 1\n255\n0 0 0").is_none());
+        assert!(Canvas::from_ppm("P3\n2 1\n255\n0 0 0").is_none());
+        // Huge dimensions are rejected before any memory is allocated.
+        assert!(Canvas::from_ppm(&format!("P3\n{} {}\n255\n0 0 0", usize::MAX, 2)).is_none());
+        assert!(Canvas::from_ppm("P3\n100000 100000\n255\n0 0 0").is_none());
+    }
+
+    #[test]
+    fn a_ppm_round_trip() {
+        let mut canvas = Canvas::new(5, 3);
+        canvas.write_pixel(0, 0, Color::new(1.0, 0.0, 0.0));
+        canvas.write_pixel(4, 2, Color::new(0.0, 0.0, 1.0));
+
+        assert_eq!(canvas, Canvas::from_ppm(&canvas.to_ppm()).unwrap());
+    }
+
+    #[test]
+    fn writing_a_rectangle_to_a_canvas() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let black = Color::new(0.0, 0.0, 0.0);
+        let mut c = Canvas::new(10, 10);
+        c.write_rect(2, 4, 3, 2, red);
+
+        for y in 0..10 {
+            for x in 0..10 {
+                if (2..5).contains(&x) && (4..6).contains(&y) {
+                    assert_eq!(red, c.pixel_at(x, y));
+                } else {
+                    assert_eq!(black, c.pixel_at(x, y));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn writing_a_rectangle_past_the_canvas_edge() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let black = Color::new(0.0, 0.0, 0.0);
+        let mut c = Canvas::new(5, 3);
+        c.write_rect(3, 1, 10, 10, red);
+
+        assert_eq!(red, c.pixel_at(3, 1));
+        assert_eq!(red, c.pixel_at(4, 2));
+        assert_eq!(black, c.pixel_at(2, 1));
+        assert_eq!(black, c.pixel_at(4, 0));
+
+        // completely outside of the canvas
+        c.write_rect(7, 7, 2, 2, Color::new(0.0, 1.0, 0.0));
+        c.write_rect(usize::MAX, 0, 2, 2, Color::new(0.0, 1.0, 0.0));
+        assert_eq!(black, c.pixel_at(0, 0));
+    }
+
+    fn count_pixels(c: &Canvas, color: Color) -> usize {
+        c.pixels.iter().flatten().filter(|p| **p == color).count()
+    }
+
+    #[test]
+    fn drawing_a_horizontal_line() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let mut c = Canvas::new(10, 10);
+        c.draw_line(1, 3, 6, 3, red);
+
+        for x in 1..=6 {
+            assert_eq!(red, c.pixel_at(x, 3));
+        }
+        assert_eq!(6, count_pixels(&c, red));
+    }
+
+    #[test]
+    fn drawing_a_vertical_line() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let mut c = Canvas::new(10, 10);
+        c.draw_line(4, 8, 4, 2, red);
+
+        for y in 2..=8 {
+            assert_eq!(red, c.pixel_at(4, y));
+        }
+        assert_eq!(7, count_pixels(&c, red));
+    }
+
+    #[test]
+    fn drawing_a_diagonal_line() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let mut c = Canvas::new(10, 10);
+        c.draw_line(0, 0, 5, 5, red);
+
+        for i in 0..=5 {
+            assert_eq!(red, c.pixel_at(i, i));
+        }
+        assert_eq!(6, count_pixels(&c, red));
+    }
+
+    #[test]
+    fn drawing_a_line_past_the_canvas_edge() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let mut c = Canvas::new(5, 5);
+        c.draw_line(-3, 2, 8, 2, red);
+
+        for x in 0..5 {
+            assert_eq!(red, c.pixel_at(x, 2));
+        }
+        assert_eq!(5, count_pixels(&c, red));
+    }
+
+    #[test]
+    fn upscaling_duplicates_pixels_in_blocks() {
+        let colors = [
+            [Color::new(1.0, 0.0, 0.0), Color::new(0.0, 1.0, 0.0)],
+            [Color::new(0.0, 0.0, 1.0), Color::new(1.0, 1.0, 1.0)],
+        ];
+        let mut c = Canvas::new(2, 2);
+        for (y, row) in colors.iter().enumerate() {
+            for (x, color) in row.iter().enumerate() {
+                c.write_pixel(x, y, *color);
+            }
+        }
+
+        let r = c.resize(4, 4);
+
+        assert_eq!(4, r.width());
+        assert_eq!(4, r.height());
+        for y in 0..4 {
+            for x in 0..4 {
+                assert_eq!(colors[y / 2][x / 2], r.pixel_at(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn downscaling_preserves_corners() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let mut c = Canvas::new(10, 10);
+        c.write_pixel(0, 0, red);
+        c.write_pixel(9, 0, red);
+        c.write_pixel(0, 9, red);
+        c.write_pixel(9, 9, red);
+
+        let r = c.resize(3, 3);
+
+        assert_eq!(red, r.pixel_at(0, 0));
+        assert_eq!(red, r.pixel_at(2, 0));
+        assert_eq!(red, r.pixel_at(0, 2));
+        assert_eq!(red, r.pixel_at(2, 2));
+        assert_eq!(4, count_pixels(&r, red));
+    }
+
+    #[test]
+    fn resizing_to_a_single_pixel_takes_the_first_pixel() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let mut c = Canvas::new(4, 4);
+        c.write_pixel(0, 0, red);
+
+        assert_eq!(red, c.resize(1, 1).pixel_at(0, 0));
+    }
+
+    #[test]
+    fn write_ppm_matches_to_ppm() {
+        let mut c = Canvas::new(7, 3);
+        c.write_pixel(0, 0, Color::new(1.5, 0.0, 0.0));
+        c.write_pixel(2, 1, Color::new(0.0, 0.5, 0.0));
+        c.write_pixel(6, 2, Color::new(-0.5, 0.0, 1.0));
+
+        let mut buf: Vec<u8> = Vec::new();
+        c.write_ppm(&mut buf).unwrap();
+
+        assert_eq!(c.to_ppm(), String::from_utf8(buf).unwrap());
+    }
+
+    #[test]
+    fn write_ppm_ends_with_a_newline() {
+        for (w, h) in [(5, 1), (3, 2), (1, 1)].iter() {
+            let mut buf: Vec<u8> = Vec::new();
+            Canvas::new(*w, *h).write_ppm(&mut buf).unwrap();
+
+            let ppm = String::from_utf8(buf).unwrap();
+            assert!(ppm.ends_with("0 0 0\n"));
+            assert!(!ppm.ends_with("\n\n"));
+        }
+    }
+
+    #[test]
+    fn an_all_white_canvas_as_ascii() {
+        let mut c = Canvas::new(20, 10);
+        c.write_rect(0, 0, 20, 10, Color::new(1.0, 1.0, 1.0));
+
+        assert_eq!("@@@@@@@@@@\n@@@@@@@@@@\n", c.to_ascii(10));
+    }
+
+    #[test]
+    fn an_all_black_canvas_as_ascii() {
+        let c = Canvas::new(20, 10);
+
+        assert_eq!("          \n          \n", c.to_ascii(10));
+    }
+
+    #[test]
+    fn a_canvas_as_ascii_follows_the_brightness() {
+        let mut c = Canvas::new(4, 2);
+        c.write_rect(2, 0, 2, 2, Color::new(1.0, 1.0, 1.0));
+
+        assert_eq!(" @\n", c.to_ascii(2));
+        assert_eq!("", c.to_ascii(0));
+    }
+
+    #[test]
+    fn flood_filling_the_interior_of_a_bordered_rectangle() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let blue = Color::new(0.0, 0.0, 1.0);
+        let black = Color::new(0.0, 0.0, 0.0);
+        let mut c = Canvas::new(10, 8);
+        // border of the rectangle from (2, 1) to (7, 6)
+        c.draw_line(2, 1, 7, 1, red);
+        c.draw_line(2, 6, 7, 6, red);
+        c.draw_line(2, 1, 2, 6, red);
+        c.draw_line(7, 1, 7, 6, red);
+        // a slightly different shade still counts as the same color
+        c.write_pixel(4, 3, Color::new(0.0005, 0.0, 0.0));
+
+        c.flood_fill(4, 4, blue);
+
+        for y in 0..8 {
+            for x in 0..10 {
+                let expected = if (3..7).contains(&x) && (2..6).contains(&y) {
+                    blue
+                } else if (2..8).contains(&x) && (1..7).contains(&y) {
+                    red
+                } else {
+                    black
+                };
+                assert_eq!(expected, c.pixel_at(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn flood_filling_with_the_same_color_terminates() {
+        let mut c = Canvas::new(4, 4);
+        c.flood_fill(0, 0, Color::new(0.0, 0.0, 0.0));
+
+        assert_eq!(16, count_pixels(&c, Color::new(0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn compositing_a_canvas_with_a_checkerboard_mask() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let black = Color::new(0.0, 0.0, 0.0);
+        let mut bottom = Canvas::new(4, 3);
+        let mut top = Canvas::new(4, 3);
+        top.write_rect(0, 0, 4, 3, red);
+        let mask: Vec<Vec<bool>> = (0..3).map(|y| (0..4).map(|x| (x + y) % 2 == 0).collect()).collect();
+
+        bottom.composite_over(&top, &mask);
+
+        for y in 0..3 {
+            for x in 0..4 {
+                let expected = if (x + y) % 2 == 0 { red } else { black };
+                assert_eq!(expected, bottom.pixel_at(x, y));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn compositing_with_a_mask_of_the_wrong_size_panics() {
+        let mut bottom = Canvas::new(2, 2);
+        let top = Canvas::new(2, 2);
+
+        bottom.composite_over(&top, &[vec![true, true]]);
+    }
 }
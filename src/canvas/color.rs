@@ -29,25 +29,154 @@ impl Color {
     pub fn new(red: f32, green: f32, blue: f32) -> Self {
         Color { r: red, g: green, b: blue }
     }
+    
+    /** Get the red component.
+     */
+    pub fn r(&self) -> f32 {
+        self.r
+    }
+    
+    /** Get the green component.
+     */
+    pub fn g(&self) -> f32 {
+        self.g
+    }
+    
+    /** Get the blue component.
+     */
+    pub fn b(&self) -> f32 {
+        self.b
+    }
+    
+    /** Clamp each channel to the range [0, 1].
+     *
+     * Values below 0 become 0 and values above 1 become 1.
+     */
+    pub fn clamp01(&self) -> Self {
+        Color::new(self.r.clamp(0.0, 1.0), self.g.clamp(0.0, 1.0), self.b.clamp(0.0, 1.0))
+    }
+    
+    /** Add two colors and limit each channel to 1.
+     *
+     * Unlike `+` the result never exceeds 1, e.g. adding white to
+     * white results in white instead of (2, 2, 2). Only the upper
+     * limit is applied, negative channels are left as they are.
+     */
+    pub fn add_saturating(&self, other: &Color) -> Self {
+        Color::new((self.r + other.r).min(1.0),
+                   (self.g + other.g).min(1.0),
+                   (self.b + other.b).min(1.0))
+    }
+    
+    /** Convert the color into (r, g, b) bytes.
+     *
+     * Each channel is clamped to [0, 1] first and
+     * then scaled to [0, 255]. The scaled value is rounded
+     * to the nearest integer (halves are rounded up), e.g.
+     * 0.5 -> 127.5 -> 128.
+     */
+    pub fn to_bytes(&self) -> [u8; 3] {
+        const MAX: f32 = 255.0;
+
+        let c = self.clamp01();
+        let scale = |i: f32| -> u8 { (i * MAX).round() as u8 };
+
+        [scale(c.r), scale(c.g), scale(c.b)]
+    }
+    
+    /** Get the channels as [red, green, blue].
+     *
+     * Together with `from_channels` this allows processing
+     * all channels the same way, e.g. for gamma correction.
+     */
+    pub fn channels(&self) -> [f32; 3] {
+        [self.r, self.g, self.b]
+    }
+    
+    /** Create a color from its channels [red, green, blue].
+     */
+    pub fn from_channels(channels: [f32; 3]) -> Self {
+        Color::new(channels[0], channels[1], channels[2])
+    }
+    
+    /** Get the relative luminance (perceived brightness) of the color.
+     *
+     * The channels are weighted using the Rec. 709 coefficients,
+     * i.e. `0.2126 * r + 0.7152 * g + 0.0722 * b`. White has a
+     * luminance of 1 and black a luminance of 0.
+     */
+    pub fn luminance(&self) -> f32 {
+        0.2126 * self.r + 0.7152 * self.g + 0.0722 * self.b
+    }
+    
+    /** Linearly interpolate between this color and `other`.
+     *
+     * The channels are interpolated as they are, i.e. both colors
+     * are treated as linear colors (which is what the renderer uses).
+     * `t = 0` returns this color and `t = 1` returns `other`.
+     */
+    pub fn lerp(&self, other: &Color, t: f32) -> Color {
+        *self + (*other - *self) * t
+    }
+    
+    /** Interpolate between two sRGB encoded colors.
+     *
+     * Both colors are treated as sRGB encoded (e.g. colors picked in
+     * an image editor). They are converted to linear space, interpolated
+     * there and the result is converted back to sRGB. Unlike `lerp`
+     * this is physically correct and avoids too dark transitions at
+     * the edges of patterns, e.g. blending black and white with
+     * `t = 0.5` results in ~0.735 instead of 0.5.
+     */
+    pub fn blend_srgb(&self, other: &Color, t: f32) -> Color {
+        let a = Color::from_channels(self.channels().map(srgb_to_linear));
+        let b = Color::from_channels(other.channels().map(srgb_to_linear));
+
+        Color::from_channels(a.lerp(&b, t).channels().map(linear_to_srgb))
+    }
+}
+
+/** Decode a sRGB encoded channel into linear space.
+ */
+fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/** Encode a linear channel using the sRGB transfer function.
+ */
+fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
 }
 
 impl PpmColor for Color {
     fn to_ppm_color(&self) -> String {
-        const MAX: f32 = 255.0;
+        let [r, g, b] = self.to_bytes();
+        
+        format!("{} {} {}", r, g, b)
+    }
+}
 
-        let normalize = |i: f32| -> f32
-            {   
-                if i < 0.0 {
-                    0.0
-                } else if i > 1.0 {
-                    MAX
-                } else {
-                    (i * MAX).ceil()
-                }
-            };
+/** Treat an array as (r, g, b) color.
+ */
+impl PpmColor for [f32; 3] {
+    fn to_ppm_color(&self) -> String {
+        Color::new(self[0], self[1], self[2]).to_ppm_color()
+    }
+}
 
-        
-        format!("{} {} {}", normalize(self.r), normalize(self.g), normalize(self.b))
+/** Treat a tuple as (r, g, b) color.
+ */
+impl PpmColor for (f32, f32, f32) {
+    fn to_ppm_color(&self) -> String {
+        Color::new(self.0, self.1, self.2).to_ppm_color()
     }
 }
 
@@ -151,4 +280,116 @@ mod tests {
     fn to_ppm_color_tuple_3() {
         assert_eq!(String::from("255 128 0"), Color::new(1.5, 0.5, -0.5).to_ppm_color());
     }
+
+    #[test]
+    fn clamping_a_color() {
+        assert_eq!(Color::new(1.0, 0.0, 0.5), Color::new(1.5, -0.5, 0.5).clamp01());
+    }
+
+    #[test]
+    fn to_bytes_clamps_over_bright_channels() {
+        assert_eq!([255, 255, 255], Color::new(1.5, 1.0, 100.0).to_bytes());
+    }
+
+    #[test]
+    fn to_bytes_clamps_negative_channels() {
+        assert_eq!([0, 0, 0], Color::new(-0.5, 0.0, -100.0).to_bytes());
+    }
+
+    #[test]
+    fn to_bytes_scales_in_range_channels() {
+        assert_eq!([255, 128, 0], Color::new(1.0, 0.5, 0.0).to_bytes());
+    }
+
+    #[test]
+    fn to_bytes_rounds_to_the_nearest_integer() {
+        assert_eq!([0, 0, 0], Color::new(0.0, 0.0, 0.0).to_bytes());
+        assert_eq!([128, 128, 128], Color::new(0.5, 0.5, 0.5).to_bytes());
+        assert_eq!([191, 191, 191], Color::new(0.75, 0.75, 0.75).to_bytes());
+        assert_eq!([255, 255, 255], Color::new(1.0, 1.0, 1.0).to_bytes());
+    }
+
+    #[test]
+    fn to_bytes_does_not_bias_upwards() {
+        // 0.502 * 255 = 128.01 which would be 129 with ceiling
+        assert_eq!([128, 128, 128], Color::new(0.502, 0.502, 0.502).to_bytes());
+    }
+
+    #[test]
+    fn array_to_ppm_color() {
+        assert_eq!(Color::new(1.0, 0.0, 0.5).to_ppm_color(), [1.0, 0.0, 0.5].to_ppm_color());
+        assert_eq!(String::from("255 128 0"), [1.5, 0.5, -0.5].to_ppm_color());
+    }
+
+    #[test]
+    fn tuple_to_ppm_color() {
+        assert_eq!(Color::new(1.0, 0.0, 0.5).to_ppm_color(), (1.0, 0.0, 0.5).to_ppm_color());
+        assert_eq!(String::from("255 128 0"), (1.5, 0.5, -0.5).to_ppm_color());
+    }
+
+    #[test]
+    fn round_tripping_through_channels() {
+        let c = Color::new(-0.5, 0.4, 1.7);
+
+        assert_eq!([-0.5, 0.4, 1.7], c.channels());
+        assert_eq!(c, Color::from_channels(c.channels()));
+    }
+
+    #[test]
+    fn processing_channels_generically() {
+        let c = Color::new(0.25, 0.5, 1.0);
+        let mut channels = c.channels();
+        for ch in channels.iter_mut() {
+            *ch = ch.sqrt();
+        }
+
+        assert_eq!(Color::new(0.5, 0.70710677, 1.0), Color::from_channels(channels));
+    }
+
+    #[test]
+    fn the_luminance_of_a_color() {
+        assert!((Color::new(1.0, 1.0, 1.0).luminance() - 1.0).abs() < 0.00001);
+        assert_eq!(0.0, Color::new(0.0, 0.0, 0.0).luminance());
+        assert!(Color::new(0.0, 1.0, 0.0).luminance() > Color::new(1.0, 0.0, 0.0).luminance());
+        assert!(Color::new(1.0, 0.0, 0.0).luminance() > Color::new(0.0, 0.0, 1.0).luminance());
+    }
+
+    #[test]
+    fn linear_and_srgb_blending_differ_for_black_and_white() {
+        let black = Color::new(0.0, 0.0, 0.0);
+        let white = Color::new(1.0, 1.0, 1.0);
+
+        // lerp treats the colors as linear values
+        assert_eq!(Color::new(0.5, 0.5, 0.5), black.lerp(&white, 0.5));
+
+        // blend_srgb interpolates in linear space, i.e. the sRGB encoded
+        // result is brighter than the naive average
+        let c = black.blend_srgb(&white, 0.5);
+        assert!((c.r() - 0.735357).abs() < 0.0001);
+        assert_ne!(black.lerp(&white, 0.5), c);
+    }
+
+    #[test]
+    fn both_blends_return_the_end_points() {
+        let a = Color::new(0.2, 0.4, 0.6);
+        let b = Color::new(0.9, 0.1, 0.0);
+
+        assert_eq!(a, a.lerp(&b, 0.0));
+        assert_eq!(b, a.lerp(&b, 1.0));
+        for (x, y) in a.blend_srgb(&b, 0.0).channels().iter().zip(a.channels().iter()) {
+            assert!((x - y).abs() < 0.00001);
+        }
+        for (x, y) in a.blend_srgb(&b, 1.0).channels().iter().zip(b.channels().iter()) {
+            assert!((x - y).abs() < 0.00001);
+        }
+    }
+
+    #[test]
+    fn adding_colors_with_saturation() {
+        let white = Color::new(1.0, 1.0, 1.0);
+
+        assert_eq!(white, white.add_saturating(&white));
+        assert_eq!(Color::new(2.0, 2.0, 2.0), white + white);
+        assert_eq!(Color::new(0.75, 1.0, 0.5), Color::new(0.5, 0.75, 0.25).add_saturating(&Color::new(0.25, 0.5, 0.25)));
+    }
 }
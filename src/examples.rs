@@ -1,3 +1,4 @@
 pub mod projectile;
 pub mod clock;
 pub mod sphere;
+pub mod glass;
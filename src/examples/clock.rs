@@ -1,7 +1,6 @@
 use sugar_ray::math::{
     point::Point, 
-    vector::Vector, 
-    matrix::{Matrix, transformation::*},
+    matrix::Matrix,
 };
 use sugar_ray::canvas::{
     *,
@@ -0,0 +1,87 @@
+use sugar_ray::prelude::*;
+use sugar_ray::patterns::uv::{uv_checkers, planar_map, TextureMap};
+use std::io::BufWriter;
+
+// There is no plane shape, so the floor and the background wall are
+// huge spheres.
+fn glass_world() -> World {
+    let mut w = World::new();
+
+    // FLOOR (checkered)
+    // The floor is scaled by 1000, so 1000 squares per unit of object
+    // space result in squares of one unit in world space.
+    let mut floor_material = Material::new(Color::new(0.9, 0.9, 0.9), 0.1, 0.9, 0.0, 200.0);
+    floor_material.set_reflective(0.2);
+    floor_material.set_pattern(Box::new(TextureMap::new(uv_checkers(1000,
+                                                                     1000,
+                                                                     Color::new(0.9, 0.9, 0.9),
+                                                                     Color::new(0.1, 0.1, 0.1)),
+                                                        planar_map)));
+    let mut floor = Sphere::new();
+    floor.set_transform(translation(0.0, -1001.0, 0.0) * scaling(1000.0, 1000.0, 1000.0));
+    floor.set_material(floor_material);
+
+    // BACKGROUND
+    let mut wall = Sphere::new();
+    wall.set_transform(translation(0.0, 0.0, 1012.0) * scaling(1000.0, 1000.0, 1000.0));
+    wall.set_material(Material::new(Color::new(0.2, 0.4, 0.8), 0.3, 0.7, 0.0, 200.0));
+
+    // GLASS SPHERE (both reflective and transparent, i.e. blended using Schlick)
+    let mut glass_material = Material::new(Color::new(0.05, 0.05, 0.05), 0.0, 0.1, 1.0, 300.0);
+    glass_material.set_reflective(0.9);
+    glass_material.set_transparency(0.9);
+    glass_material.set_refractive_index(1.5);
+    let mut glass = Sphere::new();
+    glass.set_material(glass_material);
+
+    // Two colored spheres behind the glass to show the refraction
+    let mut red = Sphere::new();
+    red.set_transform(translation(-1.5, -0.5, 4.0) * scaling(0.5, 0.5, 0.5));
+    red.set_material_color(Color::new(1.0, 0.2, 0.2));
+    let mut green = Sphere::new();
+    green.set_transform(translation(1.5, -0.5, 4.0) * scaling(0.5, 0.5, 0.5));
+    green.set_material_color(Color::new(0.2, 1.0, 0.2));
+
+    w.add_object(Box::new(floor))
+     .add_object(Box::new(wall))
+     .add_object(Box::new(glass))
+     .add_object(Box::new(red))
+     .add_object(Box::new(green))
+     .add_light(Box::new(PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(-10.0, 10.0, -10.0))));
+    w
+}
+
+fn glass_camera(width: usize, height: usize) -> Camera {
+    let mut camera = Camera::new(width, height, std::f64::consts::PI / 3.0);
+    camera.set_transform(view_transform(&Point::new(0.0, 1.5, -6.0),
+                                        &Point::new(0.0, 0.0, 1.0),
+                                        &Vector::new(0.0, 1.0, 0.0)));
+    camera
+}
+
+pub fn render_glass(width: usize, height: usize) -> std::io::Result<()> {
+    let canvas = glass_camera(width, height).render(&glass_world());
+
+    let mut f = BufWriter::new(std::fs::File::create("images/glass.ppm")?);
+    canvas.write_ppm(&mut f)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rendering_the_glass_scene_at_a_small_resolution() {
+        let canvas = glass_camera(32, 18).render(&glass_world());
+
+        // The center looks through the glass sphere, so it isn't black.
+        let c = canvas.pixel_at(16, 9);
+        assert!(c.r() + c.g() + c.b() > 0.0);
+
+        // The floor in the bottom row shows both colors of the checkers.
+        let brightness: Vec<f32> = (0..32).map(|x| canvas.pixel_at(x, 17).g()).collect();
+        let darkest = brightness.iter().cloned().fold(f32::INFINITY, f32::min);
+        let brightest = brightness.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+        assert!(brightest - darkest > 0.3, "{:?}", brightness);
+    }
+}
@@ -1,15 +1,4 @@
-use sugar_ray::{
-    math::{
-        point::Point,
-        vector::Vector,
-    },
-    shapes::Sphere,
-    ray::{Ray, intersection::{Intersection, Intersections}},
-    canvas::{*, color::*,},
-    ppm::*,
-    materials::*,
-    light::*,
-};
+use sugar_ray::prelude::*;
 use std::io::prelude::*;
 
 pub fn render_sphere(canvas_size: usize) -> std::io::Result<()> {
@@ -47,18 +36,10 @@ pub fn render_sphere(canvas_size: usize) -> std::io::Result<()> {
             v.norm();
             let ray = Ray::new(ray_origin, v);
 
-            if let Some(xs) = ray.intersect_sphere(&s) {
-                if let Some(mut hit) = xs.hit() {
+            if let Some((_, p, n)) = s.hit_details(&ray) {
+                let eye = *ray.direction() * (-1.0);
 
-                    let p = ray.position(hit.t());
-                    let n = hit.obj().normal_at(p.clone());
-                    let eye = *ray.direction() * (-1.0);
-
-                    canvas.write_pixel(x, y, Material::lighting(hit.obj().get_material(), &light, &p, &eye, &n));
-                } else {
-                    canvas.write_pixel(x, y, Color::new(0.0, 0.0, 0.0));
-                }
-                
+                canvas.write_pixel(x, y, Material::lighting(s.get_material(), &s, &light, &p, &eye, &n));
             }
         }
     }
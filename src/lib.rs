@@ -1,3 +1,20 @@
+//! A ray tracer following "The Ray Tracer Challenge".
+//!
+//! The most common math types are re-exported at the crate root:
+//!
+//! ```
+//! use sugar_ray::{Point, Vector, Matrix};
+//!
+//! let t = Matrix::identity();
+//! let p = Point::new(1.0, 2.0, 3.0);
+//!
+//! assert_eq!(Point::new(2.0, 2.0, 3.0), t * p + Vector::new(1.0, 0.0, 0.0));
+//!
+//! // The full paths still refer to the same types.
+//! let q: sugar_ray::math::point::Point = p;
+//! assert_eq!(p, q);
+//! ```
+
 pub mod math;
 pub mod canvas;
 pub mod ppm;
@@ -5,3 +22,16 @@ pub mod ray;
 pub mod shapes;
 pub mod light;
 pub mod materials;
+pub mod world;
+pub mod camera;
+pub mod patterns;
+pub mod util;
+pub mod prelude;
+
+pub use math::{
+    point::Point,
+    vector::Vector,
+    matrix::Matrix,
+};
+pub use canvas::{Canvas, color::Color};
+pub use ray::Ray;
@@ -1,16 +1,46 @@
 use crate::math::{
     point::Point,
+    vector::Vector,
 };
 use crate::canvas::color::Color;
 
+/// A light source that can illuminate a material.
+pub trait Light {
+    /// Get the lights intensity.
+    fn intensity(&self) -> &Color;
+
+    /// Get the lights position.
+    fn position(&self) -> &Point;
+
+    /// The fraction of the intensity that reaches the given point.
+    ///
+    /// This factor is applied to the diffuse and specular contribution
+    /// of a material. By default the light reaches every point with full
+    /// intensity.
+    ///
+    /// # Arguments
+    ///
+    /// * `_point` - The lit point (in world space)
+    fn falloff(&self, _point: &Point) -> f64 {
+        1.0
+    }
+}
+
 /// A light source without a size.
 ///
 /// This light source exists at a single point in space
 /// and is defined by its `intensity` (how bright it is/ its color)
 /// and `position`.
+///
+/// The intensity can optionally be attenuated by the distance `d` to
+/// the lit point using `1 / (kc + kl * d + kq * d^2)`. By default
+/// `kc = 1`, `kl = 0` and `kq = 0`, i.e. there is no attenuation.
 pub struct PointLight {
     intensity: Color,
     position: Point,
+    constant: f64,
+    linear: f64,
+    quadratic: f64,
 }
 
 impl PointLight {
@@ -21,7 +51,7 @@ impl PointLight {
     /// * `intensity` - The color / brightness
     /// * `position` - The position of the light source in space
     pub fn new(intensity: Color, position: Point) -> Self {
-        Self { intensity, position }
+        Self { intensity, position, constant: 1.0, linear: 0.0, quadratic: 0.0 }
     }
     
     /// Get the point lights intensity.
@@ -33,15 +63,161 @@ impl PointLight {
     pub fn position(&self) -> &Point {
         &self.position
     }
+    
+    /// Get the constant, linear and quadratic attenuation factors.
+    pub fn attenuation(&self) -> (f64, f64, f64) {
+        (self.constant, self.linear, self.quadratic)
+    }
+    
+    /// Set the attenuation factors of the light.
+    ///
+    /// # Arguments
+    ///
+    /// * `kc` - The constant factor
+    /// * `kl` - The linear factor (multiplied by the distance)
+    /// * `kq` - The quadratic factor (multiplied by the squared distance)
+    ///
+    /// # Panics
+    ///
+    /// Panics if a factor is negative or not finite, or if all factors
+    /// are zero. Otherwise the falloff could become infinite, `NaN` or
+    /// negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     light::{Light, PointLight},
+    ///     canvas::color::Color,
+    ///     math::point::Point,
+    /// };
+    ///
+    /// let mut light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, 0.0));
+    /// light.set_attenuation(1.0, 0.0, 1.0);
+    ///
+    /// assert_eq!(0.5, light.falloff(&Point::new(0.0, 1.0, 0.0)));
+    /// ```
+    pub fn set_attenuation(&mut self, kc: f64, kl: f64, kq: f64) {
+        assert!([kc, kl, kq].iter().all(|k| k.is_finite() && *k >= 0.0),
+                "the attenuation factors must be finite and non-negative, but are ({}, {}, {})",
+                kc, kl, kq);
+        assert!(kc > 0.0 || kl > 0.0 || kq > 0.0, "at least one attenuation factor must be greater than zero");
+
+        self.constant = kc;
+        self.linear = kl;
+        self.quadratic = kq;
+    }
+}
+
+impl Light for PointLight {
+    fn intensity(&self) -> &Color {
+        &self.intensity
+    }
+
+    fn position(&self) -> &Point {
+        &self.position
+    }
+
+    fn falloff(&self, point: &Point) -> f64 {
+        let d = (*point - self.position).mag();
+
+        1.0 / (self.constant + self.linear * d + self.quadratic * d * d)
+    }
+}
+
+/// A light source that shines in a specific direction.
+///
+/// The light is emitted from a single point in space and forms a
+/// cone around its `direction`. Points within the `inner_angle` of
+/// the cone are fully lit, points outside the `outer_angle` don't
+/// receive any light and in between the intensity falls off smoothly.
+///
+/// Both angles are measured in __radians__ from the direction of the light.
+pub struct SpotLight {
+    position: Point,
+    direction: Vector,
+    intensity: Color,
+    inner_angle: f64,
+    outer_angle: f64,
+}
+
+impl SpotLight {
+    /// Create a new spot light.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position of the light source in space
+    /// * `direction` - The direction the light is pointing to
+    /// * `intensity` - The color / brightness
+    /// * `inner_angle` - The angle (in radians) of the fully lit cone
+    /// * `outer_angle` - The angle (in radians) at which the light falls off to zero
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     light::SpotLight,
+    ///     canvas::color::Color,
+    ///     math::{point::Point, vector::Vector, matrix::transformation::radians},
+    /// };
+    ///
+    /// let light = SpotLight::new(Point::new(0.0, 10.0, 0.0),
+    ///                            Vector::new(0.0, -1.0, 0.0),
+    ///                            Color::new(1.0, 1.0, 1.0),
+    ///                            radians(15.0),
+    ///                            radians(30.0));
+    /// ```
+    pub fn new(position: Point, direction: Vector, intensity: Color, inner_angle: f64, outer_angle: f64) -> Self {
+        assert!(inner_angle <= outer_angle, "The inner angle must not be greater than the outer angle.");
+
+        Self { position, direction: direction.norm_cpy(), intensity, inner_angle, outer_angle }
+    }
+
+    /// Get the spot lights (normalized) direction.
+    pub fn direction(&self) -> &Vector {
+        &self.direction
+    }
+
+    /// Get the angle of the fully lit cone.
+    pub fn inner_angle(&self) -> f64 {
+        self.inner_angle
+    }
+
+    /// Get the angle at which the light falls off to zero.
+    pub fn outer_angle(&self) -> f64 {
+        self.outer_angle
+    }
+}
+
+impl Light for SpotLight {
+    fn intensity(&self) -> &Color {
+        &self.intensity
+    }
+
+    fn position(&self) -> &Point {
+        &self.position
+    }
+
+    fn falloff(&self, point: &Point) -> f64 {
+        let to_point = (*point - self.position).norm_cpy();
+        let angle = self.direction.dot(&to_point).clamp(-1.0, 1.0).acos();
+
+        if angle <= self.inner_angle {
+            1.0
+        } else if angle >= self.outer_angle {
+            0.0
+        } else {
+            // smoothstep between the outer and inner angle
+            let x = (self.outer_angle - angle) / (self.outer_angle - self.inner_angle);
+            x * x * (3.0 - 2.0 * x)
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
     use crate::{
-        math::{
-            vector::Vector,
-            point::Point,
-        },
+        math::point::Point,
         canvas::color::Color,
         light::*,
     };
@@ -55,4 +231,59 @@ mod test {
         assert_eq!(Color::new(1.0, 1.0, 1.0), *point_light.intensity());
         assert_eq!(Point::new(0.0, 0.0, 0.0), *point_light.position());
     }
+
+    #[test]
+    fn a_point_light_reaches_every_point() {
+        let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, 0.0));
+
+        assert_eq!(1.0, light.falloff(&Point::new(5.0, -3.0, 2.0)));
+    }
+
+    #[test]
+    fn the_falloff_of_a_spot_light() {
+        let light = SpotLight::new(Point::new(0.0, 0.0, 0.0),
+                                   Vector::new(0.0, 0.0, 1.0),
+                                   Color::new(1.0, 1.0, 1.0),
+                                   std::f64::consts::PI / 8.0,
+                                   std::f64::consts::PI / 4.0);
+
+        // on the axis of the cone
+        assert_eq!(1.0, light.falloff(&Point::new(0.0, 0.0, 5.0)));
+        // behind the light
+        assert_eq!(0.0, light.falloff(&Point::new(0.0, 0.0, -5.0)));
+        // in between the inner and outer cone
+        let f = light.falloff(&Point::new(0.0, (3.0 * std::f64::consts::PI / 16.0).tan(), 1.0));
+        assert!(f > 0.0 && f < 1.0);
+    }
+
+    #[test]
+    fn a_point_light_is_attenuated_by_distance() {
+        let mut light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, 0.0));
+        light.set_attenuation(1.0, 0.5, 0.25);
+
+        assert_eq!((1.0, 0.5, 0.25), light.attenuation());
+        assert_eq!(1.0, light.falloff(&Point::new(0.0, 0.0, 0.0)));
+        assert_eq!(1.0 / 3.0, light.falloff(&Point::new(0.0, 2.0, 0.0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn attenuation_factors_can_not_all_be_zero() {
+        let mut light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, 0.0));
+        light.set_attenuation(0.0, 0.0, 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn attenuation_factors_can_not_be_negative() {
+        let mut light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, 0.0));
+        light.set_attenuation(1.0, -0.5, 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn attenuation_factors_can_not_be_nan() {
+        let mut light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, 0.0));
+        light.set_attenuation(1.0, 0.0, f64::NAN);
+    }
 }
@@ -1,22 +1,24 @@
 pub mod examples;
 
-use examples::{
-    projectile, // fire a projectile over a canvas
-    clock::Clock,
-    sphere,
-};
+// The other demos are commented out below, so only the
+// glass demo is imported.
+use examples::glass;
 
 fn main() -> std::io::Result<()> {
     // Porjectile Demo
-    //projectile::fire()   
+    //examples::projectile::fire()   
    
     /* Clock Demo
-    let mut c = Clock::new(50, 3.5, 30.0);
+    let mut c = examples::clock::Clock::new(50, 3.5, 30.0);
     c.draw_clock_face();
     c.draw_clockhand();
     c.out()
     */
 
-    /* Sphere Demo */
-    sphere::render_sphere(1024)
+    /* Sphere Demo
+    examples::sphere::render_sphere(1024)
+    */
+
+    /* Glass Demo (reflection and refraction) */
+    glass::render_glass(640, 360)
 }
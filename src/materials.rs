@@ -4,10 +4,14 @@ use crate::math::{
     vector::Vector,
     point::Point,
 };
+use crate::math::matrix::Matrix;
+use crate::patterns::Pattern;
+use crate::shapes::Shape;
 
 /// A material encapsulates specific surface properties
-/// like `color`, `ambient`, `diffuse`, `specular` and
-/// `shininess`.
+/// like `color`, `ambient`, `diffuse`, `specular`,
+/// `shininess`, `specular_color`, `emissive`, `reflective`,
+/// `transparency` and `refractive_index`.
 ///
 /// # Properties
 ///
@@ -16,32 +20,113 @@ use crate::math::{
 /// * `diffuse` - Value between 0 and 1
 /// * `specular` - Value between 0 and 1
 /// * `shininess` - Value between 10 (very large highlight) and 200 (very small highlight)
-#[derive(Debug, PartialEq)]
+/// * `specular_color` - The tint of the specular highlight (white by default)
+/// * `emissive` - The color the surface emits by itself (black by default)
+/// * `reflective` - Value between 0 (non-reflective) and 1 (mirror)
+/// * `transparency` - Value between 0 (opaque) and 1 (fully transparent)
+/// * `refractive_index` - How much light bends when entering the material (1 = vacuum)
+/// * `pattern` - An optional pattern that replaces the surface color
+/// * `casts_shadow` - Whether the material blocks light for shadows (true by default)
 pub struct Material {
     color: Color,
     ambient: f64,
     diffuse: f64,
     specular: f64,
     shininess: f64,
+    specular_color: Color,
+    emissive: Color,
+    reflective: f64,
+    transparency: f64,
+    refractive_index: f64,
+    pattern: Option<Box<dyn Pattern>>,
+    pattern_inverse: Matrix, // cached inverse of the pattern transformation
+    casts_shadow: bool,
 }
 
 impl Material {
+    /// The smallest shininess used when calculating the lighting.
+    pub const MIN_SHININESS: f64 = 1.0;
+
+    /// The largest shininess used when calculating the lighting.
+    pub const MAX_SHININESS: f64 = 10_000.0;
+
     /// Create a new material.
+    ///
+    /// The specular highlight isn't tinted, i.e. the `specular_color` is white,
+    /// and the material doesn't emit any light, i.e. `emissive` is black.
+    /// The material is neither reflective nor transparent and has the
+    /// refractive index of a vacuum.
     pub fn new(color: Color, ambient: f64, diffuse: f64, specular: f64, shininess: f64) -> Self {
-        Self { color, ambient, diffuse, specular, shininess }
-    }
-
-    /// Create a material with default attributes.
-    pub fn default() -> Self {
-        Self {
-            color: Color::new(1.0, 1.0, 1.0),
-            ambient: 0.1,
-            diffuse: 0.9,
-            specular: 0.9,
-            shininess: 200.0
+        Self { 
+            color, 
+            ambient, 
+            diffuse, 
+            specular, 
+            shininess, 
+            specular_color: Color::new(1.0, 1.0, 1.0),
+            emissive: Color::new(0.0, 0.0, 0.0),
+            reflective: 0.0,
+            transparency: 0.0,
+            refractive_index: 1.0,
+            pattern: None,
+            pattern_inverse: Matrix::identity(),
+            casts_shadow: true,
         }
     }
     
+    /// Create a matte (diffuse only) material.
+    ///
+    /// The material has an ambient of 0.1, a diffuse of 0.9, no specular
+    /// highlight (specular 0.0, shininess 10.0) and isn't reflective.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The surface color
+    pub fn matte(color: Color) -> Self {
+        Material::new(color, 0.1, 0.9, 0.0, 10.0)
+    }
+    
+    /// Create a shiny material, e.g. polished plastic.
+    ///
+    /// The material has an ambient of 0.1, a diffuse of 0.6, a small,
+    /// bright highlight (specular 0.9, shininess 300.0) and a
+    /// reflectivity of 0.1.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The surface color
+    pub fn shiny(color: Color) -> Self {
+        let mut m = Material::new(color, 0.1, 0.6, 0.9, 300.0);
+        m.reflective = 0.1;
+        m
+    }
+    
+    /// Create a perfect mirror.
+    ///
+    /// The material is black with an ambient and diffuse of 0.0, so its
+    /// color only comes from the reflection (reflective 1.0) and a small
+    /// highlight (specular 1.0, shininess 300.0).
+    pub fn mirror() -> Self {
+        let mut m = Material::new(Color::new(0.0, 0.0, 0.0), 0.0, 0.0, 1.0, 300.0);
+        m.reflective = 1.0;
+        m
+    }
+    
+    /// Create a clear glass material.
+    ///
+    /// The material is black with an ambient of 0.0 and a diffuse of 0.1,
+    /// a small highlight (specular 1.0, shininess 300.0), a reflectivity
+    /// of 0.9, a transparency of 0.9 and a refractive index of 1.5. Glass
+    /// doesn't cast a shadow, i.e. light passes through it.
+    pub fn glass() -> Self {
+        let mut m = Material::new(Color::new(0.0, 0.0, 0.0), 0.0, 0.1, 1.0, 300.0);
+        m.reflective = 0.9;
+        m.transparency = 0.9;
+        m.refractive_index = 1.5;
+        m.casts_shadow = false;
+        m
+    }
+    
     /// Get the materials color
     pub fn color(&self) -> &Color {
         &self.color
@@ -72,66 +157,468 @@ impl Material {
         self.shininess
     }
     
+    /// Get the shininess that is actually used for lighting.
+    ///
+    /// The shininess is clamped between [`Material::MIN_SHININESS`] and
+    /// [`Material::MAX_SHININESS`], a `NaN` is treated as the minimum.
+    fn effective_shininess(&self) -> f64 {
+        if self.shininess.is_nan() {
+            Material::MIN_SHININESS
+        } else {
+            self.shininess.clamp(Material::MIN_SHININESS, Material::MAX_SHININESS)
+        }
+    }
+    
+    /// Get the materials specular color
+    pub fn specular_color(&self) -> &Color {
+        &self.specular_color
+    }
+    
+    /// Set a new specular color
+    ///
+    /// The specular highlight is multiplied by this color, i.e.
+    /// a red specular color results in a red highlight even if
+    /// the light source is white.
+    pub fn set_specular_color(&mut self, color: Color) {
+        self.specular_color = color;
+    }
+    
+    /// Get the materials emissive color
+    pub fn emissive(&self) -> &Color {
+        &self.emissive
+    }
+    
+    /// Set a new emissive color
+    ///
+    /// The emissive color is added to the lighting regardless
+    /// of any light source, i.e. the surface seems to glow.
+    pub fn set_emissive(&mut self, color: Color) {
+        self.emissive = color;
+    }
+    
+    /// Get the materials reflectivity
+    pub fn reflective(&self) -> f64 {
+        self.reflective
+    }
+    
+    /// Set a new reflectivity
+    pub fn set_reflective(&mut self, reflective: f64) {
+        self.reflective = reflective;
+    }
+    
+    /// Get the materials transparency
+    pub fn transparency(&self) -> f64 {
+        self.transparency
+    }
+    
+    /// Set a new transparency
+    pub fn set_transparency(&mut self, transparency: f64) {
+        self.transparency = transparency;
+    }
+    
+    /// Get the materials refractive index
+    pub fn refractive_index(&self) -> f64 {
+        self.refractive_index
+    }
+    
+    /// Set a new refractive index
+    ///
+    /// Common values are 1.0 for a vacuum, 1.00029 for air,
+    /// 1.333 for water, 1.52 for glass and 2.417 for diamond.
+    pub fn set_refractive_index(&mut self, refractive_index: f64) {
+        self.refractive_index = refractive_index;
+    }
+    
+    /// Get the materials pattern
+    pub fn pattern(&self) -> Option<&dyn Pattern> {
+        self.pattern.as_deref()
+    }
+    
+    /// Set a new pattern
+    ///
+    /// The pattern replaces the color of the material. Because
+    /// `Pattern` is a trait, custom pattern types can be used as well.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transformation of the pattern isn't invertible.
+    pub fn set_pattern(&mut self, pattern: Box<dyn Pattern>) {
+        self.pattern_inverse = pattern.transform().inverse()
+            .expect("the transformation of a pattern must be invertible");
+        self.pattern = Some(pattern);
+    }
+    
+    /// Remove the pattern, i.e. use the materials color again
+    pub fn clear_pattern(&mut self) {
+        self.pattern = None;
+        self.pattern_inverse = Matrix::identity();
+    }
+    
+    /// Check if the material casts a shadow
+    pub fn casts_shadow(&self) -> bool {
+        self.casts_shadow
+    }
+    
+    /// Set whether the material casts a shadow
+    ///
+    /// Objects made of a material that doesn't cast a shadow are
+    /// ignored by shadow rays, e.g. to keep glass from blocking
+    /// the light completely.
+    pub fn set_casts_shadow(&mut self, casts_shadow: bool) {
+        self.casts_shadow = casts_shadow;
+    }
+    
+    /// Get the surface color of an object at the given position.
+    ///
+    /// If the material has a pattern the position is first transformed
+    /// into the object space of `object` and then into pattern space
+    /// (using the inverse of the patterns transformation), where the
+    /// pattern is sampled. Otherwise the materials color is returned.
+    ///
+    /// # Arguments
+    ///
+    /// * `object` - The object the material belongs to
+    /// * `position` - The point on the surface in world space
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     materials::Material,
+    ///     canvas::color::Color,
+    ///     math::point::Point,
+    ///     patterns::SolidPattern,
+    ///     shapes::Sphere,
+    /// };
+    ///
+    /// let s = Sphere::new();
+    /// let mut m = Material::default();
+    /// assert_eq!(Color::new(1.0, 1.0, 1.0), m.color_at(&s, &Point::new(0.0, 0.0, 0.0)));
+    ///
+    /// m.set_pattern(Box::new(SolidPattern::new(Color::new(1.0, 0.0, 0.0))));
+    /// assert_eq!(Color::new(1.0, 0.0, 0.0), m.color_at(&s, &Point::new(0.0, 0.0, 0.0)));
+    /// ```
+    pub fn color_at(&self, object: &dyn Shape, position: &Point) -> Color {
+        match &self.pattern {
+            Some(pattern) => {
+                let object_point = object.world_to_object(position);
+                pattern.pattern_at(&self.pattern_inverse.mul_point(&object_point))
+            },
+            None => self.color,
+        }
+    }
+    
     /// Calculate the lighting for a specific material
-    pub fn lighting(material: &Material, 
-                    light: &PointLight, 
+    ///
+    /// The diffuse and specular contribution is scaled by the
+    /// [`Light::falloff`] of the light at the given `position`.
+    pub fn lighting<L: Light + ?Sized>(material: &Material, 
+                    object: &dyn Shape,
+                    light: &L, 
                     position: &Point,
                     eyev: &Vector,
                     normalv: &Vector) -> Color {
-        let mut diffuse: Color = Color::new(0.0, 0.0, 0.0);
-        let mut specular: Color = Color::new(0.0, 0.0, 0.0);
-        let mut ambient: Color = Color::new(0.0, 0.0, 0.0);
+        Material::lighting_shadowed(material, object, light, position, eyev, normalv, false)
+    }
+    
+    /// Calculate the lighting for a specific material that might be in shadow.
+    ///
+    /// If the point is in shadow only the ambient and emissive
+    /// contribution is returned.
+    pub fn lighting_shadowed<L: Light + ?Sized>(material: &Material, 
+                    object: &dyn Shape,
+                    light: &L, 
+                    position: &Point,
+                    eyev: &Vector,
+                    normalv: &Vector,
+                    in_shadow: bool) -> Color {
+        let (ambient, diffuse, specular) = Material::components(material, object, light, position, eyev, normalv, in_shadow);
+
+        ambient + diffuse + specular
+    }
+
+    /// Calculate the ambient, diffuse and specular contribution of the
+    /// lighting separately.
+    ///
+    /// This is useful to visualize each term while debugging the shading.
+    /// The sum of the three colors equals the result of [`Material::lighting`].
+    /// The emissive color of the material is part of the ambient contribution.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     materials::Material,
+    ///     light::PointLight,
+    ///     canvas::color::Color,
+    ///     math::{point::Point, vector::Vector},
+    ///     shapes::Sphere,
+    /// };
+    ///
+    /// let m = Material::default();
+    /// let s = Sphere::new();
+    /// let position = Point::new(0.0, 0.0, 0.0);
+    /// let eyev = Vector::new(0.0, 0.0, -1.0);
+    /// let normalv = Vector::new(0.0, 0.0, -1.0);
+    /// let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, -10.0));
+    ///
+    /// let (ambient, diffuse, specular) = Material::lighting_components(&m, &s, &light, &position, &eyev, &normalv);
+    ///
+    /// assert_eq!(Color::new(0.1, 0.1, 0.1), ambient);
+    /// assert_eq!(Color::new(0.9, 0.9, 0.9), diffuse);
+    /// assert_eq!(Color::new(0.9, 0.9, 0.9), specular);
+    /// ```
+    pub fn lighting_components<L: Light + ?Sized>(material: &Material,
+                    object: &dyn Shape,
+                    light: &L,
+                    position: &Point,
+                    eyev: &Vector,
+                    normalv: &Vector) -> (Color, Color, Color) {
+        Material::components(material, object, light, position, eyev, normalv, false)
+    }
+
+    /// Calculate the lighting using values precomputed for a material and a light.
+    ///
+    /// Produces the same color as [`Material::lighting`] but doesn't combine
+    /// the colors of the material and the light again for every point, which
+    /// helps when shading many points with the same material and light.
+    ///
+    /// # Arguments
+    ///
+    /// * `cache` - The precomputed values of a material and a light
+    /// * `object` - The shaded object
+    /// * `position` - The point to shade
+    /// * `eyev` - The vector from the point to the eye
+    /// * `normalv` - The surface normal at the point
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     materials::{Material, PrecomputedLighting},
+    ///     light::PointLight,
+    ///     canvas::color::Color,
+    ///     math::{point::Point, vector::Vector},
+    ///     shapes::Sphere,
+    /// };
+    ///
+    /// let m = Material::default();
+    /// let s = Sphere::new();
+    /// let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, -10.0));
+    /// let cache = PrecomputedLighting::new(&m, &light);
+    /// let eyev = Vector::new(0.0, 0.0, -1.0);
+    /// let normalv = Vector::new(0.0, 0.0, -1.0);
+    ///
+    /// assert_eq!(Color::new(1.9, 1.9, 1.9),
+    ///            Material::lighting_cached(&cache, &s, &Point::new(0.0, 0.0, 0.0), &eyev, &normalv));
+    /// ```
+    pub fn lighting_cached<L: Light + ?Sized>(cache: &PrecomputedLighting<'_, L>,
+                    object: &dyn Shape,
+                    position: &Point,
+                    eyev: &Vector,
+                    normalv: &Vector) -> Color {
+        let (ambient, diffuse, specular) = cache.components(object, position, eyev, normalv, false);
+
+        ambient + diffuse + specular
+    }
+
+    fn components<L: Light + ?Sized>(material: &Material,
+                    object: &dyn Shape,
+                    light: &L,
+                    position: &Point,
+                    eyev: &Vector,
+                    normalv: &Vector,
+                    in_shadow: bool) -> (Color, Color, Color) {
+        PrecomputedLighting::new(material, light).components(object, position, eyev, normalv, in_shadow)
+    }
+
+}
+
+/// The parts of the lighting that only depend on a material and a light.
+///
+/// The effective color (surface color combined with the light's intensity),
+/// the ambient color and the specular color are calculated once and reused
+/// by [`Material::lighting_cached`] for every shaded point. If the material
+/// has a pattern the surface color depends on the point, so the effective
+/// and ambient color are still calculated per point.
+pub struct PrecomputedLighting<'a, L: Light + ?Sized> {
+    material: &'a Material,
+    light: &'a L,
+    effective_color: Option<Color>,
+    ambient_color: Option<Color>,
+    specular_color: Color,
+    shininess: f64,
+}
+
+impl<'a, L: Light + ?Sized> PrecomputedLighting<'a, L> {
+    /// Precompute the lighting values for the given material and light.
+    ///
+    /// # Arguments
+    ///
+    /// * `material` - The material of the shaded surface
+    /// * `light` - The light source
+    pub fn new(material: &'a Material, light: &'a L) -> Self {
+        let effective_color = match material.pattern() {
+            Some(_) => None,
+            None => Some(*material.color() * *light.intensity()),
+        };
+
+        Self {
+            material,
+            light,
+            effective_color,
+            ambient_color: effective_color.map(|c| PrecomputedLighting::<L>::ambient_of(material, c)),
+            specular_color: *light.intensity() * *material.specular_color() * material.specular(),
+            shininess: material.effective_shininess(),
+        }
+    }
+
+    /// The ambient contribution (the surface also emits light by itself).
+    fn ambient_of(material: &Material, effective_color: Color) -> Color {
+        effective_color * material.ambient() + *material.emissive()
+    }
+
+    fn components(&self,
+                  object: &dyn Shape,
+                  position: &Point,
+                  eyev: &Vector,
+                  normalv: &Vector,
+                  in_shadow: bool) -> (Color, Color, Color) {
+        let black = Color::new(0.0, 0.0, 0.0);
 
         // Combine the surface color with the light's color/inensity
-        let effective_color = *material.color() * *light.intensity();
+        let (effective_color, ambient) = match (self.effective_color, self.ambient_color) {
+            (Some(effective_color), Some(ambient)) => (effective_color, ambient),
+            _ => {
+                let effective_color = self.material.color_at(object, position) * *self.light.intensity();
+                (effective_color, PrecomputedLighting::<L>::ambient_of(self.material, effective_color))
+            },
+        };
 
         // Find the direction to the light source
-        let mut lightv = *light.position() - *position;
+        let mut lightv = *self.light.position() - *position;
         lightv.norm();
-        
-        // Compute the ambient contribution
-        ambient = effective_color * material.ambient();
+
+        // A point in shadow is only lit by the ambient light (and itself).
+        if in_shadow {
+            return (ambient, black, black);
+        }
 
         // light_dot_normal represents the cosine of the angle between the
         // light vector and the normal vector. A negative number means the
         // light is on the other side of the surface
         let light_dot_normal = lightv.dot(normalv);
         if light_dot_normal < 0.0 {
-            diffuse = Color::new(0.0, 0.0, 0.0);
-            specular = Color::new(0.0, 0.0, 0.0);
-        } else {
-            // Compute the diffuse contribution
-            diffuse = effective_color * material.diffuse() * light_dot_normal;
-
-            // reflect_dot_eye represents the cosine of the angle between the
-            // reflection vector and the eye vector. A negative number means the
-            // light reflects away from the eye.
-            let inv_lightv = -lightv;
-            let reflectv = inv_lightv.reflect(&normalv);
-            let reflect_dot_eye = reflectv.dot(eyev);
-
-            if reflect_dot_eye <= 0.0 {
-                specular = Color::new(0.0, 0.0, 0.0);
-            } else {
-                // Compute the specular contribution
-                let factor = reflect_dot_eye.powf(material.shininess);
-                specular = *light.intensity() * material.specular() * factor;
-            }
+            return (ambient, black, black);
         }
 
-        ambient + diffuse + specular
+        // Compute the diffuse contribution
+        let diffuse = effective_color * self.material.diffuse() * light_dot_normal;
+
+        // reflect_dot_eye represents the cosine of the angle between the
+        // reflection vector and the eye vector. A negative number means the
+        // light reflects away from the eye.
+        let inv_lightv = -lightv;
+        let reflectv = inv_lightv.reflect(normalv);
+        let reflect_dot_eye = reflectv.dot(eyev);
+
+        let specular = if reflect_dot_eye <= 0.0 {
+            black
+        } else {
+            // Compute the specular contribution. Rounding errors might
+            // push the cosine slightly above 1, so it's capped to keep
+            // large exponents from blowing up.
+            let factor = reflect_dot_eye.min(1.0).powf(self.shininess);
+            self.specular_color * factor
+        };
+
+        // Only the light that actually reaches the point contributes
+        // to the diffuse and specular reflection.
+        let falloff = self.light.falloff(position);
+
+        (ambient, diffuse * falloff, specular * falloff)
+    }
+}
+
+impl std::fmt::Debug for Material {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Material")
+            .field("color", &self.color)
+            .field("ambient", &self.ambient)
+            .field("diffuse", &self.diffuse)
+            .field("specular", &self.specular)
+            .field("shininess", &self.shininess)
+            .field("specular_color", &self.specular_color)
+            .field("emissive", &self.emissive)
+            .field("reflective", &self.reflective)
+            .field("transparency", &self.transparency)
+            .field("refractive_index", &self.refractive_index)
+            .field("pattern", &self.pattern.is_some())
+            .field("casts_shadow", &self.casts_shadow)
+            .finish()
     }
+}
+
+/// Patterns are trait objects and can't be compared by value, so
+/// two patterns are only equal if they are the same object, i.e.
+/// a material with a pattern is only equal to itself.
+impl PartialEq for Material {
+    fn eq(&self, other: &Self) -> bool {
+        let same_pattern = match (&self.pattern, &other.pattern) {
+            (None, None) => true,
+            // Only compare the addresses (and not the vtables of trait objects).
+            (Some(a), Some(b)) => std::ptr::eq(a.as_ref() as *const dyn Pattern as *const (),
+                                               b.as_ref() as *const dyn Pattern as *const ()),
+            _ => false,
+        };
 
+        same_pattern &&
+        self.color == other.color &&
+        self.ambient == other.ambient &&
+        self.diffuse == other.diffuse &&
+        self.specular == other.specular &&
+        self.shininess == other.shininess &&
+        self.specular_color == other.specular_color &&
+        self.emissive == other.emissive &&
+        self.reflective == other.reflective &&
+        self.transparency == other.transparency &&
+        self.refractive_index == other.refractive_index &&
+        self.casts_shadow == other.casts_shadow
+    }
+}
+
+impl Default for Material {
+    /// Create a material with default attributes.
+    fn default() -> Self {
+        Self {
+            color: Color::new(1.0, 1.0, 1.0),
+            ambient: 0.1,
+            diffuse: 0.9,
+            specular: 0.9,
+            shininess: 200.0,
+            specular_color: Color::new(1.0, 1.0, 1.0),
+            emissive: Color::new(0.0, 0.0, 0.0),
+            reflective: 0.0,
+            transparency: 0.0,
+            refractive_index: 1.0,
+            pattern: None,
+            pattern_inverse: Matrix::identity(),
+            casts_shadow: true,
+        }
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::materials::Material;
+    use crate::materials::{Material, PrecomputedLighting};
+    use crate::patterns::Pattern;
+    use crate::shapes::Sphere;
     use crate::canvas::color::Color;
     use crate::light::*;
     use crate::math::{
         vector::Vector,
         point::Point,
+        matrix::{Matrix, transformation::*},
     };
 
     #[test]
@@ -143,6 +630,67 @@ mod test {
         assert_eq!(0.9, m.diffuse());
         assert_eq!(0.9, m.specular());
         assert_eq!(200.0, m.shininess());
+        assert_eq!(Color::new(1.0, 1.0, 1.0), *m.specular_color());
+        assert_eq!(Color::new(0.0, 0.0, 0.0), *m.emissive());
+        assert_eq!(0.0, m.reflective());
+        assert_eq!(0.0, m.transparency());
+        assert_eq!(1.0, m.refractive_index());
+        assert!(m.casts_shadow());
+    }
+
+    #[test]
+    fn the_matte_preset() {
+        let m = Material::matte(Color::new(0.2, 0.4, 0.6));
+
+        assert_eq!(Color::new(0.2, 0.4, 0.6), *m.color());
+        assert_eq!(0.1, m.ambient());
+        assert_eq!(0.9, m.diffuse());
+        assert_eq!(0.0, m.specular());
+        assert_eq!(10.0, m.shininess());
+        assert_eq!(0.0, m.reflective());
+        assert_eq!(0.0, m.transparency());
+    }
+
+    #[test]
+    fn the_shiny_preset() {
+        let m = Material::shiny(Color::new(1.0, 0.0, 0.0));
+
+        assert_eq!(Color::new(1.0, 0.0, 0.0), *m.color());
+        assert_eq!(0.1, m.ambient());
+        assert_eq!(0.6, m.diffuse());
+        assert_eq!(0.9, m.specular());
+        assert_eq!(300.0, m.shininess());
+        assert_eq!(0.1, m.reflective());
+        assert_eq!(0.0, m.transparency());
+    }
+
+    #[test]
+    fn the_mirror_preset() {
+        let m = Material::mirror();
+
+        assert_eq!(Color::new(0.0, 0.0, 0.0), *m.color());
+        assert_eq!(0.0, m.ambient());
+        assert_eq!(0.0, m.diffuse());
+        assert_eq!(1.0, m.specular());
+        assert_eq!(300.0, m.shininess());
+        assert_eq!(1.0, m.reflective());
+        assert_eq!(0.0, m.transparency());
+        assert!(m.casts_shadow());
+    }
+
+    #[test]
+    fn the_glass_preset() {
+        let m = Material::glass();
+
+        assert_eq!(Color::new(0.0, 0.0, 0.0), *m.color());
+        assert_eq!(0.0, m.ambient());
+        assert_eq!(0.1, m.diffuse());
+        assert_eq!(1.0, m.specular());
+        assert_eq!(300.0, m.shininess());
+        assert_eq!(0.9, m.reflective());
+        assert_eq!(0.9, m.transparency());
+        assert_eq!(1.5, m.refractive_index());
+        assert!(!m.casts_shadow());
     }
 
     #[test]
@@ -152,7 +700,7 @@ mod test {
         let eyev = Vector::new(0.0, 0.0, -1.0);
         let normalv = Vector::new(0.0, 0.0, -1.0);
         let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, -10.0));
-        let result = Material::lighting(&m, &light, &position, &eyev, &normalv);
+        let result = Material::lighting(&m, &Sphere::new(), &light, &position, &eyev, &normalv);
         assert_eq!(Color::new(1.9, 1.9, 1.9), result);
     }
 
@@ -163,7 +711,7 @@ mod test {
         let eyev = Vector::new(0.0, 2.0_f64.sqrt() / 2.0, -2.0_f64.sqrt() / 2.0);
         let normalv = Vector::new(0.0, 0.0, -1.0);
         let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, -10.0));
-        let result = Material::lighting(&m, &light, &position, &eyev, &normalv);
+        let result = Material::lighting(&m, &Sphere::new(), &light, &position, &eyev, &normalv);
         assert_eq!(Color::new(1.0, 1.0, 1.0), result);
     }
 
@@ -174,7 +722,7 @@ mod test {
         let eyev = Vector::new(0.0, 0.0, -1.0);
         let normalv = Vector::new(0.0, 0.0, -1.0);
         let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 10.0, -10.0));
-        let result = Material::lighting(&m, &light, &position, &eyev, &normalv);
+        let result = Material::lighting(&m, &Sphere::new(), &light, &position, &eyev, &normalv);
         assert_eq!(Color::new(0.7363961, 0.7363961, 0.7363961), result);
     }
 
@@ -185,7 +733,7 @@ mod test {
         let eyev = Vector::new(0.0, -2.0_f64.sqrt() / 2.0, -2.0_f64.sqrt() / 2.0);
         let normalv = Vector::new(0.0, 0.0, -1.0);
         let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 10.0, -10.0));
-        let result = Material::lighting(&m, &light, &position, &eyev, &normalv);
+        let result = Material::lighting(&m, &Sphere::new(), &light, &position, &eyev, &normalv);
         assert_eq!(Color::new(1.636396, 1.636396, 1.636396), result);
     }
 
@@ -196,8 +744,337 @@ mod test {
         let eyev = Vector::new(0.0, 0.0, -1.0);
         let normalv = Vector::new(0.0, 0.0, -1.0);
         let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, 10.0));
-        let result = Material::lighting(&m, &light, &position, &eyev, &normalv);
+        let result = Material::lighting(&m, &Sphere::new(), &light, &position, &eyev, &normalv);
         assert_eq!(Color::new(0.1, 0.1, 0.1), result);
     }
-    
+
+    #[test]
+    fn lighting_with_a_tinted_specular_highlight() {
+        let mut m = Material::default();
+        m.set_specular_color(Color::new(1.0, 0.0, 0.0));
+        let position = Point::new(0.0, 0.0, 0.0);
+        let eyev = Vector::new(0.0, 0.0, -1.0);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, -10.0));
+        let result = Material::lighting(&m, &Sphere::new(), &light, &position, &eyev, &normalv);
+        // only the red channel receives the specular highlight (0.9)
+        assert_eq!(Color::new(1.9, 1.0, 1.0), result);
+    }
+
+    #[test]
+    fn lighting_with_a_white_specular_color_matches_the_default() {
+        let mut m = Material::default();
+        m.set_specular_color(Color::new(1.0, 1.0, 1.0));
+        let position = Point::new(0.0, 0.0, 0.0);
+        let eyev = Vector::new(0.0, -2.0_f64.sqrt() / 2.0, -2.0_f64.sqrt() / 2.0);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 10.0, -10.0));
+        let result = Material::lighting(&m, &Sphere::new(), &light, &position, &eyev, &normalv);
+        assert_eq!(Material::lighting(&Material::default(), &Sphere::new(), &light, &position, &eyev, &normalv), result);
+        assert_eq!(Color::new(1.636396, 1.636396, 1.636396), result);
+    }
+
+    #[test]
+    fn lighting_with_a_point_inside_the_inner_cone_of_a_spot_light() {
+        let m = Material::default();
+        let position = Point::new(0.0, 0.0, 0.0);
+        let eyev = Vector::new(0.0, 0.0, -1.0);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+        let light = SpotLight::new(Point::new(0.0, 0.0, -10.0), 
+                                   Vector::new(0.0, 0.0, 1.0),
+                                   Color::new(1.0, 1.0, 1.0),
+                                   radians(10.0),
+                                   radians(20.0));
+        let result = Material::lighting(&m, &Sphere::new(), &light, &position, &eyev, &normalv);
+        assert_eq!(Color::new(1.9, 1.9, 1.9), result);
+    }
+
+    #[test]
+    fn lighting_with_a_point_outside_the_outer_cone_of_a_spot_light() {
+        let m = Material::default();
+        let position = Point::new(0.0, 0.0, 0.0);
+        let eyev = Vector::new(0.0, 0.0, -1.0);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+        let light = SpotLight::new(Point::new(0.0, 0.0, -10.0), 
+                                   Vector::new(0.0, radians(25.0).sin(), radians(25.0).cos()),
+                                   Color::new(1.0, 1.0, 1.0),
+                                   radians(10.0),
+                                   radians(20.0));
+        let result = Material::lighting(&m, &Sphere::new(), &light, &position, &eyev, &normalv);
+        assert_eq!(Color::new(0.1, 0.1, 0.1), result);
+    }
+
+    #[test]
+    fn lighting_with_a_point_between_the_cones_of_a_spot_light() {
+        let m = Material::default();
+        let position = Point::new(0.0, 0.0, 0.0);
+        let eyev = Vector::new(0.0, 0.0, -1.0);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+        let light = SpotLight::new(Point::new(0.0, 0.0, -10.0), 
+                                   Vector::new(0.0, radians(15.0).sin(), radians(15.0).cos()),
+                                   Color::new(1.0, 1.0, 1.0),
+                                   radians(10.0),
+                                   radians(20.0));
+        let result = Material::lighting(&m, &Sphere::new(), &light, &position, &eyev, &normalv);
+        // halfway between the cones results in half of the diffuse and specular contribution
+        assert_eq!(Color::new(1.0, 1.0, 1.0), result);
+    }
+
+    #[test]
+    fn lighting_with_an_attenuated_point_light() {
+        let m = Material::default();
+        let eyev = Vector::new(0.0, 0.0, -1.0);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+        let mut light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, -10.0));
+        light.set_attenuation(1.0, 0.0, 0.01);
+
+        let near = Material::lighting(&m, &Sphere::new(), &light, &Point::new(0.0, 0.0, -5.0), &eyev, &normalv);
+        let far = Material::lighting(&m, &Sphere::new(), &light, &Point::new(0.0, 0.0, 0.0), &eyev, &normalv);
+
+        // d = 5 -> 1 / 1.25 = 0.8; d = 10 -> 1 / 2 = 0.5
+        assert_eq!(Color::new(1.54, 1.54, 1.54), near);
+        assert_eq!(Color::new(1.0, 1.0, 1.0), far);
+    }
+
+    #[test]
+    fn lighting_with_the_surface_in_shadow() {
+        let m = Material::default();
+        let position = Point::new(0.0, 0.0, 0.0);
+        let eyev = Vector::new(0.0, 0.0, -1.0);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, -10.0));
+        let result = Material::lighting_shadowed(&m, &Sphere::new(), &light, &position, &eyev, &normalv, true);
+        assert_eq!(Color::new(0.1, 0.1, 0.1), result);
+    }
+
+    #[test]
+    fn an_emissive_material_glows_in_shadow() {
+        let mut m = Material::default();
+        m.set_emissive(Color::new(0.5, 0.25, 0.0));
+        let position = Point::new(0.0, 0.0, 0.0);
+        let eyev = Vector::new(0.0, 0.0, -1.0);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, -10.0));
+
+        let shadowed = Material::lighting_shadowed(&m, &Sphere::new(), &light, &position, &eyev, &normalv, true);
+        assert_eq!(Color::new(0.6, 0.35, 0.1), shadowed);
+
+        let lit = Material::lighting(&m, &Sphere::new(), &light, &position, &eyev, &normalv);
+        assert_eq!(Color::new(2.4, 2.15, 1.9), lit);
+    }
+
+    #[test]
+    fn a_black_emissive_reproduces_the_unlit_result() {
+        let mut m = Material::default();
+        m.set_emissive(Color::new(0.0, 0.0, 0.0));
+        let position = Point::new(0.0, 0.0, 0.0);
+        let eyev = Vector::new(0.0, 0.0, -1.0);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 10.0, -10.0));
+        let result = Material::lighting(&m, &Sphere::new(), &light, &position, &eyev, &normalv);
+        assert_eq!(Color::new(0.7363961, 0.7363961, 0.7363961), result);
+    }
+
+    #[test]
+    fn lighting_with_an_extreme_shininess_stays_finite() {
+        let position = Point::new(0.0, 0.0, 0.0);
+        let eyev = Vector::new(0.0, -2.0_f64.sqrt() / 2.0, -2.0_f64.sqrt() / 2.0);
+        let off_eyev = Vector::new(0.0, -0.6, -0.8);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 10.0, -10.0));
+        // ambient + diffuse without any specular highlight
+        let unlit = Color::new(0.1, 0.1, 0.1) + Color::new(0.9, 0.9, 0.9) * (2.0_f64.sqrt() / 2.0);
+
+        for shininess in [1e300, f64::INFINITY, -5.0, 0.0, f64::NAN].iter() {
+            let m = Material::new(Color::new(1.0, 1.0, 1.0), 0.1, 0.9, 0.9, *shininess);
+
+            for e in [eyev, off_eyev].iter() {
+                let result = Material::lighting(&m, &Sphere::new(), &light, &position, e, &normalv);
+                let specular = result.r() - unlit.r();
+
+                assert!(result.r().is_finite() && result.g().is_finite() && result.b().is_finite());
+                assert!((-0.0001..=0.9001).contains(&specular), "shininess {}: {:?}", shininess, result);
+            }
+        }
+    }
+
+    #[test]
+    fn a_huge_shininess_leaves_only_a_pinpoint_highlight() {
+        let m = Material::new(Color::new(1.0, 1.0, 1.0), 0.1, 0.9, 0.9, 1e300);
+        let position = Point::new(0.0, 0.0, 0.0);
+        let eyev = Vector::new(0.0, -0.6, -0.8);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 10.0, -10.0));
+        let result = Material::lighting(&m, &Sphere::new(), &light, &position, &eyev, &normalv);
+
+        // Slightly off the reflection vector the highlight vanishes.
+        assert_eq!(Color::new(0.1, 0.1, 0.1) + Color::new(0.9, 0.9, 0.9) * (2.0_f64.sqrt() / 2.0), result);
+    }
+
+    #[test]
+    fn the_lighting_components_sum_up_to_the_lighting() {
+        let mut m = Material::default();
+        m.set_emissive(Color::new(0.1, 0.0, 0.05));
+        let position = Point::new(0.0, 0.0, 0.0);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+        let cases = [
+            (Vector::new(0.0, 0.0, -1.0), Point::new(0.0, 0.0, -10.0)),
+            (Vector::new(0.0, 2.0_f64.sqrt() / 2.0, -(2.0_f64.sqrt()) / 2.0), Point::new(0.0, 0.0, -10.0)),
+            (Vector::new(0.0, 0.0, -1.0), Point::new(0.0, 10.0, -10.0)),
+            (Vector::new(0.0, -(2.0_f64.sqrt()) / 2.0, -(2.0_f64.sqrt()) / 2.0), Point::new(0.0, 10.0, -10.0)),
+            (Vector::new(0.0, 0.0, -1.0), Point::new(0.0, 0.0, 10.0)),
+        ];
+
+        for (eyev, light_position) in cases.iter() {
+            let light = PointLight::new(Color::new(1.0, 1.0, 1.0), *light_position);
+            let (ambient, diffuse, specular) = Material::lighting_components(&m, &Sphere::new(), &light, &position, eyev, &normalv);
+
+            assert_eq!(Material::lighting(&m, &Sphere::new(), &light, &position, eyev, &normalv), ambient + diffuse + specular);
+        }
+    }
+
+    #[test]
+    fn the_lighting_components_with_the_light_behind_the_surface() {
+        let m = Material::default();
+        let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, 10.0));
+        let (ambient, diffuse, specular) = Material::lighting_components(&m,
+                                                                         &Sphere::new(),
+                                                                         &light,
+                                                                         &Point::new(0.0, 0.0, 0.0),
+                                                                         &Vector::new(0.0, 0.0, -1.0),
+                                                                         &Vector::new(0.0, 0.0, -1.0));
+
+        assert_eq!(Color::new(0.1, 0.1, 0.1), ambient);
+        assert_eq!(Color::new(0.0, 0.0, 0.0), diffuse);
+        assert_eq!(Color::new(0.0, 0.0, 0.0), specular);
+    }
+
+    struct StripePattern {
+        a: Color,
+        b: Color,
+    }
+
+    impl Pattern for StripePattern {
+        fn pattern_at(&self, point: &Point) -> Color {
+            if point.x().floor() as i64 % 2 == 0 { self.a } else { self.b }
+        }
+    }
+
+    #[test]
+    fn lighting_with_a_custom_pattern_applied() {
+        let mut m = Material::new(Color::new(0.5, 0.5, 0.5), 1.0, 0.0, 0.0, 200.0);
+        m.set_pattern(Box::new(StripePattern { a: Color::new(1.0, 1.0, 1.0), b: Color::new(0.0, 0.0, 0.0) }));
+        let eyev = Vector::new(0.0, 0.0, -1.0);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+        let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, -10.0));
+
+        let c1 = Material::lighting(&m, &Sphere::new(), &light, &Point::new(0.9, 0.0, 0.0), &eyev, &normalv);
+        let c2 = Material::lighting(&m, &Sphere::new(), &light, &Point::new(1.1, 0.0, 0.0), &eyev, &normalv);
+
+        assert_eq!(Color::new(1.0, 1.0, 1.0), c1);
+        assert_eq!(Color::new(0.0, 0.0, 0.0), c2);
+    }
+
+    struct TransformedStripePattern(Matrix);
+
+    impl Pattern for TransformedStripePattern {
+        fn pattern_at(&self, point: &Point) -> Color {
+            StripePattern { a: Color::new(1.0, 1.0, 1.0), b: Color::new(0.0, 0.0, 0.0) }.pattern_at(point)
+        }
+
+        fn transform(&self) -> Matrix {
+            self.0.clone()
+        }
+    }
+
+    #[test]
+    fn a_pattern_is_sampled_in_pattern_space() {
+        let s = Sphere::new();
+        let mut m = Material::default();
+        m.set_pattern(Box::new(TransformedStripePattern(scaling(2.0, 2.0, 2.0))));
+
+        assert_eq!(Color::new(1.0, 1.0, 1.0), m.color_at(&s, &Point::new(1.5, 0.0, 0.0)));
+        assert_eq!(Color::new(0.0, 0.0, 0.0), m.color_at(&s, &Point::new(2.5, 0.0, 0.0)));
+
+        m.clear_pattern();
+        assert!(m.pattern().is_none());
+        assert_eq!(*m.color(), m.color_at(&s, &Point::new(2.5, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn a_pattern_with_an_object_transformation() {
+        let mut s = Sphere::new();
+        s.set_transform(scaling(2.0, 2.0, 2.0));
+        let mut m = Material::default();
+        m.set_pattern(Box::new(TransformedStripePattern(Matrix::identity())));
+
+        assert_eq!(Color::new(1.0, 1.0, 1.0), m.color_at(&s, &Point::new(1.5, 0.0, 0.0)));
+        assert_eq!(Color::new(0.0, 0.0, 0.0), m.color_at(&s, &Point::new(2.5, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn a_pattern_with_both_an_object_and_a_pattern_transformation() {
+        let mut s = Sphere::new();
+        s.set_transform(scaling(2.0, 2.0, 2.0));
+        let mut m = Material::default();
+        m.set_pattern(Box::new(TransformedStripePattern(translation(0.5, 0.0, 0.0))));
+
+        // world 2.5 -> object 1.25 -> pattern 0.75
+        assert_eq!(Color::new(1.0, 1.0, 1.0), m.color_at(&s, &Point::new(2.5, 0.0, 0.0)));
+        // world 3.5 -> object 1.75 -> pattern 1.25
+        assert_eq!(Color::new(0.0, 0.0, 0.0), m.color_at(&s, &Point::new(3.5, 0.0, 0.0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn a_pattern_with_a_singular_transformation_is_rejected() {
+        let mut m = Material::default();
+        m.set_pattern(Box::new(TransformedStripePattern(scaling(0.0, 1.0, 1.0))));
+    }
+
+    #[test]
+    fn a_material_with_a_pattern_equals_itself() {
+        let mut a = Material::default();
+        a.set_pattern(Box::new(StripePattern { a: Color::new(1.0, 1.0, 1.0), b: Color::new(0.0, 0.0, 0.0) }));
+        let mut b = Material::default();
+        b.set_pattern(Box::new(StripePattern { a: Color::new(1.0, 1.0, 1.0), b: Color::new(0.0, 0.0, 0.0) }));
+
+        assert_eq!(a, a);
+        assert_ne!(a, b);
+        assert_ne!(a, Material::default());
+    }
+
+    #[test]
+    fn cached_and_uncached_lighting_produce_identical_colors() {
+        let mut m = Material::new(Color::new(0.8, 0.3, 0.5), 0.2, 0.7, 0.6, 50.0);
+        m.set_specular_color(Color::new(1.0, 0.9, 0.4));
+        m.set_emissive(Color::new(0.05, 0.0, 0.1));
+        let light = PointLight::new(Color::new(0.9, 1.0, 0.7), Point::new(-3.0, 5.0, -10.0));
+        let cache = PrecomputedLighting::new(&m, &light);
+        let eyev = Vector::new(0.0, 0.0, -1.0);
+
+        for i in 0..20 {
+            let position = Point::new(i as f64 * 0.1 - 1.0, 0.3, 0.0);
+            let normalv = Vector::new(i as f64 * 0.05 - 0.5, 0.2, -1.0).norm_cpy();
+
+            let uncached = Material::lighting(&m, &Sphere::new(), &light, &position, &eyev, &normalv);
+            let cached = Material::lighting_cached(&cache, &Sphere::new(), &position, &eyev, &normalv);
+            assert_eq!(uncached.r().to_bits(), cached.r().to_bits());
+            assert_eq!(uncached.g().to_bits(), cached.g().to_bits());
+            assert_eq!(uncached.b().to_bits(), cached.b().to_bits());
+        }
+    }
+
+    #[test]
+    fn cached_lighting_samples_the_pattern_per_point() {
+        let mut m = Material::new(Color::new(0.5, 0.5, 0.5), 1.0, 0.0, 0.0, 200.0);
+        m.set_pattern(Box::new(StripePattern { a: Color::new(1.0, 1.0, 1.0), b: Color::new(0.0, 0.0, 0.0) }));
+        let light = PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, -10.0));
+        let cache = PrecomputedLighting::new(&m, &light);
+        let eyev = Vector::new(0.0, 0.0, -1.0);
+        let normalv = Vector::new(0.0, 0.0, -1.0);
+
+        assert_eq!(Color::new(1.0, 1.0, 1.0), Material::lighting_cached(&cache, &Sphere::new(), &Point::new(0.9, 0.0, 0.0), &eyev, &normalv));
+        assert_eq!(Color::new(0.0, 0.0, 0.0), Material::lighting_cached(&cache, &Sphere::new(), &Point::new(1.1, 0.0, 0.0), &eyev, &normalv));
+    }
 }
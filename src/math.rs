@@ -2,12 +2,95 @@ pub mod point;
 pub mod vector;
 pub mod matrix;
 
+/// Tolerance used to nudge points off of a surface.
+///
+/// Computed intersection points suffer from floating point
+/// errors and might end up slightly below the surface they
+/// belong to, which leads to "acne" when a ray is cast from them.
+pub const EPSILON: f64 = 0.00001;
+
+use point::Point;
+use vector::Vector;
+
+/// Calculate the barycentric coordinates of `p` with respect to the triangle `a`, `b`, `c`.
+///
+/// Returns the weights `(u, v, w)` of the corners `a`, `b` and `c`, i.e.
+/// `p = u * a + v * b + w * c` with `u + v + w = 1`. The point is projected
+/// onto the plane of the triangle first. All weights lie between 0 and 1
+/// if (and only if) the point lies within the triangle.
+///
+/// The weights can be used to interpolate values (e.g. normals) given at
+/// the corners of a triangle. A degenerate triangle yields `NaN`s.
+///
+/// # Arguments
+///
+/// * `p` - The point
+/// * `a`, `b`, `c` - The corners of the triangle
+///
+/// # Examples
+///
+/// ```
+/// use sugar_ray::math::{barycentric, point::Point};
+///
+/// let a = Point::new(0.0, 1.0, 0.0);
+/// let b = Point::new(-1.0, 0.0, 0.0);
+/// let c = Point::new(1.0, 0.0, 0.0);
+///
+/// assert_eq!((0.0, 0.5, 0.5), barycentric(&Point::new(0.0, 0.0, 0.0), &a, &b, &c));
+/// ```
+pub fn barycentric(p: &Point, a: &Point, b: &Point, c: &Point) -> (f64, f64, f64) {
+    let v0 = *b - *a;
+    let v1 = *c - *a;
+    let v2 = *p - *a;
+
+    let d00 = v0.dot(&v0);
+    let d01 = v0.dot(&v1);
+    let d11 = v1.dot(&v1);
+    let d20 = v2.dot(&v0);
+    let d21 = v2.dot(&v1);
+    let denom = d00 * d11 - d01 * d01;
+
+    let v = (d11 * d20 - d01 * d21) / denom;
+    let w = (d00 * d21 - d01 * d20) / denom;
+
+    (1.0 - v - w, v, w)
+}
+
+/// Calculate the scalar triple product `a · (b × c)` of three vectors.
+///
+/// The absolute value is the volume of the parallelepiped spanned by
+/// the vectors. The sign tells the orientation, i.e. it is positive if
+/// `a`, `b` and `c` form a right-handed system, negative if they form a
+/// left-handed one and zero if they lie in a common plane. Swapping any
+/// two arguments negates the result.
+///
+/// # Arguments
+///
+/// * `a`, `b`, `c` - The vectors
+///
+/// # Examples
+///
+/// ```
+/// use sugar_ray::math::{scalar_triple_product, vector::Vector};
+///
+/// let a = Vector::new(2.0, 0.0, 0.0);
+/// let b = Vector::new(0.0, 3.0, 0.0);
+/// let c = Vector::new(0.0, 0.0, 4.0);
+///
+/// assert_eq!(24.0, scalar_triple_product(&a, &b, &c));
+/// ```
+pub fn scalar_triple_product(a: &Vector, b: &Vector, c: &Vector) -> f64 {
+    a.dot(&b.cross(c))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::math::{
+        barycentric,
+        scalar_triple_product,
         point::Point, 
         vector::Vector,
-        matrix::Matrix,
+        matrix::{Matrix, transformation::{scaling, translation, rotation_rad_x, rotation_rad_y, shearing, perspective}},
     };
 
 
@@ -21,6 +104,14 @@ mod tests {
         assert!(Vector::new(4.3, -4.1, 2.9) == Vector::new(4.3, -4.1, 2.9));
     }
 
+    #[test]
+    fn the_origin_and_the_zero_vector() {
+        assert_eq!(Point::new(0.0, 0.0, 0.0), Point::origin());
+        assert_eq!(Vector::new(0.0, 0.0, 0.0), Vector::zero());
+        assert_eq!(0.0, Vector::zero().mag());
+        assert_eq!(Vector::zero(), Point::origin() - Point::origin());
+    }
+
     #[test]
     fn add_vector_to_point() {
         assert_eq!(Point::new(1.0, 1.0, 6.0), Point::new(3.0, -2.0, 5.0) + Vector::new(-2.0, 3.0, 1.0));
@@ -31,6 +122,74 @@ mod tests {
         assert_eq!(Vector::new(5.0, 1.0, 6.0), Vector::new(2.0, 3.0, 1.0) + Vector::new(3.0, -2.0, 5.0));
     }
 
+    #[test]
+    fn distance_between_two_points() {
+        let p = Point::new(0.0, 0.0, 0.0);
+        let q = Point::new(0.0, 0.0, 5.0);
+
+        assert_eq!(5.0, p.distance(&q));
+        assert_eq!(5.0, q.distance(&p));
+        assert_eq!(25.0, p.distance_squared(&q));
+    }
+
+    #[test]
+    fn component_wise_min_and_max_of_points() {
+        let p = Point::new(1.0, 5.0, 3.0);
+        let q = Point::new(4.0, 2.0, 6.0);
+
+        assert_eq!(Point::new(1.0, 2.0, 3.0), p.min(&q));
+        assert_eq!(Point::new(4.0, 5.0, 6.0), p.max(&q));
+        assert_eq!(p.min(&q), q.min(&p));
+        assert_eq!(p.max(&q), q.max(&p));
+    }
+
+    #[test]
+    fn min_and_max_of_a_point_with_itself() {
+        let p = Point::new(-1.0, 0.0, 7.5);
+
+        assert_eq!(p, p.min(&p));
+        assert_eq!(p, p.max(&p));
+    }
+
+    #[test]
+    fn refracting_a_vector_at_normal_incidence() {
+        let v = Vector::new(0.0, 0.0, 1.0);
+        let n = Vector::new(0.0, 0.0, -1.0);
+
+        assert_eq!(Some(Vector::new(0.0, 0.0, 1.0)), v.refract(&n, 1.0 / 1.5));
+        assert_eq!(Some(Vector::new(0.0, 0.0, 1.0)), v.refract(&n, 1.5));
+    }
+
+    #[test]
+    fn refracting_a_vector_bends_it_towards_the_normal() {
+        // 45 degrees from air into glass
+        let v = Vector::new(1.0, -1.0, 0.0);
+        let n = Vector::new(0.0, 1.0, 0.0);
+        let t = v.refract(&n, 1.0 / 1.5).unwrap();
+
+        let sin_t = t.x();
+        assert!((t.mag() - 1.0).abs() < 1e-10);
+        assert!(t.y() < 0.0);
+        assert!((sin_t - (2.0_f64.sqrt() / 2.0) / 1.5).abs() < 1e-10);
+    }
+
+    #[test]
+    fn refracting_a_vector_under_total_internal_reflection() {
+        // 60 degrees from glass into air exceeds the critical angle (~41.8 degrees)
+        let v = Vector::new(3.0_f64.sqrt() / 2.0, -0.5, 0.0);
+        let n = Vector::new(0.0, 1.0, 0.0);
+
+        assert_eq!(None, v.refract(&n, 1.5));
+    }
+
+    #[test]
+    fn distance_matches_magnitude_of_difference() {
+        let p = Point::new(1.0, -2.0, 3.0);
+        let q = Point::new(-4.0, 2.0, 0.5);
+
+        assert_eq!((q - p).mag(), p.distance(&q));
+    }
+
     #[test]
     fn sub_point_from_point() {
         assert_eq!(Vector::new(-2.0, -4.0, -6.0), Point::new(3.0, 2.0, 1.0) - Point::new(5.0, 6.0, 7.0));
@@ -159,6 +318,88 @@ mod tests {
         assert!(m.is_none());
     }
     
+    #[test]
+    fn try_constructing_a_matrix_with_nan() {
+        let m = Matrix::from_vec_checked(vec![vec![1.0,2.0],
+                                         vec![f64::NAN,4.0]]);
+        assert!(m.is_none());
+    }
+
+    #[test]
+    fn try_constructing_a_matrix_with_infinite_values() {
+        assert!(Matrix::from_vec_checked(vec![vec![f64::INFINITY, 0.0]]).is_none());
+        assert!(Matrix::from_vec_checked(vec![vec![0.0], vec![f64::NEG_INFINITY]]).is_none());
+    }
+
+    #[test]
+    fn constructing_a_finite_matrix_checked() {
+        let v = vec![vec![1.0,2.0], vec![3.0,4.0]];
+        assert_eq!(Matrix::from_vec(v.clone()), Matrix::from_vec_checked(v));
+        assert!(Matrix::from_vec_checked(vec![vec![1.0,2.0], vec![3.0]]).is_none());
+    }
+
+    #[test]
+    fn constructing_a_matrix_from_a_diagonal() {
+        let m = Matrix::from_diagonal(&[1.0, -2.0, 3.0]);
+        let expected = Matrix::from_vec(vec![vec![1.0,0.0,0.0],
+                                             vec![0.0,-2.0,0.0],
+                                             vec![0.0,0.0,3.0]]).unwrap();
+        assert_eq!(expected, m);
+    }
+
+    #[test]
+    fn a_diagonal_matrix_equals_a_scaling_matrix() {
+        assert_eq!(scaling(2.0, 3.0, 4.0), Matrix::from_diagonal(&[2.0, 3.0, 4.0, 1.0]));
+    }
+
+    #[test]
+    fn tolerant_equal_matrices_are_not_necessarily_strictly_equal() {
+        let m1 = Matrix::from_vec(vec![vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap();
+        let m2 = Matrix::from_vec(vec![vec![1.0, 2.0], vec![3.0, 4.0 + 1e-15]]).unwrap();
+
+        assert_eq!(m1, m2);
+        assert!(!m1.strict_eq(&m2));
+        assert!(m1.strict_eq(&m1.clone()));
+    }
+
+    #[test]
+    fn strict_equality_compares_bits_and_dimensions() {
+        let zero = Matrix::from_vec(vec![vec![0.0]]).unwrap();
+        let neg_zero = Matrix::from_vec(vec![vec![-0.0]]).unwrap();
+        let nan = Matrix::from_vec(vec![vec![f64::NAN]]).unwrap();
+
+        assert_eq!(zero, neg_zero);
+        assert!(!zero.strict_eq(&neg_zero));
+        assert!(nan.strict_eq(&nan.clone()));
+        assert!(!Matrix::new(2, 3).strict_eq(&Matrix::new(3, 2)));
+    }
+
+    #[test]
+    fn square_and_non_square_matrices() {
+        assert!(Matrix::identity().is_square());
+        assert!(Matrix::new(1, 1).is_square());
+        assert!(!Matrix::new(2, 4).is_square());
+        assert!(!Matrix::new(4, 2).is_square());
+    }
+
+    #[test]
+    #[should_panic(expected = "the determinant is only defined for square matrices, but the matrix is 2 x 4")]
+    fn the_determinant_of_a_non_square_matrix_panics() {
+        Matrix::new(2, 4).det();
+    }
+
+    #[test]
+    #[should_panic(expected = "the inverse is only defined for square matrices")]
+    fn the_inverse_of_a_non_square_matrix_panics() {
+        Matrix::new(3, 4).inverse();
+    }
+
+    #[test]
+    #[should_panic(expected = "the cofactor is only defined for square matrices")]
+    fn the_cofactor_of_a_non_square_matrix_panics() {
+        Matrix::new(4, 3).cofactor(0, 0);
+    }
+
     #[test]
     fn assigning_values_to_a_2x2_matrix() {
         let mut m = Matrix::new(2, 2);
@@ -204,7 +445,7 @@ mod tests {
                                  vec![1.0, -2.0, -7.0],
                                  vec![0.0, 1.5, 1.0]]).unwrap();
 
-        assert_eq!(true, m1 == m2);
+        assert!(m1 == m2);
     }
 
     #[test]
@@ -217,7 +458,7 @@ mod tests {
                                  vec![1.0, -3.0, -7.0],
                                  vec![0.0, 1.5, 1.00001]]).unwrap();
 
-        assert_eq!(false, m1 == m2);
+        assert!(m1 != m2);
     }
 
     #[test]
@@ -230,7 +471,7 @@ mod tests {
                                  vec![1.0, -7.0],
                                  vec![0.0, 1.0]]).unwrap();
 
-        assert_eq!(false, m1 == m2);
+        assert!(m1 != m2);
     }
 
     #[test]
@@ -244,7 +485,7 @@ mod tests {
                                  vec![0.0, 1.5, 1.0],
                                  vec![1.0,2.0,3.0]]).unwrap();
 
-        assert_eq!(true, m1 != m2);
+        assert!(m1 != m2);
     }
 
 
@@ -424,7 +665,7 @@ mod tests {
                                  vec![4.0,-9.0,3.0,-7.0],
                                  vec![9.0,1.0,7.0,-6.0]]).unwrap();
 
-        assert_eq!(true, m.is_inv());
+        assert!(m.is_inv());
     }
 
     #[test]
@@ -434,7 +675,7 @@ mod tests {
                                  vec![0.0,-5.0,1.0,-5.0],
                                  vec![0.0,0.0,0.0,0.0]]).unwrap();
 
-        assert_eq!(false, m.is_inv());
+        assert!(!m.is_inv());
     }
 
     #[test]
@@ -510,4 +751,625 @@ mod tests {
         assert_eq!(expected, x);
     }
 
+    #[test]
+    fn displaying_a_matrix() {
+        let m = Matrix::from_vec(vec![vec![1.0,2.0,3.0],
+                                 vec![-4.5,5.25,6.0],
+                                 vec![7.0,8.0,-100.125]]).unwrap();
+
+        let s = format!("{:.3}", m);
+
+        assert_eq!(3, s.lines().count());
+        assert_eq!("   1.000    2.000    3.000", s.lines().next().unwrap());
+        assert!(s.contains("-4.500"));
+        assert!(s.contains("5.250"));
+        assert!(s.contains("-100.125"));
+    }
+
+    #[test]
+    fn displaying_a_matrix_respects_precision() {
+        let m = Matrix::from_vec(vec![vec![1.0, 0.5]]).unwrap();
+
+        assert_eq!("1.0 0.5", format!("{:.1}", m));
+        assert_eq!("1.000 0.500", format!("{}", m));
+    }
+
+    #[test]
+    fn mutating_a_cell_updates_the_determinant() {
+        let mut m = Matrix::from_vec(vec![vec![1.0,2.0,6.0],
+                                 vec![-5.0,8.0,-4.0],
+                                 vec![2.0,6.0,4.0]]).unwrap();
+
+        assert_eq!(-196.0, m.det());
+        m[0][0] = 2.0;
+        assert_eq!(-140.0, m.det());
+        m.set(0, 0, 1.0);
+        assert_eq!(-196.0, m.det());
+    }
+
+    #[test]
+    fn a_matrix_can_be_shared_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+
+        assert_send_sync::<Matrix>();
+    }
+
+    #[test]
+    fn calculating_the_matrix_of_minors_of_a_3x3_matrix() {
+        let m = Matrix::from_vec(vec![vec![3.0,5.0,0.0],
+                                 vec![2.0,-1.0,-7.0],
+                                 vec![6.0,-1.0,5.0]]).unwrap();
+        let minors = m.minor_matrix();
+
+        for r in 0..3 {
+            for c in 0..3 {
+                assert_eq!(m.minor(r, c), minors[r][c]);
+            }
+        }
+    }
+
+    #[test]
+    fn calculating_the_matrix_of_cofactors_of_a_3x3_matrix() {
+        let m = Matrix::from_vec(vec![vec![1.0,2.0,6.0],
+                                 vec![-5.0,8.0,-4.0],
+                                 vec![2.0,6.0,4.0]]).unwrap();
+        let cofactors = m.cofactor_matrix();
+
+        assert_eq!(56.0, cofactors[0][0]);
+        assert_eq!(12.0, cofactors[0][1]);
+        assert_eq!(-46.0, cofactors[0][2]);
+
+        for r in 0..3 {
+            for c in 0..3 {
+                assert_eq!(m.cofactor(r, c), cofactors[r][c]);
+            }
+        }
+    }
+
+    #[test]
+    fn multiplying_a_matrix_by_its_adjugate() {
+        let matrices = vec![
+            Matrix::from_vec(vec![vec![1.0,2.0,6.0],
+                             vec![-5.0,8.0,-4.0],
+                             vec![2.0,6.0,4.0]]).unwrap(),
+            Matrix::from_vec(vec![vec![3.0,5.0,0.0],
+                             vec![2.0,-1.0,-7.0],
+                             vec![6.0,-1.0,5.0]]).unwrap(),
+            Matrix::from_vec(vec![vec![-2.0,-8.0,3.0,5.0],
+                             vec![-3.0,1.0,7.0,3.0],
+                             vec![1.0,2.0,-9.0,6.0],
+                             vec![-6.0,7.0,7.0,-9.0]]).unwrap(),
+            Matrix::from_vec(vec![vec![-5.0,2.0,6.0,-8.0],
+                             vec![1.0,-5.0,1.0,8.0],
+                             vec![7.0,7.0,-6.0,-7.0],
+                             vec![1.0,-3.0,7.0,4.0]]).unwrap(),
+        ];
+
+        for m in matrices {
+            let mut expected = Matrix::new(m.rows(), m.cols());
+            for i in 0..m.rows() {
+                expected[i][i] = m.det();
+            }
+
+            assert_eq!(expected, m.adjugate().mul(&m));
+            assert_eq!(expected, m.mul(&m.adjugate()));
+        }
+    }
+
+    #[test]
+    fn the_determinant_of_a_1x1_matrix() {
+        let m = Matrix::from_vec(vec![vec![-3.5]]).unwrap();
+
+        assert_eq!(-3.5, m.det());
+    }
+
+    #[test]
+    fn the_rank_of_the_identity_matrix() {
+        assert_eq!(4, Matrix::identity().rank());
+    }
+
+    #[test]
+    fn a_zero_row_reduces_the_rank() {
+        let mut m = Matrix::identity();
+        m.set(2, 2, 0.0);
+
+        assert_eq!(3, m.rank());
+        assert!(!m.is_inv());
+    }
+
+    #[test]
+    fn the_rank_of_a_singular_matrix_is_less_than_its_dimension() {
+        let m = Matrix::from_vec(vec![vec![-4.0, 2.0, -2.0, -3.0],
+                                      vec![9.0, 6.0, 2.0, 6.0],
+                                      vec![0.0, -5.0, 1.0, -5.0],
+                                      vec![0.0, 0.0, 0.0, 0.0]]).unwrap();
+
+        assert!(m.rank() < 4);
+        assert!(!m.is_inv());
+        assert_eq!(4, scaling(2.0, 3.0, 4.0).rank());
+    }
+
+    #[test]
+    fn the_rank_of_a_non_square_matrix() {
+        let m = Matrix::from_vec(vec![vec![1.0, 2.0, 3.0, 4.0],
+                                      vec![2.0, 4.0, 6.0, 8.0]]).unwrap();
+
+        assert_eq!(1, m.rank());
+    }
+
+    #[test]
+    fn building_an_augmented_matrix() {
+        let a = Matrix::from_vec(vec![vec![2.0, 1.0, -1.0],
+                                      vec![-3.0, -1.0, 2.0],
+                                      vec![-2.0, 1.0, 2.0]]).unwrap();
+        let i = Matrix::from_diagonal(&[1.0, 1.0, 1.0]);
+        let aug = a.hconcat(&i).unwrap();
+
+        assert_eq!(3, aug.rows());
+        assert_eq!(6, aug.cols());
+        assert_eq!(Matrix::from_vec(vec![vec![2.0, 1.0, -1.0, 1.0, 0.0, 0.0],
+                                         vec![-3.0, -1.0, 2.0, 0.0, 1.0, 0.0],
+                                         vec![-2.0, 1.0, 2.0, 0.0, 0.0, 1.0]]).unwrap(), aug);
+    }
+
+    #[test]
+    fn stacking_matrices_vertically() {
+        let a = Matrix::from_diagonal(&[1.0, 2.0, 3.0]);
+        let b = Matrix::from_vec(vec![vec![4.0, 5.0, 6.0]]).unwrap();
+        let m = a.vconcat(&b).unwrap();
+
+        assert_eq!(4, m.rows());
+        assert_eq!(3, m.cols());
+        assert_eq!(6.0, m[3][2]);
+        assert_eq!(2.0, m[1][1]);
+    }
+
+    #[test]
+    fn concatenating_matrices_with_mismatched_dimensions() {
+        let a = Matrix::new(3, 3);
+        let b = Matrix::new(2, 3);
+
+        assert!(a.hconcat(&b).is_none());
+        assert!(a.vconcat(&Matrix::new(3, 2)).is_none());
+        assert!(a.vconcat(&b).is_some());
+    }
+
+    #[test]
+    fn padding_a_3x3_rotation_to_a_homogeneous_matrix() {
+        let (sin, cos) = (std::f64::consts::PI / 5.0).sin_cos();
+        let r3 = Matrix::from_vec(vec![vec![cos, -sin, 0.0],
+                                       vec![sin, cos, 0.0],
+                                       vec![0.0, 0.0, 1.0]]).unwrap();
+        let r4 = r3.to_homogeneous();
+        let p = Point::new(2.0, -3.0, 4.0);
+
+        // Apply the 3 x 3 matrix to the linear part of the point by hand.
+        let v = [p.x(), p.y(), p.z()];
+        let mut expected = [0.0; 3];
+        for (r, e) in expected.iter_mut().enumerate() {
+            *e = (0..3).map(|c| r3[r][c] * v[c]).sum();
+        }
+
+        assert_eq!(4, r4.rows());
+        assert_eq!(4, r4.cols());
+        assert_eq!(1.0, r4[3][3]);
+        assert_eq!(Point::new(expected[0], expected[1], expected[2]), r4 * p);
+        assert_eq!(Matrix::identity().rotate_z(std::f64::consts::PI / 5.0), r3.to_homogeneous());
+    }
+
+    #[test]
+    #[should_panic]
+    fn padding_a_non_3x3_matrix_panics() {
+        Matrix::identity().to_homogeneous();
+    }
+
+    #[test]
+    fn applying_a_matrix_to_many_points() {
+        let m = Matrix::identity()
+                    .rotate_x(std::f64::consts::PI / 3.0)
+                    .scale(2.0, 1.0, 0.5)
+                    .translate(1.0, -2.0, 3.0);
+        let points = vec![Point::new(0.0, 0.0, 0.0),
+                          Point::new(1.0, 2.0, 3.0),
+                          Point::new(-4.0, 0.5, 7.0)];
+
+        let expected: Vec<Point> = points.iter().map(|p| m.mul_point(p)).collect();
+        assert_eq!(expected, m.apply_to_points(&points));
+        assert!(m.apply_to_points(&[]).is_empty());
+    }
+
+    #[test]
+    fn comparing_points_with_a_tolerance() {
+        let p = Point::new(1.0, -2.0, 3.0);
+        let q = Point::new(1.0 + 1e-8, -2.0, 3.0 - 1e-8);
+
+        assert!(p.approx_eq(&q, 1e-6));
+        assert!(!p.approx_eq(&q, 1e-10));
+        assert!(q.approx_eq(&p, 1e-6));
+    }
+
+    #[test]
+    fn comparing_vectors_with_a_tolerance() {
+        let v = Vector::new(0.5, 0.0, -1.0);
+        let w = Vector::new(0.5, 1e-8, -1.0);
+
+        assert!(v.approx_eq(&w, 1e-6));
+        assert!(!v.approx_eq(&w, 1e-10));
+        assert!(v.approx_eq(&v, 0.0));
+    }
+
+    #[test]
+    fn transposing_a_matrix_in_place() {
+        let m = Matrix::from_vec(vec![vec![0.0, 9.0, 3.0, 0.0],
+                                      vec![9.0, 8.0, 0.0, 8.0],
+                                      vec![1.0, 8.0, 5.0, 3.0],
+                                      vec![0.0, 0.0, 5.0, 8.0]]).unwrap();
+        let mut t = m.clone();
+        t.transpose_in_place();
+
+        assert!(m.transpose().strict_eq(&t));
+        t.transpose_in_place();
+        assert!(m.strict_eq(&t));
+    }
+
+    #[test]
+    #[should_panic]
+    fn transposing_a_non_square_matrix_in_place_panics() {
+        Matrix::new(2, 3).transpose_in_place();
+    }
+
+    #[test]
+    fn the_cofactor_signs_form_a_checkerboard() {
+        let expected = [[1.0, -1.0, 1.0, -1.0],
+                        [-1.0, 1.0, -1.0, 1.0],
+                        [1.0, -1.0, 1.0, -1.0],
+                        [-1.0, 1.0, -1.0, 1.0]];
+
+        for (row, signs) in expected.iter().enumerate() {
+            for (col, sign) in signs.iter().enumerate() {
+                assert_eq!(*sign, Matrix::checkerboard_sign(row, col));
+            }
+        }
+    }
+
+    #[test]
+    fn solving_a_3x3_linear_system() {
+        let a = Matrix::from_vec(vec![vec![2.0, 1.0, -1.0],
+                                      vec![-3.0, -1.0, 2.0],
+                                      vec![-2.0, 1.0, 2.0]]).unwrap();
+        let b = [8.0, -11.0, -3.0];
+        let x = a.solve(&b).unwrap();
+
+        for (e, v) in [2.0, 3.0, -1.0].iter().zip(x.iter()) {
+            assert!((e - v).abs() < 0.00001);
+        }
+        for (row, expected) in b.iter().enumerate() {
+            let ax: f64 = (0..3).map(|c| a[row][c] * x[c]).sum();
+            assert!((ax - expected).abs() < 0.00001);
+        }
+    }
+
+    #[test]
+    fn solving_a_singular_or_mismatched_system() {
+        let singular = Matrix::from_vec(vec![vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap();
+
+        assert!(singular.solve(&[1.0, 2.0]).is_none());
+        assert!(Matrix::identity().solve(&[1.0, 2.0, 3.0]).is_none());
+        assert!(Matrix::new(2, 3).solve(&[1.0, 2.0]).is_none());
+    }
+
+    #[test]
+    fn a_perspective_projection_divides_by_w() {
+        // Projects onto the plane z = -1 (camera at the origin looking toward -z).
+        let m = Matrix::from_vec(vec![vec![1.0, 0.0, 0.0, 0.0],
+                                      vec![0.0, 1.0, 0.0, 0.0],
+                                      vec![0.0, 0.0, 1.0, 0.0],
+                                      vec![0.0, 0.0, -1.0, 0.0]]).unwrap();
+        let p = Point::new(2.0, 4.0, -4.0);
+
+        assert_eq!(Point::new(0.5, 1.0, -1.0), m.mul_point(&p));
+        assert_eq!(Point::new(0.5, 1.0, -1.0), m * p);
+    }
+
+    #[test]
+    fn affine_transformations_dont_divide() {
+        let t = Matrix::identity().scale(2.0, 2.0, 2.0).translate(1.0, 2.0, 3.0);
+
+        assert_eq!(Point::new(3.0, 4.0, 5.0), t.mul_point(&Point::new(1.0, 1.0, 1.0)));
+        assert_eq!(Vector::new(2.0, 2.0, 2.0), t * Vector::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn checking_invertibility_by_row_reduction_matches_the_determinant() {
+        let invertible = Matrix::from_vec(vec![vec![6.0, 4.0, 4.0, 4.0],
+                                               vec![5.0, 5.0, 7.0, 6.0],
+                                               vec![4.0, -9.0, 3.0, -7.0],
+                                               vec![9.0, 1.0, 7.0, -6.0]]).unwrap();
+        let singular = Matrix::from_vec(vec![vec![-4.0, 2.0, -2.0, -3.0],
+                                             vec![9.0, 6.0, 2.0, 6.0],
+                                             vec![0.0, -5.0, 1.0, -5.0],
+                                             vec![0.0, 0.0, 0.0, 0.0]]).unwrap();
+
+        for m in [invertible, singular, Matrix::identity(), scaling(0.001, 0.001, 0.001)].iter() {
+            assert_eq!(m.is_inv(), m.is_inv_fast());
+        }
+        assert!(!Matrix::new(3, 4).is_inv_fast());
+        assert!(!Matrix::new(3, 3).is_inv_fast());
+    }
+
+    #[test]
+    fn checking_the_invertibility_of_a_large_matrix() {
+        let n = 60;
+        let mut m = Matrix::new(n, n);
+        for r in 0..n {
+            for c in 0..n {
+                m[r][c] = if r == c { n as f64 } else { ((r * 7 + c * 3) % 5) as f64 };
+            }
+        }
+        // Diagonally dominant, i.e. invertible.
+        assert!(m.is_inv_fast());
+
+        // Make the last row a linear combination of the first two.
+        for c in 0..n {
+            m[n - 1][c] = 2.0 * m[0][c] - 3.0 * m[1][c];
+        }
+        assert!(!m.is_inv_fast());
+    }
+
+    #[test]
+    fn barycentric_coordinates_of_the_centroid_and_the_vertices() {
+        let a = Point::new(1.0, 0.0, 2.0);
+        let b = Point::new(-3.0, 4.0, 0.5);
+        let c = Point::new(2.0, -1.0, -3.0);
+        let centroid = Point::new((a.x() + b.x() + c.x()) / 3.0,
+                                  (a.y() + b.y() + c.y()) / 3.0,
+                                  (a.z() + b.z() + c.z()) / 3.0);
+
+        let (u, v, w) = barycentric(&centroid, &a, &b, &c);
+        assert!((u - 1.0 / 3.0).abs() < 0.00001);
+        assert!((v - 1.0 / 3.0).abs() < 0.00001);
+        assert!((w - 1.0 / 3.0).abs() < 0.00001);
+
+        let (u, v, w) = barycentric(&a, &a, &b, &c);
+        assert!((u - 1.0).abs() < 0.00001 && v.abs() < 0.00001 && w.abs() < 0.00001);
+        let (u, v, w) = barycentric(&c, &a, &b, &c);
+        assert!(u.abs() < 0.00001 && v.abs() < 0.00001 && (w - 1.0).abs() < 0.00001);
+    }
+
+    #[test]
+    fn constructing_a_4x4_matrix_from_an_array() {
+        let m = Matrix::from_array([[1.0, 2.0, 3.0, 4.0],
+                                    [5.5, 6.5, 7.5, 8.5],
+                                    [9.0, 10.0, 11.0, 12.0],
+                                    [13.5, 14.5, 15.5, 16.5]]);
+        let expected = Matrix::from_vec(vec![vec![1.0, 2.0, 3.0, 4.0],
+                                             vec![5.5, 6.5, 7.5, 8.5],
+                                             vec![9.0, 10.0, 11.0, 12.0],
+                                             vec![13.5, 14.5, 15.5, 16.5]]).unwrap();
+
+        assert_eq!(4, m.rows());
+        assert_eq!(4, m.cols());
+        assert_eq!(expected, m);
+        assert_eq!(expected, Matrix::from_rows_slice(&[&[1.0, 2.0, 3.0, 4.0],
+                                                       &[5.5, 6.5, 7.5, 8.5],
+                                                       &[9.0, 10.0, 11.0, 12.0],
+                                                       &[13.5, 14.5, 15.5, 16.5]]).unwrap());
+    }
+
+    #[test]
+    fn constructing_a_matrix_from_ragged_rows_fails() {
+        assert!(Matrix::from_rows_slice(&[&[1.0, 2.0], &[3.0]]).is_none());
+        assert!(Matrix::from_rows_slice(&[]).is_none());
+    }
+
+    #[test]
+    fn the_scalar_triple_product_of_the_standard_basis() {
+        let x = Vector::new(1.0, 0.0, 0.0);
+        let y = Vector::new(0.0, 1.0, 0.0);
+        let z = Vector::new(0.0, 0.0, 1.0);
+
+        assert_eq!(1.0, scalar_triple_product(&x, &y, &z));
+        assert_eq!(-1.0, scalar_triple_product(&y, &x, &z));
+        assert_eq!(-1.0, scalar_triple_product(&x, &z, &y));
+        assert_eq!(-1.0, scalar_triple_product(&z, &y, &x));
+    }
+
+    #[test]
+    fn swapping_two_arguments_negates_the_scalar_triple_product() {
+        let a = Vector::new(1.0, 2.0, 3.0);
+        let b = Vector::new(-2.0, 0.5, 4.0);
+        let c = Vector::new(3.0, -1.0, 2.0);
+
+        let abc = scalar_triple_product(&a, &b, &c);
+        assert!(abc != 0.0);
+        assert_eq!(-abc, scalar_triple_product(&b, &a, &c));
+        assert_eq!(0.0, scalar_triple_product(&a, &a, &c));
+    }
+
+    #[test]
+    fn multiplying_into_a_preallocated_matrix() {
+        let a = Matrix::from_array([[1.0, 2.0, 3.0, 4.0],
+                                    [5.0, 6.0, 7.0, 8.0],
+                                    [9.0, 8.0, 7.0, 6.0],
+                                    [5.0, 4.0, 3.0, 2.0]]);
+        let b = Matrix::from_array([[-2.0, 1.0, 2.0, 3.0],
+                                    [3.0, 2.0, 1.0, -1.0],
+                                    [4.0, 3.0, 6.0, 5.0],
+                                    [1.0, 2.0, 7.0, 8.0]]);
+        let mut out = Matrix::new(4, 4);
+
+        a.mul_into(&b, &mut out);
+        assert_eq!(a.mul(&b), out);
+        assert_eq!(a.mul(&b).det(), out.det());
+
+        // the buffer is overwritten (not accumulated) on reuse
+        b.mul_into(&a, &mut out);
+        assert_eq!(b.mul(&a), out);
+        assert_eq!(b.mul(&a).det(), out.det());
+    }
+
+    #[test]
+    #[should_panic]
+    fn multiplying_into_a_matrix_of_the_wrong_size_panics() {
+        let a = Matrix::new(4, 4);
+        let mut out = Matrix::new(3, 4);
+
+        a.mul_into(&a, &mut out);
+    }
+
+    #[test]
+    fn transforming_a_point_by_a_translation() {
+        let p = Point::new(-3.0, 4.0, 5.0);
+        let m = translation(1.0, 2.0, 3.0);
+
+        assert_eq!(Point::new(-2.0, 6.0, 8.0), p.transform(&m));
+        assert_eq!(m.mul_point(&p), p.transform(&m));
+    }
+
+    #[test]
+    fn translation_does_not_affect_a_transformed_vector() {
+        let v = Vector::new(-3.0, 4.0, 5.0);
+
+        assert_eq!(v, v.transform(&translation(1.0, 2.0, 3.0)));
+        assert_eq!(Vector::new(-6.0, 8.0, 10.0), v.transform(&scaling(2.0, 2.0, 2.0)));
+    }
+
+    #[test]
+    fn constructing_the_identity_from_a_flat_slice() {
+        let id_data = [1.0, 0.0, 0.0, 0.0,
+                       0.0, 1.0, 0.0, 0.0,
+                       0.0, 0.0, 1.0, 0.0,
+                       0.0, 0.0, 0.0, 1.0];
+        let m = Matrix::from_slice(4, 4, &id_data).unwrap();
+
+        assert_eq!(Matrix::identity(), m);
+        assert_eq!(&id_data, m.as_slice());
+    }
+
+    #[test]
+    fn a_flat_slice_round_trip() {
+        let m = Matrix::from_array([[1.0, 2.0, 3.0],
+                                    [4.0, 5.0, 6.0]]);
+        let copy = Matrix::from_slice(m.rows(), m.cols(), m.as_slice()).unwrap();
+
+        assert!(m.strict_eq(&copy));
+        assert!(Matrix::from_slice(3, 2, &[1.0; 5]).is_none());
+        assert!(Matrix::from_slice(0, 0, &[]).is_none());
+    }
+
+    #[test]
+    fn a_well_conditioned_matrix_has_a_small_condition_number() {
+        let m = Matrix::from_array([[3.0, -9.0, 7.0, 3.0],
+                                    [3.0, -8.0, 2.0, -9.0],
+                                    [-4.0, 4.0, 4.0, 1.0],
+                                    [-6.0, 5.0, -1.0, 1.0]]);
+
+        assert!(m.condition_estimate() < 100.0);
+        assert!(m.inverse_well_conditioned(1e8).is_some());
+    }
+
+    #[test]
+    fn a_nearly_singular_matrix_has_a_large_condition_number() {
+        let m = Matrix::from_array([[1.0, 2.0, 3.0, 0.0],
+                                    [2.0, 4.0, 6.0 + 1e-10, 0.0],
+                                    [0.0, 1.0, 1.0, 0.0],
+                                    [0.0, 0.0, 0.0, 1.0]]);
+
+        assert!(m.condition_estimate() > 1e8);
+        assert!(m.inverse_well_conditioned(1e8).is_none());
+    }
+
+    #[test]
+    fn indexing_points_and_vectors_by_axis() {
+        let p = Point::new(1.0, -2.0, 3.5);
+        let v = Vector::new(-4.0, 5.0, 0.25);
+
+        assert_eq!([p.x(), p.y(), p.z()], [p[0], p[1], p[2]]);
+        assert_eq!([v.x(), v.y(), v.z()], [v[0], v[1], v[2]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn indexing_a_point_out_of_range_panics() {
+        let p = Point::new(1.0, 2.0, 3.0);
+        let _ = p[3];
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn indexing_a_vector_out_of_range_panics() {
+        let v = Vector::new(1.0, 2.0, 3.0);
+        let _ = v[3];
+    }
+
+    #[test]
+    fn extracting_the_scale_of_a_rotated_scaling() {
+        let m = rotation_rad_y(0.7) * rotation_rad_x(-1.2) * scaling(2.0, 3.0, 4.0);
+
+        assert!(m.extract_scale().approx_eq(&Vector::new(2.0, 3.0, 4.0), 0.00001));
+        assert!(Matrix::identity().extract_scale().approx_eq(&Vector::new(1.0, 1.0, 1.0), 0.00001));
+    }
+
+    fn assert_matrix_near(expected: &Matrix, actual: &Matrix) {
+        assert!(expected.as_slice().iter().zip(actual.as_slice()).all(|(a, b)| (a - b).abs() < 0.00001),
+                "expected\n{}\nbut got\n{}", expected, actual);
+    }
+
+    #[test]
+    fn decomposing_and_recomposing_a_transformation() {
+        let m = translation(1.0, -2.0, 3.0) * rotation_rad_x(0.8) * scaling(2.0, 3.0, 0.5);
+        let (t, r, s) = m.decompose().unwrap();
+
+        assert!(t.approx_eq(&Vector::new(1.0, -2.0, 3.0), 0.00001));
+        assert_matrix_near(&rotation_rad_x(0.8), &r);
+        assert!(s.approx_eq(&Vector::new(2.0, 3.0, 0.5), 0.00001));
+        assert_matrix_near(&m, &(translation(t.x(), t.y(), t.z()) * r * scaling(s.x(), s.y(), s.z())));
+    }
+
+    #[test]
+    fn decomposing_a_mirroring_transformation() {
+        let m = rotation_rad_y(0.3) * scaling(-1.0, 2.0, 2.0);
+        let (t, r, s) = m.decompose().unwrap();
+
+        assert!(r.det() > 0.0);
+        assert_matrix_near(&m, &(translation(t.x(), t.y(), t.z()) * r * scaling(s.x(), s.y(), s.z())));
+    }
+
+    #[test]
+    fn shears_and_projections_cannot_be_decomposed() {
+        assert!(shearing(0.0, 1.0, 0.0, 0.0, 0.0, 0.0).decompose().is_none());
+        assert!((scaling(1.0, 2.0, 1.0) * rotation_rad_x(0.5)).decompose().is_none());
+        assert!(perspective(1.0, 1.0, 0.1, 100.0).decompose().is_none());
+        assert!(scaling(0.0, 1.0, 1.0).decompose().is_none());
+        assert!(Matrix::new(3, 3).decompose().is_none());
+    }
+
+    #[test]
+    fn the_frobenius_norm_of_the_identity() {
+        assert_eq!(2.0, Matrix::identity().frobenius_norm());
+        assert_eq!(0.0, Matrix::new(3, 2).frobenius_norm());
+    }
+
+    #[test]
+    fn scaling_a_matrix_scales_its_frobenius_norm() {
+        let m = Matrix::from_array([[1.0, -2.0, 3.0],
+                                    [0.5, 4.0, -6.0]]);
+
+        for k in [2.0, -3.0, 0.25].iter() {
+            let data: Vec<f64> = m.as_slice().iter().map(|e| e * k).collect();
+            let scaled = Matrix::from_slice(m.rows(), m.cols(), &data).unwrap();
+
+            assert!((scaled.frobenius_norm() - k.abs() * m.frobenius_norm()).abs() < 0.00001);
+        }
+    }
+
+    #[test]
+    fn rotating_a_vector_around_the_axes() {
+        let quarter = std::f64::consts::PI / 2.0;
+
+        assert!(Vector::new(0.0, 1.0, 0.0).rotate_x(quarter).approx_eq(&Vector::new(0.0, 0.0, 1.0), 0.00001));
+        assert!(Vector::new(0.0, 0.0, 1.0).rotate_y(quarter).approx_eq(&Vector::new(1.0, 0.0, 0.0), 0.00001));
+        assert!(Vector::new(0.0, 1.0, 0.0).rotate_z(quarter).approx_eq(&Vector::new(-1.0, 0.0, 0.0), 0.00001));
+        assert_eq!(Vector::new(1.0, 2.0, 3.0).transform(&rotation_rad_x(0.3)), Vector::new(1.0, 2.0, 3.0).rotate_x(0.3));
+    }
 }
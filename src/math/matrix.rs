@@ -39,7 +39,7 @@
 /// ```
 ///
 /// 3. You can also concatenate transformations in a more natural way through
-/// transformation methods defined for Matrix.
+///    transformation methods defined for Matrix.
 /// ```
 /// use sugar_ray::math::{point::Point, matrix::{Matrix, transformation::*}};
 ///
@@ -57,8 +57,7 @@ pub mod transformation;
 use std::{
     ops,
     cmp,
-    marker::Copy,
-    clone::Clone,
+    fmt,
 };
 
 use super::vector::Vector;
@@ -90,9 +89,21 @@ use transformation::*;
 /// assert_eq!(Matrix::from_vec(vec![vec![0.0, 2.3], vec![7.5, 0.0]]).unwrap(), m);
 /// assert_eq!(7.5, m[1][0]);
 /// ```
+///
+/// # Determinant
+///
+/// The determinant isn't cached but calculated on every call to
+/// [`Matrix::det`], so mutating a cell (e.g. `m[0][1] = 2.3` or
+/// [`Matrix::set`]) is always reflected. This also keeps `Matrix`
+/// `Send` and `Sync`.
+///
+/// # Storage
+///
+/// The cells are stored in a single flat buffer in row-major order,
+/// see [`Matrix::as_slice`] and [`Matrix::from_slice`].
 #[derive(Clone, Debug)]
 pub struct Matrix {
-    m: Vec<Vec<f64>>,
+    m: Vec<f64>, // row-major
     rows: usize,
     cols: usize,
 }
@@ -116,7 +127,7 @@ impl Matrix {
     /// let m: Matrix = Matrix::new(4, 4);
     /// ```
     pub fn new(rows: usize, cols: usize) -> Self {
-        Self { m: vec![vec![0 as f64; cols]; rows], rows, cols }
+        Self { m: vec![0 as f64; rows * cols], rows, cols }
     }
     
     /// Generate a __N__ x __M__ Matrix from an existing vector (Vec).
@@ -154,7 +165,7 @@ impl Matrix {
     /// ```
     pub fn from_vec(v: Vec<Vec<f64>>) -> Option<Matrix> {
         // Non existing Matrix
-        if v.len() == 0 {
+        if v.is_empty() {
             None
         } else {
             let row_len = v[0].len();
@@ -165,8 +176,144 @@ impl Matrix {
                 }
             }
 
-            Some(Matrix { rows: v.len(), cols: row_len, m: v })
+            Some(Matrix { rows: v.len(), cols: row_len, m: v.concat() })
+        }
+    }
+    
+    /// Generate a __N__ x __M__ Matrix from an existing vector (Vec) that
+    /// only contains finite values.
+    ///
+    /// Works like [`from_vec`](Matrix::from_vec) but also returns None if
+    /// any element is `NaN` or infinite.
+    ///
+    /// # Arguments
+    ///
+    /// * `v` - The vector to use
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// assert!(Matrix::from_vec_checked(vec![vec![1.0, 0.0], vec![0.0, 2.0]]).is_some());
+    /// assert!(Matrix::from_vec_checked(vec![vec![1.0, f64::NAN], vec![0.0, 2.0]]).is_none());
+    /// ```
+    pub fn from_vec_checked(v: Vec<Vec<f64>>) -> Option<Matrix> {
+        if v.iter().flatten().any(|e| !e.is_finite()) {
+            return None;
+        }
+
+        Matrix::from_vec(v)
+    }
+    
+    /// Generate a __N__ x __M__ Matrix from a slice of rows.
+    ///
+    /// Works like [`from_vec`](Matrix::from_vec) but copies the values,
+    /// i.e. the rows don't have to be allocated as `Vec`s.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The rows of the matrix
+    ///
+    /// __All rows must have the same length!__
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let m = Matrix::from_rows_slice(&[&[1.0, 0.0], &[0.0, 2.0]]).unwrap();
+    ///
+    /// assert_eq!(Matrix::from_vec(vec![vec![1.0, 0.0], vec![0.0, 2.0]]).unwrap(), m);
+    /// assert!(Matrix::from_rows_slice(&[&[1.0, 0.0], &[2.0]]).is_none());
+    /// ```
+    pub fn from_rows_slice(rows: &[&[f64]]) -> Option<Matrix> {
+        Matrix::from_vec(rows.iter().map(|row| row.to_vec()).collect())
+    }
+    
+    /// Generate a __rows__ x __cols__ Matrix from a flat slice in row-major order.
+    ///
+    /// Returns None if the length of `data` isn't `rows * cols` or if
+    /// the matrix wouldn't have any row.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - The number of rows of the matrix
+    /// * `cols` - The number of columns of the matrix
+    /// * `data` - The cells, row by row
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let m = Matrix::from_slice(2, 3, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+    ///
+    /// assert_eq!(Matrix::from_vec(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]).unwrap(), m);
+    /// assert!(Matrix::from_slice(2, 2, &[1.0, 2.0, 3.0]).is_none());
+    /// ```
+    pub fn from_slice(rows: usize, cols: usize, data: &[f64]) -> Option<Matrix> {
+        if rows == 0 || rows.checked_mul(cols)? != data.len() {
+            return None;
+        }
+
+        Some(Matrix { rows, cols, m: data.to_vec() })
+    }
+    
+    /// Generate a __R__ x __C__ Matrix from a two dimensional array.
+    ///
+    /// The size is known at compile time, so (unlike
+    /// [`from_vec`](Matrix::from_vec)) there is no need to return
+    /// an `Option`.
+    ///
+    /// # Arguments
+    ///
+    /// * `arr` - The rows of the matrix
+    ///
+    /// __The matrix must have at least one row!__
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let m = Matrix::from_array([[1.0, 2.0, 3.0],
+    ///                             [4.0, 5.0, 6.0]]);
+    ///
+    /// assert_eq!(2, m.rows());
+    /// assert_eq!(3, m.cols());
+    /// assert_eq!(6.0, m[1][2]);
+    /// ```
+    pub fn from_array<const R: usize, const C: usize>(arr: [[f64; C]; R]) -> Matrix {
+        assert!(R > 0, "a matrix must have at least one row");
+
+        Matrix { rows: R, cols: C, m: arr.concat() }
+    }
+    
+    /// Generate a __N__ x __N__ Matrix with the given values on its
+    /// diagonal and __0.0__ everywhere else.
+    ///
+    /// # Arguments
+    ///
+    /// * `diag` - The values of the diagonal (top left to bottom right)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let m = Matrix::from_diagonal(&[1.0, 1.0, 1.0, 1.0]);
+    ///
+    /// assert_eq!(Matrix::identity(), m);
+    /// ```
+    pub fn from_diagonal(diag: &[f64]) -> Matrix {
+        let mut m = Matrix::new(diag.len(), diag.len());
+
+        for (i, v) in diag.iter().enumerate() {
+            m[i][i] = *v;
         }
+
+        m
     }
     
     /// Get the number of rows of the matrix.
@@ -179,6 +326,102 @@ impl Matrix {
         self.cols
     }
     
+    /// Check if the matrix has as many rows as columns.
+    ///
+    /// The determinant, minors, cofactors and the inverse are only
+    /// defined for square matrices.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// assert!(Matrix::new(3, 3).is_square());
+    /// assert!(!Matrix::new(2, 4).is_square());
+    /// ```
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+    
+    /// Panic with a descriptive message if the matrix isn't square.
+    fn assert_square(&self, op: &str) {
+        assert!(self.is_square(),
+                "{} is only defined for square matrices, but the matrix is {} x {}",
+                op, self.rows, self.cols);
+    }
+    
+    /// Set the element at row `row` and column `col` to `v`.
+    ///
+    /// This is equivalent to `m[row][col] = v`.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row
+    /// * `col` - The column
+    /// * `v` - The new value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let mut m = Matrix::from_vec(vec![vec![1.0, 5.0], vec![-3.0,2.0]]).unwrap();
+    /// assert_eq!(17.0, m.det());
+    ///
+    /// m.set(0, 0, 2.0);
+    /// assert_eq!(19.0, m.det());
+    /// ```
+    pub fn set(&mut self, row: usize, col: usize, v: f64) {
+        self[row][col] = v;
+    }
+    
+    /// Check if two matrices are exactly equal.
+    ///
+    /// In contrast to `==`, which tolerates tiny floating point errors,
+    /// the elements are compared bit by bit. This means that `0.0` and
+    /// `-0.0` are considered different while `NaN` equals itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The matrix to compare against
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let m1 = Matrix::from_vec(vec![vec![1.0, 2.0]]).unwrap();
+    /// let m2 = Matrix::from_vec(vec![vec![1.0, 2.0 + 1e-15]]).unwrap();
+    ///
+    /// assert_eq!(m1, m2);
+    /// assert!(!m1.strict_eq(&m2));
+    /// assert!(m1.strict_eq(&m1.clone()));
+    /// ```
+    pub fn strict_eq(&self, other: &Matrix) -> bool {
+        self.rows == other.rows && self.cols == other.cols &&
+        self.m.iter()
+            .zip(other.m.iter())
+            .all(|(a, b)| a.to_bits() == b.to_bits())
+    }
+    
+    /// Get the cells of the matrix as a flat slice in row-major order.
+    ///
+    /// This doesn't copy anything, i.e. the slice can be passed
+    /// directly to APIs that expect a flat buffer (e.g. graphics APIs).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let m = Matrix::from_vec(vec![vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap();
+    ///
+    /// assert_eq!(&[1.0, 2.0, 3.0, 4.0], m.as_slice());
+    /// ```
+    pub fn as_slice(&self) -> &[f64] {
+        &self.m
+    }
+    
     /// Transposes a given matrix.
     ///
     /// `transpose` switches the rows and columns of a matrix.
@@ -211,6 +454,100 @@ impl Matrix {
 
         m
     }
+
+    /// Transpose a square matrix in place.
+    ///
+    /// Unlike [`transpose`](Matrix::transpose) this doesn't allocate a new matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix isn't square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let mut m = Matrix::from_vec(vec![vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap();
+    /// m.transpose_in_place();
+    ///
+    /// assert_eq!(Matrix::from_vec(vec![vec![1.0, 3.0], vec![2.0, 4.0]]).unwrap(), m);
+    /// ```
+    pub fn transpose_in_place(&mut self) {
+        self.assert_square("the in place transposition");
+
+        for i in 0..self.rows {
+            for j in i + 1..self.cols {
+                self.m.swap(i * self.cols + j, j * self.cols + i);
+            }
+        }
+    }
+
+    /// Join two matrices side by side.
+    ///
+    /// The columns of `other` are appended to the right of the
+    /// columns of `self`. Returns None if both matrices don't have
+    /// the same number of rows.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The matrix to append on the right
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let a = Matrix::from_vec(vec![vec![1.0], vec![2.0]]).unwrap();
+    /// let b = Matrix::from_vec(vec![vec![3.0, 4.0], vec![5.0, 6.0]]).unwrap();
+    ///
+    /// assert_eq!(Matrix::from_vec(vec![vec![1.0, 3.0, 4.0], vec![2.0, 5.0, 6.0]]).unwrap(),
+    ///            a.hconcat(&b).unwrap());
+    /// assert!(a.hconcat(&Matrix::new(3, 1)).is_none());
+    /// ```
+    pub fn hconcat(&self, other: &Matrix) -> Option<Matrix> {
+        if self.rows != other.rows {
+            return None;
+        }
+
+        Matrix::from_vec((0..self.rows)
+                               .map(|i| self[i].iter().chain(other[i].iter()).copied().collect())
+                               .collect())
+    }
+
+    /// Stack two matrices on top of each other.
+    ///
+    /// The rows of `other` are appended below the rows of `self`.
+    /// Returns None if both matrices don't have the same number
+    /// of columns.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The matrix to append at the bottom
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let a = Matrix::from_vec(vec![vec![1.0, 2.0]]).unwrap();
+    /// let b = Matrix::from_vec(vec![vec![3.0, 4.0]]).unwrap();
+    ///
+    /// assert_eq!(Matrix::from_vec(vec![vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap(),
+    ///            a.vconcat(&b).unwrap());
+    /// assert!(a.vconcat(&Matrix::new(1, 3)).is_none());
+    /// ```
+    pub fn vconcat(&self, other: &Matrix) -> Option<Matrix> {
+        if self.cols != other.cols {
+            return None;
+        }
+
+        let mut m = Matrix::new(self.rows + other.rows, self.cols);
+        m.m[..self.m.len()].copy_from_slice(&self.m);
+        m.m[self.m.len()..].copy_from_slice(&other.m);
+
+        Some(m)
+    }
     
     /// Find the determinant of a matrix.
     ///
@@ -224,14 +561,22 @@ impl Matrix {
     ///
     ///  assert_eq!(17.0, m.det());
     ///  ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix isn't square.
     pub fn det(&self) -> f64 {
-        if self.cols == 2 {
+        self.assert_square("the determinant");
+
+        if self.cols == 1 {
+            self[0][0]
+        } else if self.cols == 2 {
             (self[0][0] * self[1][1]) - (self[0][1] * self[1][0])
         } else {
             let mut det = 0.0;
 
             for c in 0..self.cols {
-                det = det + self[0][c] * self.cofactor(0, c);    
+                det += self[0][c] * self.cofactor(0, c);    
             }
 
             det
@@ -267,151 +612,478 @@ impl Matrix {
     pub fn submatrix(&self, row: usize, col: usize) -> Self {
         let mut m = Matrix::new(self.rows - 1, self.cols - 1);
         let mut r_new = 0;
-        let mut c_new = 0;
 
         for r in 0..self.rows {
-            if r == row {
-                // skip over "deleted" row
-                continue;
-            }
-            
-            c_new = 0;
+            if r == row {
+                // skip over "deleted" row
+                continue;
+            }
+            
+            let mut c_new = 0;
+            for c in 0..self.cols {
+                if c == col {
+                    // skip over "deleted" column
+                    continue;
+                }
+
+                m[r_new][c_new] = self[r][c];
+                c_new += 1;
+            }
+
+            r_new += 1;
+        }
+
+        m
+    }
+    
+    /// Calculate the minor of an element at row `row` and column `col`.
+    ///
+    /// The minor of an element of the matrix M, M(i,j) is the determinant of the
+    /// sub-matrix at (i,j).
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row
+    /// * `col` - The column
+    ///
+    /// # Examples
+    ///
+    /// 1. Calculating the minor of a 3 x 3 matrix
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    /// 
+    /// let m = Matrix::from_vec(vec![vec![3.0,5.0,0.0],
+    ///                          vec![2.0,-1.0,-7.0],
+    ///                           vec![6.0,-1.0,5.0]]).unwrap();
+    ///
+    /// assert_eq!(25.0, m.minor(1, 0));
+    /// ```
+    pub fn minor(&self, row: usize, col: usize) -> f64 {
+        self.assert_square("the minor");
+        self.submatrix(row, col).det()
+    }
+    
+    /// Calculate the cofactor of an element at row `row` and column `col`.
+    ///
+    /// The cofactor is the (possibly) sign changed minor of the element.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row
+    /// * `col` - The column
+    ///
+    /// (row, col) determines the element within the matrix
+    /// for which the cofactor is to be calculated.
+    ///
+    /// # Examples
+    /// 
+    /// 1. Calculating cofactors of a 3 x 3 matrix
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    ///
+    /// let m = Matrix::from_vec(vec![vec![3.0,5.0,0.0],
+    ///                          vec![2.0,-1.0,-7.0],
+    ///                          vec![6.0,-1.0,5.0]]).unwrap();
+    ///
+    /// // cofactor for the element m[0][0]
+    /// assert_eq!(-12.0, m.cofactor(0, 0));
+    /// // cofactor for the element m[1][0]
+    /// assert_eq!(-25.0, m.cofactor(1, 0));
+    /// ```
+    pub fn cofactor(&self, row: usize, col: usize) -> f64 {
+        self.assert_square("the cofactor");
+
+        Matrix::checkerboard_sign(row, col) * self.minor(row, col)
+    }
+
+    /// Get the sign used to turn the minor at (`row`, `col`) into a cofactor.
+    ///
+    /// The signs form a checkerboard pattern starting with `+` in the
+    /// top left corner:
+    ///
+    /// ```text
+    /// + - + -
+    /// - + - +
+    /// + - + -
+    /// - + - +
+    /// ```
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - The row of the element
+    /// * `col` - The column of the element
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// assert_eq!(1.0, Matrix::checkerboard_sign(0, 0));
+    /// assert_eq!(-1.0, Matrix::checkerboard_sign(1, 0));
+    /// assert_eq!(1.0, Matrix::checkerboard_sign(3, 1));
+    /// ```
+    // `usize::is_multiple_of` requires Rust 1.87, the crate doesn't declare a `rust-version`.
+    #[allow(clippy::manual_is_multiple_of)]
+    pub fn checkerboard_sign(row: usize, col: usize) -> f64 {
+        if (row + col) % 2 == 0 { 1.0 } else { -1.0 }
+    }
+    
+    /// Checks if the given matrix (is_inv)ersible.
+    /// 
+    /// A matrix is inversible if it's determinant is
+    /// not equal to zero.
+    pub fn is_inv(&self) -> bool {
+        self.det().abs() != 0.0
+    }
+
+    /// Calculate the Frobenius norm of the matrix.
+    ///
+    /// The norm is the square root of the sum of all squared cells.
+    /// The norm of the difference of two matrices is a measure of how
+    /// far they are apart, e.g. `(m - identity)` for a transformation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let m = Matrix::from_vec(vec![vec![1.0, -2.0], vec![2.0, 4.0]]).unwrap();
+    ///
+    /// assert_eq!(5.0, m.frobenius_norm());
+    /// ```
+    pub fn frobenius_norm(&self) -> f64 {
+        self.m.iter().map(|e| e * e).sum::<f64>().sqrt()
+    }
+
+    /// Calculates the rank of the given matrix.
+    ///
+    /// The matrix is reduced to row echelon form using Gaussian
+    /// elimination (with partial pivoting). The rank is the number
+    /// of pivots whose absolute value is greater than [`EPSILON`].
+    ///
+    /// A N x N matrix with a rank less than N isn't invertible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let a = Matrix::from_vec(vec![vec![1.0, 2.0, 3.0],
+    ///                               vec![2.0, 4.0, 6.0],
+    ///                               vec![0.0, 1.0, 1.0]]).unwrap();
+    ///
+    /// assert_eq!(4, Matrix::identity().rank());
+    /// assert_eq!(2, a.rank());
+    /// ```
+    ///
+    /// [`EPSILON`]: super::EPSILON
+    pub fn rank(&self) -> usize {
+        self.rank_with_tolerance(super::EPSILON)
+    }
+
+    /// Checks if the given matrix is invertible using row reduction.
+    ///
+    /// Unlike [`is_inv`](Matrix::is_inv), which calculates the determinant
+    /// using cofactor expansion (__O(n!)__), this reduces the matrix to row
+    /// echelon form (__O(n³)__) and looks for a zero pivot. A pivot counts
+    /// as zero if it's negligible compared to the largest element of the
+    /// matrix. Non-square matrices aren't invertible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let singular = Matrix::from_vec(vec![vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap();
+    ///
+    /// assert!(Matrix::identity().is_inv_fast());
+    /// assert!(!singular.is_inv_fast());
+    /// ```
+    pub fn is_inv_fast(&self) -> bool {
+        if !self.is_square() {
+            return false;
+        }
+
+        let largest = self.m.iter().fold(0.0_f64, |acc, e| acc.max(e.abs()));
+        if largest == 0.0 {
+            return false;
+        }
+
+        let tolerance = largest * self.rows as f64 * f64::EPSILON;
+        self.rank_with_tolerance(tolerance) == self.rows
+    }
+
+    /// Reduce the matrix to row echelon form using Gaussian elimination
+    /// (with partial pivoting) and count the pivots whose absolute value
+    /// is greater than `tolerance`.
+    fn rank_with_tolerance(&self, tolerance: f64) -> usize {
+        self.pivots(tolerance).len()
+    }
+
+    /// Reduce the matrix to row echelon form using Gaussian elimination
+    /// (with partial pivoting) and return the absolute values of all
+    /// pivots that are greater than `tolerance`.
+    fn pivots(&self, tolerance: f64) -> Vec<f64> {
+        let mut m: Vec<Vec<f64>> = (0..self.rows).map(|i| self[i].to_vec()).collect();
+        let mut pivots = Vec::with_capacity(self.rows);
+        let mut rank = 0;
+
+        for col in 0..self.cols {
+            if rank == self.rows {
+                break;
+            }
+
+            // Use the row with the largest absolute value in this column as pivot.
+            let pivot = (rank..self.rows)
+                .max_by(|&a, &b| m[a][col].abs().partial_cmp(&m[b][col].abs()).unwrap_or(cmp::Ordering::Equal))
+                .unwrap();
+
+            if m[pivot][col].abs() <= tolerance {
+                continue;
+            }
+
+            m.swap(rank, pivot);
+
+            let (upper, lower) = m.split_at_mut(rank + 1);
+            let pivot_row = &upper[rank];
+            for row in lower.iter_mut() {
+                let factor = row[col] / pivot_row[col];
+                for (x, p) in row.iter_mut().zip(pivot_row.iter()).skip(col) {
+                    *x -= factor * p;
+                }
+            }
+
+            pivots.push(m[rank][col].abs());
+            rank += 1;
+        }
+
+        pivots
+    }
+    
+    /// Estimate the condition number of the matrix.
+    ///
+    /// The estimate is the ratio of the largest to the smallest absolute
+    /// pivot of the Gaussian elimination (with partial pivoting). A small
+    /// value (close to 1) means the matrix is well-conditioned. The larger
+    /// the value the more precision is lost when the matrix is inverted.
+    /// A singular matrix has an infinite condition number.
+    ///
+    /// __The matrix must be square!__
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::{Matrix, transformation::scaling};
+    ///
+    /// assert_eq!(1.0, Matrix::identity().condition_estimate());
+    /// assert_eq!(4.0, scaling(2.0, 0.5, 1.0).condition_estimate());
+    /// assert_eq!(f64::INFINITY, scaling(0.0, 1.0, 1.0).condition_estimate());
+    /// ```
+    pub fn condition_estimate(&self) -> f64 {
+        self.assert_square("the condition number");
+
+        let pivots = self.pivots(0.0);
+        if pivots.len() < self.rows {
+            return f64::INFINITY;
+        }
+
+        let largest = pivots.iter().fold(0.0_f64, |acc, p| acc.max(*p));
+        let smallest = pivots.iter().fold(f64::INFINITY, |acc, p| acc.min(*p));
+        largest / smallest
+    }
+
+    /// Calculates the inverse of the matrix if it isn't ill-conditioned.
+    ///
+    /// Works like [`inverse`](Matrix::inverse) but also returns None if
+    /// the [`condition_estimate`](Matrix::condition_estimate) is greater
+    /// than `max_condition`, i.e. if the inverse would be dominated by
+    /// rounding errors.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_condition` - The largest acceptable condition number
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::{Matrix, transformation::scaling};
+    ///
+    /// assert!(scaling(2.0, 2.0, 2.0).inverse_well_conditioned(1e10).is_some());
+    /// assert!(scaling(1e-12, 1.0, 1.0).inverse().is_some());
+    /// assert!(scaling(1e-12, 1.0, 1.0).inverse_well_conditioned(1e10).is_none());
+    /// ```
+    pub fn inverse_well_conditioned(&self, max_condition: f64) -> Option<Self> {
+        if self.condition_estimate() > max_condition {
+            return None;
+        }
+
+        self.inverse()
+    }
+    
+    /// Calculates the inverse of the given matrix.
+    ///
+    /// A matrix has an inverse if it's determinant is
+    /// not equal to zero.
+    /// 
+    ///
+    /// # Examples
+    ///
+    /// If you have three matrices __A__, __B__ and __C__ where
+    /// __A * B = C__, then you can calculate __A from C__
+    /// by __multiplying C by the inverse of B__.
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let a = Matrix::from_vec(vec![vec![3.0,-9.0,7.0,3.0],
+    ///                           vec![3.0,-8.0,2.0,-9.0],
+    ///                          vec![-4.0,4.0,4.0,1.0],
+    ///                          vec![-6.0,5.0,-1.0,1.0]]).unwrap();
+    ///
+    /// let b = Matrix::from_vec(vec![vec![8.0,2.0,2.0,2.0],
+    ///                          vec![3.0,-1.0,7.0,0.0],
+    ///                           vec![7.0,0.0,5.0,4.0],
+    ///                           vec![6.0,-2.0,0.0,5.0]]).unwrap();
+    ///
+    /// let c = a.mul(&b);
+    /// assert_eq!(a, c.mul(&b.inverse().unwrap()));
+    /// ```
+    pub fn inverse(&self) -> Option<Self> {
+        self.assert_square("the inverse");
+
+        if !self.is_inv() {
+            return None;
+        }
+
+        // Step 1: construct a matrix of cofactors
+        // Step 2: Transpose matrix of cofactors (switch rows and columns for each element)
+        //
+        // Both steps result in the adjugate.
+        let mut m = self.adjugate();
+
+        // Step 3: Divide each element by the determinant
+        //         of the original matrix
+        let f = 1.0 / self.det();
+        for r in 0..self.rows {
             for c in 0..self.cols {
-                if c == col {
-                    // skip over "deleted" column
-                    continue;
-                }
-
-                m[r_new][c_new] = self[r][c];
-                c_new += 1;
+                m[r][c] *= f;
             }
-
-            r_new += 1;
         }
 
-        m
+        Some(m)
     }
     
-    /// Calculate the minor of an element at row `row` and column `col`.
+    /// Solve the linear system __A * x = b__ for __x__.
     ///
-    /// The minor of an element of the matrix M, M(i,j) is the determinant of the
-    /// sub-matrix at (i,j).
+    /// The solution is calculated by multiplying the inverse of
+    /// __A__ (`self`) with __b__ (treated as a column vector).
+    ///
+    /// Returns None if the matrix isn't square, isn't invertible or
+    /// `b` doesn't have one value per row.
     ///
     /// # Arguments
     ///
-    /// * `row` - The row
-    /// * `col` - The column
+    /// * `b` - The right hand side of the system
     ///
     /// # Examples
     ///
-    /// 1. Calculating the minor of a 3 x 3 matrix
     /// ```
     /// use sugar_ray::math::matrix::Matrix;
-    /// 
-    /// let m = Matrix::from_vec(vec![vec![3.0,5.0,0.0],
-    ///                          vec![2.0,-1.0,-7.0],
-    ///                           vec![6.0,-1.0,5.0]]).unwrap();
     ///
-    /// assert_eq!(25.0, m.minor(1, 0));
+    /// // 2x + y = 5 and x - y = 1
+    /// let a = Matrix::from_vec(vec![vec![2.0, 1.0], vec![1.0, -1.0]]).unwrap();
+    /// let x = a.solve(&[5.0, 1.0]).unwrap();
+    ///
+    /// assert!((x[0] - 2.0).abs() < 0.00001);
+    /// assert!((x[1] - 1.0).abs() < 0.00001);
     /// ```
-    pub fn minor(&self, row: usize, col: usize) -> f64 {
-        self.submatrix(row, col).det()
+    pub fn solve(&self, b: &[f64]) -> Option<Vec<f64>> {
+        if !self.is_square() || b.len() != self.rows {
+            return None;
+        }
+
+        let inv = self.inverse()?;
+
+        Some(inv.m.chunks(inv.cols)
+                  .map(|row| row.iter().zip(b.iter()).map(|(a, b)| a * b).sum())
+                  .collect())
     }
     
-    /// Calculate the cofactor of an element at row `row` and column `col`.
+    /// Calculate the adjugate of a matrix.
     ///
-    /// The cofactor is the (possibly) sign changed minor of the element.
+    /// The adjugate is the transpose of the [`cofactor_matrix`](Matrix::cofactor_matrix).
+    /// Multiplying a matrix with its adjugate results in the identity matrix
+    /// scaled by the determinant.
     ///
-    /// # Arguments
+    /// # Examples
     ///
-    /// * `row` - The row
-    /// * `col` - The column
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
     ///
-    /// (row, col) determines the element within the matrix
-    /// for which the cofactor is to be calculated.
+    /// let m = Matrix::from_vec(vec![vec![1.0, 5.0], vec![-3.0,2.0]]).unwrap();
+    ///
+    /// assert_eq!(Matrix::from_vec(vec![vec![2.0, -5.0], vec![3.0, 1.0]]).unwrap(), m.adjugate());
+    /// ```
+    pub fn adjugate(&self) -> Self {
+        self.cofactor_matrix().transpose()
+    }
+    
+    /// Calculate the matrix of minors.
+    ///
+    /// Each element (row, col) of the resulting matrix is
+    /// the [`minor`](Matrix::minor) of the element (row, col)
+    /// of the given matrix.
     ///
     /// # Examples
-    /// 
-    /// 1. Calculating cofactors of a 3 x 3 matrix
+    ///
     /// ```
     /// use sugar_ray::math::matrix::Matrix;
     ///
-    ///
     /// let m = Matrix::from_vec(vec![vec![3.0,5.0,0.0],
     ///                          vec![2.0,-1.0,-7.0],
     ///                          vec![6.0,-1.0,5.0]]).unwrap();
     ///
-    /// // cofactor for the element m[0][0]
-    /// assert_eq!(-12.0, m.cofactor(0, 0));
-    /// // cofactor for the element m[1][0]
-    /// assert_eq!(-25.0, m.cofactor(1, 0));
+    /// assert_eq!(25.0, m.minor_matrix()[1][0]);
     /// ```
-    pub fn cofactor(&self, row: usize, col: usize) -> f64 {
-        let mut d = self.minor(row, col);
+    pub fn minor_matrix(&self) -> Self {
+        let mut m = Matrix::new(self.rows, self.cols);
 
-        if (row +  col) % 2 != 0 {
-            d = -d;
+        for r in 0..self.rows {
+            for c in 0..self.cols {
+                m[r][c] = self.minor(r, c);
+            }
         }
 
-        d
-    }
-    
-    /// Checks if the given matrix (is_inv)ersible.
-    /// 
-    /// A matrix is inversible if it's determinant is
-    /// not equal to zero.
-    pub fn is_inv(&self) -> bool {
-        self.det().abs() != 0.0
+        m
     }
     
-    /// Calculates the inverse of the given matrix.
+    /// Calculate the matrix of cofactors.
     ///
-    /// A matrix has an inverse if it's determinant is
-    /// not equal to zero.
-    /// 
+    /// Each element (row, col) of the resulting matrix is
+    /// the [`cofactor`](Matrix::cofactor) of the element (row, col)
+    /// of the given matrix.
     ///
     /// # Examples
     ///
-    /// If you have three matrices __A__, __B__ and __C__ where
-    /// __A * B = C__, then you can calculate __A from C__
-    /// by __multiplying C by the inverse of B__.
     /// ```
     /// use sugar_ray::math::matrix::Matrix;
     ///
-    /// let a = Matrix::from_vec(vec![vec![3.0,-9.0,7.0,3.0],
-    ///                           vec![3.0,-8.0,2.0,-9.0],
-    ///                          vec![-4.0,4.0,4.0,1.0],
-    ///                          vec![-6.0,5.0,-1.0,1.0]]).unwrap();
-    ///
-    /// let b = Matrix::from_vec(vec![vec![8.0,2.0,2.0,2.0],
-    ///                          vec![3.0,-1.0,7.0,0.0],
-    ///                           vec![7.0,0.0,5.0,4.0],
-    ///                           vec![6.0,-2.0,0.0,5.0]]).unwrap();
+    /// let m = Matrix::from_vec(vec![vec![3.0,5.0,0.0],
+    ///                          vec![2.0,-1.0,-7.0],
+    ///                          vec![6.0,-1.0,5.0]]).unwrap();
     ///
-    /// let c = a.mul(&b);
-    /// assert_eq!(a, c.mul(&b.inverse().unwrap()));
+    /// assert_eq!(-25.0, m.cofactor_matrix()[1][0]);
     /// ```
-    pub fn inverse(&self) -> Option<Self> {
-        if !self.is_inv() {
-            return None;
-        }
+    pub fn cofactor_matrix(&self) -> Self {
+        let mut m = self.minor_matrix();
 
-        let mut m = Matrix::new(self.rows, self.cols);
-        let det = self.det();
-
-        // Setp 1: construct a matrix of cofactors
-        // Step 2: Transpose matrix of cofactors (switch rows and columns for each element)
-        // Step 3:  Divide each element by the determinant
-        //          of the original matrix
-        //
-        //  All steps are executed in a single loop.
         for r in 0..self.rows {
             for c in 0..self.cols {
-                m[c][r] = self.cofactor(r, c) / det;
+                m[r][c] *= Matrix::checkerboard_sign(r, c);
             }
         }
 
-        Some(m)
+        m
     }
 
     /* Multiplies to matrices.
@@ -424,15 +1096,7 @@ impl Matrix {
                 "Number of Columns of first matrix must be equal to the number of rows of the second.");  
         
         let mut matrix = Matrix::new(self.rows, other.cols);
-        let n = self.cols;
-        
-        for i in 0..matrix.rows {
-            for j in 0..matrix.cols {
-                for k in 0..n {
-                    matrix[i][j] = matrix[i][j] + (self[i][k] * other[k][j]);
-                }
-            }
-        }
+        self.mul_into(other, &mut matrix);
 
         matrix
     }
@@ -468,20 +1132,108 @@ impl Matrix {
         self._mul(other)
     }
     
+    /// Multiply two matrices and write the result into `out`.
+    ///
+    /// Works like [`mul`](Matrix::mul) but doesn't allocate a new matrix,
+    /// i.e. `out` can be reused, e.g. within a loop. The previous
+    /// content of `out` is overwritten.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The matrix to multiply with
+    /// * `out` - The matrix the result is written to
+    ///
+    /// __`out` must have as many rows as `self` and as many columns as `other`!__
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::{Matrix, transformation::{translation, scaling}};
+    ///
+    /// let a = translation(1.0, 2.0, 3.0);
+    /// let b = scaling(2.0, 2.0, 2.0);
+    /// let mut out = Matrix::new(4, 4);
+    ///
+    /// a.mul_into(&b, &mut out);
+    ///
+    /// assert_eq!(a.mul(&b), out);
+    /// ```
+    pub fn mul_into(&self, other: &Self, out: &mut Self) {
+        assert!(self.cols == other.rows, 
+                "Number of Columns of first matrix must be equal to the number of rows of the second.");  
+        assert!(out.rows == self.rows && out.cols == other.cols,
+                "The output matrix must have the dimensions of the product.");
+
+        for (i, row) in out.m.chunks_mut(other.cols).enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..self.cols).map(|k| self[i][k] * other[k][j]).sum();
+            }
+        }
+    }
+    
     /// Multiply a matrix with a Point.
+    ///
+    /// The point is treated as homogeneous coordinate with __w = 1__.
+    /// If the resulting __w__ isn't 1 (e.g. for a perspective projection),
+    /// the coordinates are divided by __w__ (perspective divide). A
+    /// resulting __w__ of 0 (a point at infinity) is left undivided.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::{point::Point, matrix::Matrix};
+    ///
+    /// // Projects a point onto the plane z = 1.
+    /// let m = Matrix::from_vec(vec![vec![1.0, 0.0, 0.0, 0.0],
+    ///                               vec![0.0, 1.0, 0.0, 0.0],
+    ///                               vec![0.0, 0.0, 1.0, 0.0],
+    ///                               vec![0.0, 0.0, 1.0, 0.0]]).unwrap();
+    ///
+    /// assert_eq!(Point::new(1.0, 2.0, 1.0), m.mul_point(&Point::new(2.0, 4.0, 2.0)));
+    /// ```
     pub fn mul_point(&self, other: &Point) -> Point {
-        Point::new(
-        (self[0][0] * other.x() + self[0][1] * other.y() + self[0][2] * other.z() + self[0][3] * 1.0), 
-        (self[1][0] * other.x() + self[1][1] * other.y() + self[1][2] * other.z() + self[1][3] * 1.0),
-        (self[2][0] * other.x() + self[2][1] * other.y() + self[2][2] * other.z() + self[2][3] * 1.0))
+        let x = self[0][0] * other.x() + self[0][1] * other.y() + self[0][2] * other.z() + self[0][3] * 1.0;
+        let y = self[1][0] * other.x() + self[1][1] * other.y() + self[1][2] * other.z() + self[1][3] * 1.0;
+        let z = self[2][0] * other.x() + self[2][1] * other.y() + self[2][2] * other.z() + self[2][3] * 1.0;
+        let w = self[3][0] * other.x() + self[3][1] * other.y() + self[3][2] * other.z() + self[3][3] * 1.0;
+
+        if w == 1.0 || w == 0.0 {
+            Point::new(x, y, z)
+        } else {
+            Point::new(x / w, y / w, z / w)
+        }
     }
     
+    /// Multiply a matrix with each of the given points.
+    ///
+    /// # Arguments
+    ///
+    /// * `points` - The points to transform
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::{point::Point, matrix::transformation::translation};
+    ///
+    /// let t = translation(1.0, 2.0, 3.0);
+    /// let points = t.apply_to_points(&[Point::new(0.0, 0.0, 0.0), Point::new(1.0, 1.0, 1.0)]);
+    ///
+    /// assert_eq!(vec![Point::new(1.0, 2.0, 3.0), Point::new(2.0, 3.0, 4.0)], points);
+    /// ```
+    pub fn apply_to_points(&self, points: &[Point]) -> Vec<Point> {
+        points.iter().map(|p| self.mul_point(p)).collect()
+    }
+
     /// Multiply a matrix with a Vector.
+    ///
+    /// The vector is treated as homogeneous coordinate with __w = 0__,
+    /// so it isn't affected by translations. A vector describes a
+    /// direction, i.e. no perspective divide is applied.
     pub fn mul_vec(&self, other: &Vector) -> Vector {
         Vector::new(
-        (self[0][0] * other.x() + self[0][1] * other.y() + self[0][2] * other.z() + self[0][3] * 0.0), 
-        (self[1][0] * other.x() + self[1][1] * other.y() + self[1][2] * other.z() + self[1][3] * 0.0),
-        (self[2][0] * other.x() + self[2][1] * other.y() + self[2][2] * other.z() + self[2][3] * 0.0))
+        self[0][0] * other.x() + self[0][1] * other.y() + self[0][2] * other.z() + self[0][3] * 0.0, 
+        self[1][0] * other.x() + self[1][1] * other.y() + self[1][2] * other.z() + self[1][3] * 0.0,
+        self[2][0] * other.x() + self[2][1] * other.y() + self[2][2] * other.z() + self[2][3] * 0.0)
     }
     
     ///Round each element to its nearest integer.
@@ -510,7 +1262,139 @@ impl Matrix {
                               vec![0.0,0.0,1.0,0.0],
                               vec![0.0,0.0,0.0,1.0]]).unwrap()
     }
-    
+
+    /// Extract the scale factors of a 4 x 4 transformation.
+    ///
+    /// The scale factors are the lengths of the first three columns,
+    /// i.e. of the transformed x, y and z axes. Rotations and
+    /// translations don't change these lengths, so the scaling of
+    /// e.g. `rotation * scaling` can still be recovered. Negative
+    /// scale factors (mirroring) are reported as positive values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix isn't a 4 x 4 matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::{vector::Vector, matrix::transformation::{translation, scaling}};
+    ///
+    /// let m = translation(1.0, 2.0, 3.0) * scaling(2.0, 3.0, 4.0);
+    ///
+    /// assert_eq!(Vector::new(2.0, 3.0, 4.0), m.extract_scale());
+    /// ```
+    pub fn extract_scale(&self) -> Vector {
+        assert!(self.rows == 4 && self.cols == 4,
+                "the scale can only be extracted from a 4 x 4 matrix, but the matrix is {} x {}",
+                self.rows, self.cols);
+
+        let column = |c: usize| Vector::new(self[0][c], self[1][c], self[2][c]).mag();
+
+        Vector::new(column(0), column(1), column(2))
+    }
+
+    /// Decompose a 4 x 4 transformation into translation, rotation and scale.
+    ///
+    /// Returns `(translation, rotation, scale)` so that the matrix equals
+    /// `translation(t) * rotation * scaling(s)`. The rotation is returned
+    /// as 4 x 4 transformation. A mirroring transformation results in a
+    /// negative x scale factor, so the rotation is always a proper rotation.
+    ///
+    /// Returns None if the matrix isn't a 4 x 4 matrix or if it can't be
+    /// described that way, i.e. if it's a projection, contains a shear (or
+    /// a scaling that isn't applied before the rotation) or collapses an axis.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::{
+    ///     vector::Vector,
+    ///     matrix::{Matrix, transformation::{translation, scaling, shearing}},
+    /// };
+    ///
+    /// let (t, r, s) = (translation(1.0, 2.0, 3.0) * scaling(2.0, 2.0, 2.0)).decompose().unwrap();
+    ///
+    /// assert_eq!(Vector::new(1.0, 2.0, 3.0), t);
+    /// assert_eq!(Matrix::identity(), r);
+    /// assert_eq!(Vector::new(2.0, 2.0, 2.0), s);
+    /// assert!(shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0).decompose().is_none());
+    /// ```
+    pub fn decompose(&self) -> Option<(Vector, Matrix, Vector)> {
+        if self.rows != 4 || self.cols != 4 {
+            return None;
+        }
+
+        // A projection doesn't keep w = 1.
+        let last_row = [0.0, 0.0, 0.0, 1.0];
+        if self[3].iter().zip(last_row.iter()).any(|(a, b)| (a - b).abs() > super::EPSILON) {
+            return None;
+        }
+
+        let translation = Vector::new(self[0][3], self[1][3], self[2][3]);
+        let extracted = self.extract_scale();
+        let mut scale = [extracted.x(), extracted.y(), extracted.z()];
+        let mut rotation = Matrix::identity();
+
+        for (c, s) in scale.iter().enumerate() {
+            if *s <= super::EPSILON {
+                return None;
+            }
+            for r in 0..3 {
+                rotation[r][c] = self[r][c] / s;
+            }
+        }
+
+        // The columns of a rotation are orthonormal. Otherwise the
+        // matrix contains a shear.
+        let m = rotation.transpose().mul(&rotation);
+        if m.as_slice().iter().zip(Matrix::identity().as_slice()).any(|(a, b)| (a - b).abs() > super::EPSILON) {
+            return None;
+        }
+
+        // A mirroring is moved into the scale, so the rotation is proper.
+        if rotation.det() < 0.0 {
+            scale[0] = -scale[0];
+            for r in 0..3 {
+                rotation[r][0] = -rotation[r][0];
+            }
+        }
+
+        Some((translation, rotation, Vector::new(scale[0], scale[1], scale[2])))
+    }
+
+    /// Pad a 3 x 3 matrix (e.g. a rotation) to a 4 x 4 transformation matrix.
+    ///
+    /// The 3 x 3 matrix becomes the upper left (linear) part. The
+    /// bottom right cell is __1.0__ and all other new cells are __0.0__,
+    /// i.e. the resulting transformation doesn't translate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix isn't a 3 x 3 matrix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let m = Matrix::from_diagonal(&[2.0, 3.0, 4.0]);
+    ///
+    /// assert_eq!(Matrix::from_diagonal(&[2.0, 3.0, 4.0, 1.0]), m.to_homogeneous());
+    /// ```
+    pub fn to_homogeneous(&self) -> Self {
+        assert!(self.rows == 3 && self.cols == 3,
+                "only a 3 x 3 matrix can be padded to a homogeneous 4 x 4 matrix, but the matrix is {} x {}",
+                self.rows, self.cols);
+
+        let mut m = Matrix::identity();
+        for (r, row) in self.m.chunks(self.cols).enumerate() {
+            m[r][..3].copy_from_slice(row);
+        }
+
+        m
+    }
+
     /// Apply rotation on the x-axis to the given matrix.
     ///
     /// # Arguments
@@ -652,16 +1536,18 @@ impl Matrix {
 }
 
 impl ops::Index<usize> for Matrix {
-    type Output = Vec<f64>;
+    type Output = [f64];
 
-    fn index(&self, i: usize) -> &Vec<f64> {
-        &self.m[i]
+    fn index(&self, i: usize) -> &[f64] {
+        assert!(i < self.rows, "row {} is out of bounds for a matrix with {} rows", i, self.rows);
+        &self.m[i * self.cols..(i + 1) * self.cols]
     }
 }
 
 impl ops::IndexMut<usize> for Matrix {
     fn index_mut(&mut self, i: usize) -> &mut Self::Output {
-        &mut self.m[i]
+        assert!(i < self.rows, "row {} is out of bounds for a matrix with {} rows", i, self.rows);
+        &mut self.m[i * self.cols..(i + 1) * self.cols]
     }
 }
 
@@ -682,10 +1568,7 @@ impl ops::Mul<Point> for Matrix {
     type Output = Point;
 
     fn mul(self, other: Point) -> Point {
-        Point::new(
-            (self[0][0] * other.x() + self[0][1] * other.y() + self[0][2] * other.z() + self[0][3] * 1.0), 
-            (self[1][0] * other.x() + self[1][1] * other.y() + self[1][2] * other.z() + self[1][3] * 1.0),
-            (self[2][0] * other.x() + self[2][1] * other.y() + self[2][2] * other.z() + self[2][3] * 1.0))
+        self.mul_point(&other)
     }
 }
 
@@ -693,14 +1576,7 @@ impl ops::Mul<Vector> for Matrix {
     type Output = Vector;
 
     fn mul(self, other: Vector) -> Vector {
-        Vector::new(
-
-
-
-
-            (self[0][0] * other.x() + self[0][1] * other.y() + self[0][2] * other.z() + self[0][3] * 0.0), 
-            (self[1][0] * other.x() + self[1][1] * other.y() + self[1][2] * other.z() + self[1][3] * 0.0),
-            (self[2][0] * other.x() + self[2][1] * other.y() + self[2][2] * other.z() + self[2][3] * 0.0))
+        self.mul_vec(&other)
     }
 }
 
@@ -714,28 +1590,53 @@ impl cmp::PartialEq for Matrix {
         //const EPSILON: f64 = f64::EPSILON * 20.0;
         const EPSILON: f64 = 0.00000000000001;
         
-        // iterate over rows
-        for r in 0..self.rows {
-            // iterate over columns
-            for c in 0..self.cols {
-                if (self[r][c] - other[r][c]).abs() > EPSILON   {
-                    return false;
-                }
-            }
-        }
-
-        true
+        // stops at the first element that differs
+        self.m.iter()
+            .zip(other.m.iter())
+            .all(|(a, b)| (a - b).abs() <= EPSILON)
     }
 }
 
+impl fmt::Display for Matrix {
+    /// Print the matrix row by row.
+    ///
+    /// All columns are right-aligned and printed with a fixed
+    /// precision. The precision of the formatter is used if
+    /// specified (e.g. `{:.3}`), otherwise it defaults to 3.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::matrix::Matrix;
+    ///
+    /// let m = Matrix::from_vec(vec![vec![1.0, -2.5], vec![10.25, 0.0]]).unwrap();
+    ///
+    /// assert_eq!(" 1.00 -2.50\n10.25  0.00", format!("{:.2}", m));
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let precision = f.precision().unwrap_or(3);
+        
+        // Format every cell first to find the widest one.
+        let cells: Vec<Vec<String>> = (0..self.rows)
+            .map(|i| self[i].iter().map(|v| format!("{:.*}", precision, v)).collect())
+            .collect();
 
+        let width = cells.iter().flatten().map(|c| c.len()).max().unwrap_or(0);
 
+        for (r, row) in cells.iter().enumerate() {
+            if r > 0 {
+                writeln!(f)?;
+            }
 
+            for (c, cell) in row.iter().enumerate() {
+                if c > 0 {
+                    write!(f, " ")?;
+                }
 
+                write!(f, "{:>width$}", cell, width = width)?;
+            }
+        }
 
-
-
-
-
-
-
+        Ok(())
+    }
+}
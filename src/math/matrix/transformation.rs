@@ -1,7 +1,7 @@
 use crate::math::{
+    matrix::Matrix,
     point::Point,
     vector::Vector,
-    matrix::Matrix,
 };
 
 /// Create a 4 x 4 translation matrix.
@@ -67,10 +67,7 @@ pub fn translation(x: f64, y: f64, z: f64) -> Matrix {
 /// assert_eq!(Vector::new(-8.0,18.0,32.0), t * v);
 /// ```
 pub fn scaling(x: f64, y: f64, z: f64) -> Matrix {
-    Matrix::from_vec(vec![vec![x,0.0,0.0,0.0],
-                      vec![0.0,y,0.0,0.0],
-                      vec![0.0,0.0,z,0.0],
-                      vec![0.0,0.0,0.0,1.0]]).unwrap()
+    Matrix::from_diagonal(&[x, y, z, 1.0])
 }
 
 /// Translate degree into radians.
@@ -225,15 +222,121 @@ pub fn shearing(xpy: f64, xpz: f64, ypx: f64, ypz: f64, zpx: f64, zpy: f64) -> M
                      vec![0.0, 0.0, 0.0, 1.0]]).unwrap()
 }
 
+/// Create a view transformation that orients the world relative to the eye.
+///
+/// # Arguments
+///
+/// * `from` - The position of the eye
+/// * `to` - The point the eye looks at
+/// * `up` - A vector pointing (approximately) upwards
+///
+/// # Examples
+///
+/// ```
+/// use sugar_ray::math::{point::Point, vector::Vector, matrix::{Matrix, transformation::*}};
+///
+/// // The default orientation: looking from the origin into negative z
+/// let t = view_transform(&Point::new(0.0, 0.0, 0.0),
+///                        &Point::new(0.0, 0.0, -1.0),
+///                        &Vector::new(0.0, 1.0, 0.0));
+///
+/// assert_eq!(Matrix::identity(), t);
+/// ```
+pub fn view_transform(from: &Point, to: &Point, up: &Vector) -> Matrix {
+    let forward = (*to - *from).norm_cpy();
+    let left = forward.cross(&up.norm_cpy());
+    let true_up = left.cross(&forward);
+
+    let orientation = Matrix::from_vec(vec![vec![left.x(), left.y(), left.z(), 0.0],
+                                            vec![true_up.x(), true_up.y(), true_up.z(), 0.0],
+                                            vec![-forward.x(), -forward.y(), -forward.z(), 0.0],
+                                            vec![0.0, 0.0, 0.0, 1.0]]).unwrap();
+
+    orientation * translation(-from.x(), -from.y(), -from.z())
+}
+
+/// Create a 4 x 4 perspective projection matrix.
+///
+/// The matrix maps the view frustum of a camera at the origin looking
+/// toward negative z into normalized device coordinates (NDC), i.e. the
+/// cube from (-1, -1, -1) to (1, 1, 1). Points on the near plane end up
+/// at `z = -1` and points on the far plane at `z = 1` (OpenGL convention).
+///
+/// The perspective divide is applied when the matrix is multiplied with a
+/// Point (see [`Matrix::mul_point`]).
+///
+/// # Arguments
+///
+/// * `fov` - The vertical field of view in __radians__
+/// * `aspect` - The ratio of width to height
+/// * `near` - The (positive) distance to the near plane
+/// * `far` - The (positive) distance to the far plane
+///
+/// # Examples
+///
+/// ```
+/// use sugar_ray::math::{point::Point, matrix::{Matrix, transformation::*}};
+///
+/// let p = perspective(std::f64::consts::PI / 2.0, 1.0, 1.0, 10.0);
+///
+/// assert_eq!(Point::new(0.0, 0.0, -1.0), p * Point::new(0.0, 0.0, -1.0));
+/// ```
+pub fn perspective(fov: f64, aspect: f64, near: f64, far: f64) -> Matrix {
+    let f = 1.0 / (fov / 2.0).tan();
+
+    Matrix::from_vec(vec![vec![f / aspect, 0.0, 0.0, 0.0],
+                          vec![0.0, f, 0.0, 0.0],
+                          vec![0.0, 0.0, (far + near) / (near - far), 2.0 * far * near / (near - far)],
+                          vec![0.0, 0.0, -1.0, 0.0]]).unwrap()
+}
+
+/// Create a 4 x 4 matrix that mirrors across the plane `x = k`.
+///
+/// The plane is moved to the origin, the x coordinate is scaled
+/// by -1 and the plane is moved back again.
+///
+/// # Arguments
+///
+/// * `k` - The x coordinate of the plane
+///
+/// # Examples
+///
+/// ```
+/// use sugar_ray::math::{point::Point, matrix::{Matrix, transformation::*}};
+///
+/// let t = reflection_x(2.0);
+/// let p = Point::new(5.0, 0.0, 0.0);
+///
+/// assert_eq!(Point::new(-1.0, 0.0, 0.0), t * p);
+/// ```
+pub fn reflection_x(k: f64) -> Matrix {
+    translation(k, 0.0, 0.0) * scaling(-1.0, 1.0, 1.0) * translation(-k, 0.0, 0.0)
+}
+
+/// Create a 4 x 4 matrix that mirrors across the plane `y = k`.
+///
+/// # Arguments
+///
+/// * `k` - The y coordinate of the plane
+pub fn reflection_y(k: f64) -> Matrix {
+    translation(0.0, k, 0.0) * scaling(1.0, -1.0, 1.0) * translation(0.0, -k, 0.0)
+}
+
+/// Create a 4 x 4 matrix that mirrors across the plane `z = k`.
+///
+/// # Arguments
+///
+/// * `k` - The z coordinate of the plane
+pub fn reflection_z(k: f64) -> Matrix {
+    translation(0.0, 0.0, k) * scaling(1.0, 1.0, -1.0) * translation(0.0, 0.0, -k)
+}
+
 #[cfg(test)]
 mod test {
     use crate::math::{
         point::Point,
         vector::Vector,
-        matrix::{
-            Matrix,
-            transformation::*,
-        },
+        matrix::{Matrix, transformation::*},
     };
 
     #[test]
@@ -285,6 +388,63 @@ mod test {
         assert_eq!(Point::new(-2.0,3.0,4.0), t * v);
     }
 
+    #[test]
+    fn reflecting_a_point_across_the_plane_x_equals_k() {
+        let t = reflection_x(2.0);
+        assert_eq!(Point::new(-1.0, 0.0, 0.0), t * Point::new(5.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn reflecting_a_point_across_the_planes_y_and_z_equals_k() {
+        assert_eq!(Point::new(1.0, -4.0, 3.0), reflection_y(-1.0) * Point::new(1.0, 2.0, 3.0));
+        assert_eq!(Point::new(1.0, 2.0, 7.0), reflection_z(5.0) * Point::new(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn a_reflection_does_not_move_points_on_the_plane() {
+        let p = Point::new(2.0, 3.0, -4.0);
+        assert_eq!(p, reflection_x(2.0) * p);
+    }
+
+    #[test]
+    fn a_reflection_mirrors_vectors_without_translating_them() {
+        let v = Vector::new(1.0, 2.0, 3.0);
+        assert_eq!(Vector::new(-1.0, 2.0, 3.0), reflection_x(10.0) * v);
+    }
+
+    #[test]
+    fn a_view_transformation_matrix_looking_in_positive_z_direction() {
+        let t = view_transform(&Point::new(0.0, 0.0, 0.0),
+                               &Point::new(0.0, 0.0, 1.0),
+                               &Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(scaling(-1.0, 1.0, -1.0), t);
+    }
+
+    #[test]
+    fn the_view_transformation_moves_the_world() {
+        let t = view_transform(&Point::new(0.0, 0.0, 8.0),
+                               &Point::new(0.0, 0.0, 0.0),
+                               &Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(translation(0.0, 0.0, -8.0), t);
+    }
+
+    #[test]
+    fn an_arbitrary_view_transformation() {
+        let t = view_transform(&Point::new(1.0, 3.0, 2.0),
+                               &Point::new(4.0, -2.0, 8.0),
+                               &Vector::new(1.0, 1.0, 0.0));
+        let expected = Matrix::from_vec(vec![vec![-0.50709, 0.50709, 0.67612, -2.36643],
+                                             vec![0.76772, 0.60609, 0.12122, -2.82843],
+                                             vec![-0.35857, 0.59761, -0.71714, 0.00000],
+                                             vec![0.00000, 0.00000, 0.00000, 1.00000]]).unwrap();
+
+        for r in 0..4 {
+            for c in 0..4 {
+                assert!((expected[r][c] - t[r][c]).abs() < 0.0001);
+            }
+        }
+    }
+
     #[test]
     fn degree_to_radians() {
         assert_eq!(std::f64::consts::PI, radians(180.0));
@@ -297,7 +457,7 @@ mod test {
         let half_quarter = rotation_rad_x(std::f64::consts::PI / 4.0);
         let full_quarter = rotation_rad_x(std::f64::consts::PI / 2.0);
 
-        assert_eq!(Point::new(0.0, (2.0 as f64).sqrt() / 2.0, (2.0 as f64).sqrt() / 2.0), half_quarter * p);
+        assert_eq!(Point::new(0.0, 2.0_f64.sqrt() / 2.0, 2.0_f64.sqrt() / 2.0), half_quarter * p);
         assert_eq!(Point::new(0.0, 0.0, 1.0), full_quarter * p);
     }
 
@@ -307,7 +467,7 @@ mod test {
         let mut half_quarter = rotation_rad_x(std::f64::consts::PI / 4.0);
         half_quarter = half_quarter.inverse().unwrap();
 
-        assert_eq!(Point::new(0.0, (2.0 as f64).sqrt() / 2.0, -(2.0 as f64).sqrt() / 2.0), half_quarter * p);
+        assert_eq!(Point::new(0.0, 2.0_f64.sqrt() / 2.0, -2.0_f64.sqrt() / 2.0), half_quarter * p);
     }
 
     #[test]
@@ -316,7 +476,7 @@ mod test {
         let half_quarter = rotation_rad_y(std::f64::consts::PI / 4.0);
         let full_quarter = rotation_rad_y(std::f64::consts::PI / 2.0);
 
-        assert_eq!(Point::new((2.0 as f64).sqrt() / 2.0, 0.0, (2.0 as f64).sqrt() / 2.0), half_quarter * p);
+        assert_eq!(Point::new(2.0_f64.sqrt() / 2.0, 0.0, 2.0_f64.sqrt() / 2.0), half_quarter * p);
         assert_eq!(Point::new(1.0, 0.0, 0.0), full_quarter * p);
     }
 
@@ -326,7 +486,7 @@ mod test {
         let half_quarter = rotation_rad_z(std::f64::consts::PI / 4.0);
         let full_quarter = rotation_rad_z(std::f64::consts::PI / 2.0);
 
-        assert_eq!(Point::new(-(2.0 as f64).sqrt() / 2.0, (2.0 as f64).sqrt() / 2.0, 0.0), half_quarter * p);
+        assert_eq!(Point::new(-2.0_f64.sqrt() / 2.0, 2.0_f64.sqrt() / 2.0, 0.0), half_quarter * p);
         assert_eq!(Point::new(-1.0, 0.0, 0.0), full_quarter * p);
     }
 
@@ -383,4 +543,24 @@ mod test {
 
         assert_eq!(pt, t * p);
     }
+
+    #[test]
+    fn a_perspective_projection_maps_the_frustum_to_ndc() {
+        let p = perspective(std::f64::consts::PI / 2.0, 2.0, 1.0, 100.0);
+
+        let near = p.mul_point(&Point::new(0.0, 0.0, -1.0));
+        let far = p.mul_point(&Point::new(0.0, 0.0, -100.0));
+        assert!((near.z() + 1.0).abs() < 0.00001);
+        assert!((far.z() - 1.0).abs() < 0.00001);
+
+        // The top right corner of the near plane (half height = tan(45°) = 1).
+        let corner = p.mul_point(&Point::new(2.0, 1.0, -1.0));
+        assert!((corner.x() - 1.0).abs() < 0.00001);
+        assert!((corner.y() - 1.0).abs() < 0.00001);
+
+        // Points further away are divided by their distance.
+        let q = p.mul_point(&Point::new(2.0, 1.0, -10.0));
+        assert!((q.x() - 0.1).abs() < 0.00001);
+        assert!((q.y() - 0.1).abs() < 0.00001);
+    }
 }
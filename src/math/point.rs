@@ -1,4 +1,5 @@
 use super::vector::*;
+use super::matrix::Matrix;
 use std::{ops, cmp};
 
 /// A Point represents a position in 3-dimensional space.
@@ -28,6 +29,19 @@ impl Point {
     pub fn new(x: f64, y: f64, z: f64) -> Self {
         Self { x, y, z }
     }
+
+    /// Create a Point at the origin, i.e. (0, 0, 0).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::point::Point;
+    ///
+    /// assert_eq!(Point::new(0.0, 0.0, 0.0), Point::origin());
+    /// ```
+    pub fn origin() -> Self {
+        Self::new(0.0, 0.0, 0.0)
+    }
     
     /// Return the x position of a Point.
     pub fn x(&self) -> f64 {
@@ -43,6 +57,126 @@ impl Point {
     pub fn z(&self) -> f64 {
         self.z
     }
+
+    /// Transform the point by the given matrix.
+    ///
+    /// Same as `m.mul_point(&p)` but reads from left to right,
+    /// i.e. `p.transform(&m)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The transformation matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::{point::Point, matrix::transformation::translation};
+    ///
+    /// let p = Point::new(1.0, 1.0, 1.0);
+    ///
+    /// assert_eq!(Point::new(2.0, 3.0, 4.0), p.transform(&translation(1.0, 2.0, 3.0)));
+    /// ```
+    pub fn transform(&self, m: &Matrix) -> Point {
+        m.mul_point(self)
+    }
+
+    /// Calculate the distance between two points.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other point
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::point::Point;
+    ///
+    /// let p = Point::new(0.0, 0.0, 0.0);
+    /// let q = Point::new(0.0, 3.0, 4.0);
+    ///
+    /// assert_eq!(5.0, p.distance(&q));
+    /// ```
+    pub fn distance(&self, other: &Point) -> f64 {
+        self.distance_squared(other).sqrt()
+    }
+
+    /// Calculate the squared distance between two points.
+    ///
+    /// This is cheaper than [`Point::distance`] because it
+    /// avoids the square root and can be used to compare distances.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other point
+    pub fn distance_squared(&self, other: &Point) -> f64 {
+        (self.x - other.x).powi(2) + (self.y - other.y).powi(2) + (self.z - other.z).powi(2)
+    }
+
+    /// Check if two points are equal within the given tolerance.
+    ///
+    /// The points are equal if none of their coordinates differ
+    /// by more than `epsilon`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other point
+    /// * `epsilon` - The largest allowed difference per coordinate
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::{EPSILON, point::Point};
+    ///
+    /// let p = Point::new(1.0, 2.0, 3.0);
+    ///
+    /// assert!(p.approx_eq(&Point::new(1.000001, 2.0, 3.0), EPSILON));
+    /// assert!(!p.approx_eq(&Point::new(1.1, 2.0, 3.0), EPSILON));
+    /// ```
+    pub fn approx_eq(&self, other: &Point, epsilon: f64) -> bool {
+        (self.x - other.x).abs() <= epsilon &&
+        (self.y - other.y).abs() <= epsilon &&
+        (self.z - other.z).abs() <= epsilon
+    }
+
+    /// Get the component-wise minimum of two points.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other point
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::point::Point;
+    ///
+    /// let p = Point::new(1.0, 5.0, 3.0);
+    /// let q = Point::new(4.0, 2.0, 6.0);
+    ///
+    /// assert_eq!(Point::new(1.0, 2.0, 3.0), p.min(&q));
+    /// ```
+    pub fn min(&self, other: &Point) -> Point {
+        Point::new(self.x.min(other.x), self.y.min(other.y), self.z.min(other.z))
+    }
+
+    /// Get the component-wise maximum of two points.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other point
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::point::Point;
+    ///
+    /// let p = Point::new(1.0, 5.0, 3.0);
+    /// let q = Point::new(4.0, 2.0, 6.0);
+    ///
+    /// assert_eq!(Point::new(4.0, 5.0, 6.0), p.max(&q));
+    /// ```
+    pub fn max(&self, other: &Point) -> Point {
+        Point::new(self.x.max(other.x), self.y.max(other.y), self.z.max(other.z))
+    }
 }
 
 impl ops::Add<Vector> for Point {
@@ -125,6 +259,32 @@ impl ops::Sub<Vector> for Point {
     }
 }
 
+impl ops::Index<usize> for Point {
+    type Output = f64;
+
+    /// Get a coordinate by its axis (0 = x, 1 = y, 2 = z).
+    ///
+    /// Panics if `axis` is greater than 2.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::point::Point;
+    ///
+    /// let p = Point::new(1.0, 2.0, 3.0);
+    ///
+    /// assert_eq!(2.0, p[1]);
+    /// ```
+    fn index(&self, axis: usize) -> &f64 {
+        match axis {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("axis {} is out of bounds for a point (0..3)", axis),
+        }
+    }
+}
+
 impl cmp::PartialEq for Point {
     fn eq(&self, other: &Self) -> bool {
         (self.x - other.x).abs() <= f64::EPSILON &&
@@ -1,5 +1,5 @@
-use super::point::*;
 use std::ops;
+use super::matrix::{Matrix, transformation::{rotation_rad_x, rotation_rad_y, rotation_rad_z}};
 
 /** Vector representing magnitude and direction in 3-dimensional space.
  */
@@ -15,6 +15,14 @@ impl Vector {
         Self { x, y, z }
     }
 
+    /** Create the zero Vector, i.e. (0, 0, 0).
+     *
+     * The zero vector has no direction and a magnitude of 0.
+     */
+    pub fn zero() -> Self {
+        Self::new(0.0, 0.0, 0.0)
+    }
+
     pub fn x(&self) -> f64 {
         self.x 
     }
@@ -44,9 +52,9 @@ impl Vector {
         let m = self.mag();
         
         if m != 0.0 {
-            self.x = self.x / m;
-            self.y = self.y / m;
-            self.z = self.z / m;
+            self.x /= m;
+            self.y /= m;
+            self.z /= m;
         }
         self
     }
@@ -61,9 +69,9 @@ impl Vector {
         let mut v = Self {  x: self.x, y: self.y, z: self.z };
         
         if m != 0.0 {
-            v.x = v.x / m;
-            v.y = v.y / m;
-            v.z = v.z / m;
+            v.x /= m;
+            v.y /= m;
+            v.z /= m;
         }
 
         v
@@ -80,6 +88,32 @@ impl Vector {
         self.x * vec.x + self.y * vec.y + self.z * vec.z 
     }
 
+    /// Check if two vectors are equal within the given tolerance.
+    ///
+    /// The vectors are equal if none of their components differ
+    /// by more than `epsilon`.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The other vector
+    /// * `epsilon` - The largest allowed difference per component
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::{EPSILON, vector::Vector};
+    ///
+    /// let v = Vector::new(1.0, 2.0, 3.0);
+    ///
+    /// assert!(v.approx_eq(&Vector::new(1.0, 2.000001, 3.0), EPSILON));
+    /// assert!(!v.approx_eq(&Vector::new(1.0, 2.1, 3.0), EPSILON));
+    /// ```
+    pub fn approx_eq(&self, other: &Self, epsilon: f64) -> bool {
+        (self.x - other.x).abs() <= epsilon &&
+        (self.y - other.y).abs() <= epsilon &&
+        (self.z - other.z).abs() <= epsilon
+    }
+
     /** The cross product of two vectors.
      *
      * Calculates a new vector that is prependicular to both of the original vectors.
@@ -90,6 +124,72 @@ impl Vector {
                     self.x * vec.y - self.y * vec.x)
     }
     
+    /// Transform the vector by the given matrix.
+    ///
+    /// Same as `m.mul_vec(&v)` but reads from left to right,
+    /// i.e. `v.transform(&m)`. Vectors have no position, so
+    /// they aren't affected by translations.
+    ///
+    /// # Arguments
+    ///
+    /// * `m` - The transformation matrix
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::{vector::Vector, matrix::transformation::scaling};
+    ///
+    /// let v = Vector::new(1.0, 1.0, 1.0);
+    ///
+    /// assert_eq!(Vector::new(2.0, 3.0, 4.0), v.transform(&scaling(2.0, 3.0, 4.0)));
+    /// ```
+    pub fn transform(&self, m: &Matrix) -> Vector {
+        m.mul_vec(self)
+    }
+
+    /// Rotate the vector around the x axis.
+    ///
+    /// Same as `v.transform(&rotation_rad_x(rad))`.
+    ///
+    /// # Arguments
+    ///
+    /// * `rad` - The angle in radians
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::{EPSILON, vector::Vector};
+    ///
+    /// let v = Vector::new(0.0, 1.0, 0.0).rotate_x(std::f64::consts::PI / 2.0);
+    ///
+    /// assert!(v.approx_eq(&Vector::new(0.0, 0.0, 1.0), EPSILON));
+    /// ```
+    pub fn rotate_x(&self, rad: f64) -> Vector {
+        self.transform(&rotation_rad_x(rad))
+    }
+
+    /// Rotate the vector around the y axis.
+    ///
+    /// Same as `v.transform(&rotation_rad_y(rad))`.
+    ///
+    /// # Arguments
+    ///
+    /// * `rad` - The angle in radians
+    pub fn rotate_y(&self, rad: f64) -> Vector {
+        self.transform(&rotation_rad_y(rad))
+    }
+
+    /// Rotate the vector around the z axis.
+    ///
+    /// Same as `v.transform(&rotation_rad_z(rad))`.
+    ///
+    /// # Arguments
+    ///
+    /// * `rad` - The angle in radians
+    pub fn rotate_z(&self, rad: f64) -> Vector {
+        self.transform(&rotation_rad_z(rad))
+    }
+
     /// Reflect the vector around a given normal.
     ///
     /// # Arguments
@@ -120,6 +220,42 @@ impl Vector {
     pub fn reflect(&self, normal: &Self) -> Self {
         *self - (*normal * 2.0 * self.dot(normal))
     }
+    
+    /// Refract a (incoming) vector at a surface (Snell's law).
+    ///
+    /// Returns None in case of total internal reflection, i.e.
+    /// if no light passes through the surface.
+    ///
+    /// # Arguments
+    ///
+    /// * `normal` - The surface normal (pointing towards the incoming vector)
+    /// * `n_ratio` - The ratio `n1 / n2` of the refractive indices
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::math::vector::Vector;
+    ///
+    /// let v = Vector::new(0.0, -1.0, 0.0);
+    /// let n = Vector::new(0.0, 1.0, 0.0);
+    ///
+    /// // Light entering a surface head-on isn't bent.
+    /// assert_eq!(Some(Vector::new(0.0, -1.0, 0.0)), v.refract(&n, 1.0 / 1.5));
+    /// ```
+    pub fn refract(&self, normal: &Self, n_ratio: f64) -> Option<Self> {
+        let v = self.norm_cpy();
+        let cos_i = -v.dot(normal);
+        let sin2_t = n_ratio * n_ratio * (1.0 - cos_i * cos_i);
+
+        // Total internal reflection
+        if sin2_t > 1.0 {
+            return None;
+        }
+
+        let cos_t = (1.0 - sin2_t).sqrt();
+
+        Some((v * n_ratio + *normal * (n_ratio * cos_i - cos_t)).norm_cpy())
+    }
 }
 
 /** The sum of two vectors.
@@ -174,3 +310,20 @@ impl ops::Div<f64> for Vector {
         Vector::new(self.x / rhs, self.y / rhs, self.z / rhs)
     }
 }
+
+/** Get a component by its axis (0 = x, 1 = y, 2 = z).
+ *
+ * Panics if `axis` is greater than 2.
+ */
+impl ops::Index<usize> for Vector {
+    type Output = f64;
+
+    fn index(&self, axis: usize) -> &f64 {
+        match axis {
+            0 => &self.x,
+            1 => &self.y,
+            2 => &self.z,
+            _ => panic!("axis {} is out of bounds for a vector (0..3)", axis),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+pub mod uv;
+pub mod perturbed;
+pub mod combinators;
+
+use crate::{
+    math::{point::Point, matrix::Matrix},
+    canvas::color::Color,
+};
+
+/// A pattern assigns a color to every point in space.
+///
+/// Patterns can be used to texture the surface of an object,
+/// e.g. with stripes or checkers.
+pub trait Pattern {
+    /// Get the color of the pattern at the given point.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point (in pattern space)
+    fn pattern_at(&self, point: &Point) -> Color;
+
+    /// Get the transformation of the pattern.
+    ///
+    /// The transformation maps pattern space to the space the
+    /// pattern is applied in, e.g. a scaling makes stripes wider.
+    /// Defaults to the identity matrix.
+    fn transform(&self) -> Matrix {
+        Matrix::identity()
+    }
+}
+
+impl<P: Pattern + ?Sized> Pattern for Box<P> {
+    fn pattern_at(&self, point: &Point) -> Color {
+        (**self).pattern_at(point)
+    }
+
+    fn transform(&self) -> Matrix {
+        (**self).transform()
+    }
+}
+
+/// A pattern with a single color.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct SolidPattern {
+    color: Color,
+}
+
+impl SolidPattern {
+    /// Create a new solid pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - The color of the pattern
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     canvas::color::Color,
+    ///     math::point::Point,
+    ///     patterns::{Pattern, SolidPattern},
+    /// };
+    ///
+    /// let p = SolidPattern::new(Color::new(1.0, 0.0, 0.0));
+    ///
+    /// assert_eq!(Color::new(1.0, 0.0, 0.0), p.pattern_at(&Point::new(3.0, -2.0, 1.5)));
+    /// ```
+    pub fn new(color: Color) -> Self {
+        Self { color }
+    }
+}
+
+impl Pattern for SolidPattern {
+    fn pattern_at(&self, _point: &Point) -> Color {
+        self.color
+    }
+}
+
+/// A child pattern of a combinator together with the cached inverse
+/// of the child's transformation.
+///
+/// Combinators sample their children through this wrapper, so the point
+/// is converted from the combinator's pattern space into the child's
+/// pattern space first.
+pub(crate) struct ChildPattern<P: Pattern> {
+    pattern: P,
+    inverse: Matrix,
+}
+
+impl<P: Pattern> ChildPattern<P> {
+    /// Wrap a child pattern.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transformation of the pattern isn't invertible.
+    pub(crate) fn new(pattern: P) -> Self {
+        let inverse = pattern.transform().inverse()
+            .expect("the transformation of a pattern must be invertible");
+
+        Self { pattern, inverse }
+    }
+
+    /// Get the wrapped pattern.
+    pub(crate) fn pattern(&self) -> &P {
+        &self.pattern
+    }
+
+    /// Sample the child at a point given in the parent's pattern space.
+    pub(crate) fn pattern_at(&self, point: &Point) -> Color {
+        self.pattern.pattern_at(&self.inverse.mul_point(point))
+    }
+}
@@ -0,0 +1,179 @@
+use crate::{
+    math::point::Point,
+    canvas::color::Color,
+    patterns::{Pattern, ChildPattern},
+};
+
+/// A pattern that blends two patterns by averaging their colors.
+///
+/// Both patterns are sampled in their own pattern space, i.e. their
+/// transformations are applied relative to the blended pattern.
+pub struct BlendedPattern {
+    a: ChildPattern<Box<dyn Pattern>>,
+    b: ChildPattern<Box<dyn Pattern>>,
+}
+
+impl BlendedPattern {
+    /// Create a new blended pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The first pattern
+    /// * `b` - The second pattern
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transformation of a pattern isn't invertible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     canvas::color::Color,
+    ///     math::point::Point,
+    ///     patterns::{Pattern, SolidPattern, combinators::BlendedPattern},
+    /// };
+    ///
+    /// let p = BlendedPattern::new(Box::new(SolidPattern::new(Color::new(1.0, 0.0, 0.0))),
+    ///                             Box::new(SolidPattern::new(Color::new(0.0, 0.0, 1.0))));
+    ///
+    /// assert_eq!(Color::new(0.5, 0.0, 0.5), p.pattern_at(&Point::new(0.0, 0.0, 0.0)));
+    /// ```
+    pub fn new(a: Box<dyn Pattern>, b: Box<dyn Pattern>) -> Self {
+        Self { a: ChildPattern::new(a), b: ChildPattern::new(b) }
+    }
+}
+
+impl Pattern for BlendedPattern {
+    fn pattern_at(&self, point: &Point) -> Color {
+        (self.a.pattern_at(point) + self.b.pattern_at(point)) * 0.5_f32
+    }
+}
+
+/// A pattern that uses a `selector` pattern to choose between two other patterns.
+///
+/// If the average of the selectors color channels at a given point is
+/// less than 0.5 (i.e. dark) pattern `a` is sampled, otherwise pattern `b`.
+/// All three patterns are sampled in their own pattern space.
+pub struct NestedPattern {
+    selector: ChildPattern<Box<dyn Pattern>>,
+    a: ChildPattern<Box<dyn Pattern>>,
+    b: ChildPattern<Box<dyn Pattern>>,
+}
+
+impl NestedPattern {
+    /// Create a new nested pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `selector` - The pattern that decides which pattern is sampled
+    /// * `a` - The pattern used where the selector is dark
+    /// * `b` - The pattern used where the selector is bright
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transformation of a pattern isn't invertible.
+    pub fn new(selector: Box<dyn Pattern>, a: Box<dyn Pattern>, b: Box<dyn Pattern>) -> Self {
+        Self { selector: ChildPattern::new(selector), a: ChildPattern::new(a), b: ChildPattern::new(b) }
+    }
+}
+
+impl Pattern for NestedPattern {
+    fn pattern_at(&self, point: &Point) -> Color {
+        let s = self.selector.pattern_at(point);
+
+        if (s.r() + s.g() + s.b()) / 3.0 < 0.5 {
+            self.a.pattern_at(point)
+        } else {
+            self.b.pattern_at(point)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        math::point::Point,
+        canvas::color::Color,
+        patterns::{
+            Pattern,
+            SolidPattern,
+            combinators::*,
+            uv::{uv_checkers, planar_map, TextureMap},
+        },
+        math::matrix::{Matrix, transformation::scaling},
+    };
+
+    // Stripes along the x axis that alternate every unit.
+    struct StripePattern {
+        transform: Matrix,
+    }
+
+    impl Pattern for StripePattern {
+        fn pattern_at(&self, point: &Point) -> Color {
+            if point.x().floor() as i64 % 2 == 0 { Color::new(1.0, 1.0, 1.0) } else { Color::new(0.0, 0.0, 0.0) }
+        }
+
+        fn transform(&self) -> Matrix {
+            self.transform.clone()
+        }
+    }
+
+    #[test]
+    fn blending_two_solid_patterns() {
+        let p = BlendedPattern::new(Box::new(SolidPattern::new(Color::new(1.0, 0.2, 0.0))),
+                                    Box::new(SolidPattern::new(Color::new(0.0, 0.4, 1.0))));
+
+        assert_eq!(Color::new(0.5, 0.3, 0.5), p.pattern_at(&Point::new(0.0, 0.0, 0.0)));
+        assert_eq!(Color::new(0.5, 0.3, 0.5), p.pattern_at(&Point::new(2.5, -1.0, 7.0)));
+    }
+
+    #[test]
+    fn nesting_switches_at_the_selectors_boundaries() {
+        let black = Color::new(0.0, 0.0, 0.0);
+        let white = Color::new(1.0, 1.0, 1.0);
+        let red = Color::new(1.0, 0.0, 0.0);
+        let blue = Color::new(0.0, 0.0, 1.0);
+
+        let selector = TextureMap::new(uv_checkers(2, 2, black, white), planar_map);
+        let p = NestedPattern::new(Box::new(selector),
+                                   Box::new(SolidPattern::new(red)),
+                                   Box::new(SolidPattern::new(blue)));
+
+        assert_eq!(red, p.pattern_at(&Point::new(0.25, 0.0, 0.25)));
+        assert_eq!(red, p.pattern_at(&Point::new(0.49, 0.0, 0.25)));
+        assert_eq!(blue, p.pattern_at(&Point::new(0.5, 0.0, 0.25)));
+        assert_eq!(blue, p.pattern_at(&Point::new(0.25, 0.0, 0.75)));
+        assert_eq!(red, p.pattern_at(&Point::new(0.75, 0.0, 0.75)));
+    }
+
+    #[test]
+    fn blending_samples_a_child_in_its_own_pattern_space() {
+        let stripes = StripePattern { transform: scaling(2.0, 2.0, 2.0) };
+        let p = BlendedPattern::new(Box::new(stripes),
+                                    Box::new(SolidPattern::new(Color::new(0.0, 0.0, 0.0))));
+
+        // The stripes are twice as wide, i.e. x = 1.5 is still in the first stripe.
+        assert_eq!(Color::new(0.5, 0.5, 0.5), p.pattern_at(&Point::new(1.5, 0.0, 0.0)));
+        assert_eq!(Color::new(0.0, 0.0, 0.0), p.pattern_at(&Point::new(2.5, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn nesting_samples_the_children_in_their_own_pattern_space() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let blue = Color::new(0.0, 0.0, 1.0);
+        let p = NestedPattern::new(Box::new(StripePattern { transform: scaling(2.0, 2.0, 2.0) }),
+                                   Box::new(SolidPattern::new(red)),
+                                   Box::new(SolidPattern::new(blue)));
+
+        assert_eq!(blue, p.pattern_at(&Point::new(1.5, 0.0, 0.0)));
+        assert_eq!(red, p.pattern_at(&Point::new(2.5, 0.0, 0.0)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn a_child_with_a_singular_transformation_is_rejected() {
+        BlendedPattern::new(Box::new(StripePattern { transform: scaling(0.0, 1.0, 1.0) }),
+                            Box::new(SolidPattern::new(Color::new(0.0, 0.0, 0.0))));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::{
+    math::{point::Point, vector::Vector},
+    canvas::color::Color,
+    patterns::{Pattern, ChildPattern},
+};
+
+/// A pattern that jitters the point before sampling an inner pattern.
+///
+/// The offset is computed using a simple (deterministic) value noise,
+/// i.e. the same `seed` always results in the same perturbation. The
+/// offset along each axis is in the range [-`scale`, `scale`].
+///
+/// This can be used to make patterns look more organic, e.g. to get
+/// wavy instead of straight stripes. The point is jittered before
+/// it's converted into the pattern space of the inner pattern.
+pub struct PerturbedPattern<P: Pattern> {
+    pattern: ChildPattern<P>,
+    scale: f64,
+    seed: u64,
+}
+
+impl<P: Pattern> PerturbedPattern<P> {
+    /// Create a new perturbed pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The inner pattern to sample
+    /// * `scale` - The maximum offset along each axis
+    /// * `seed` - The seed of the noise function
+    ///
+    /// # Panics
+    ///
+    /// Panics if the transformation of the inner pattern isn't invertible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     canvas::color::Color,
+    ///     math::point::Point,
+    ///     patterns::{
+    ///         Pattern,
+    ///         perturbed::PerturbedPattern,
+    ///         uv::{uv_checkers, planar_map, TextureMap},
+    ///     },
+    /// };
+    ///
+    /// let checkers = TextureMap::new(uv_checkers(2, 2, Color::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0)), planar_map);
+    /// let p = PerturbedPattern::new(checkers, 0.0, 42);
+    ///
+    /// assert_eq!(Color::new(0.0, 0.0, 0.0), p.pattern_at(&Point::new(0.25, 0.0, 0.25)));
+    /// ```
+    pub fn new(pattern: P, scale: f64, seed: u64) -> Self {
+        Self { pattern: ChildPattern::new(pattern), scale, seed }
+    }
+
+    /// Get the inner pattern.
+    pub fn pattern(&self) -> &P {
+        self.pattern.pattern()
+    }
+
+    /// Get the maximum offset along each axis.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Get the seed of the noise function.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl<P: Pattern> Pattern for PerturbedPattern<P> {
+    fn pattern_at(&self, point: &Point) -> Color {
+        // Use a different seed for each axis, otherwise the point
+        // would always be moved along the diagonal.
+        let offset = Vector::new(value_noise(point, self.seed),
+                                 value_noise(point, self.seed.wrapping_add(1)),
+                                 value_noise(point, self.seed.wrapping_add(2)));
+
+        self.pattern.pattern_at(&(*point + offset * self.scale))
+    }
+}
+
+/* Hash the integer lattice coordinates into a value between -1 and 1.
+ */
+fn lattice(x: i64, y: i64, z: i64, seed: u64) -> f64 {
+    let mut h = seed
+        ^ (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F)
+        ^ (z as u64).wrapping_mul(0x1656_67B1_9E37_79F9);
+
+    // finalizer of splitmix64
+    h = (h ^ (h >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    h ^= h >> 31;
+
+    (h >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+}
+
+/* Smoothly interpolated noise between the lattice values surrounding the point.
+ *
+ * The result is in the range [-1, 1].
+ */
+fn value_noise(point: &Point, seed: u64) -> f64 {
+    let (x0, y0, z0) = (point.x().floor(), point.y().floor(), point.z().floor());
+    let (xi, yi, zi) = (x0 as i64, y0 as i64, z0 as i64);
+
+    let smooth = |t: f64| t * t * (3.0 - 2.0 * t);
+    let (tx, ty, tz) = (smooth(point.x() - x0), smooth(point.y() - y0), smooth(point.z() - z0));
+    let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+
+    let mut c = [0.0; 4];
+    for (i, v) in c.iter_mut().enumerate() {
+        let dy = (i & 1) as i64;
+        let dz = (i >> 1) as i64;
+        *v = lerp(lattice(xi, yi + dy, zi + dz, seed), lattice(xi + 1, yi + dy, zi + dz, seed), tx);
+    }
+
+    lerp(lerp(c[0], c[1], ty), lerp(c[2], c[3], ty), tz)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        math::point::Point,
+        canvas::color::Color,
+        patterns::{
+            Pattern,
+            perturbed::*,
+            uv::{uv_checkers, planar_map, TextureMap, UvCheckers},
+        },
+    };
+
+    fn checkers() -> TextureMap<UvCheckers> {
+        TextureMap::new(uv_checkers(2, 2, Color::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0)), planar_map)
+    }
+
+    #[test]
+    fn the_noise_is_in_range() {
+        for i in 0..100 {
+            let n = value_noise(&Point::new(i as f64 * 0.37, i as f64 * -1.3, i as f64 * 0.71), 7);
+            assert!((-1.0..=1.0).contains(&n));
+        }
+    }
+
+    #[test]
+    fn a_perturbed_pattern_without_magnitude_equals_the_inner_pattern() {
+        let p = PerturbedPattern::new(checkers(), 0.0, 42);
+        let inner = checkers();
+
+        for i in 0..100 {
+            let point = Point::new(i as f64 * 0.05, 0.0, i as f64 * 0.03);
+            assert_eq!(inner.pattern_at(&point), p.pattern_at(&point));
+        }
+    }
+
+    #[test]
+    fn a_perturbed_pattern_differs_at_a_boundary() {
+        let p = PerturbedPattern::new(checkers(), 0.2, 42);
+        let inner = checkers();
+
+        // sample points close to the boundary at x = 0.5
+        let differs = (0..100)
+            .map(|i| Point::new(0.49, 0.0, i as f64 * 0.01))
+            .any(|point| inner.pattern_at(&point) != p.pattern_at(&point));
+
+        assert!(differs);
+    }
+
+    #[test]
+    fn a_perturbed_pattern_is_deterministic() {
+        let p1 = PerturbedPattern::new(checkers(), 0.2, 7);
+        let p2 = PerturbedPattern::new(checkers(), 0.2, 7);
+
+        for i in 0..100 {
+            let point = Point::new(0.49, i as f64 * 0.1, i as f64 * 0.01);
+            assert_eq!(p1.pattern_at(&point), p2.pattern_at(&point));
+        }
+    }
+}
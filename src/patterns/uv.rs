@@ -0,0 +1,430 @@
+use crate::{
+    math::point::Point,
+    canvas::{Canvas, color::Color},
+    patterns::Pattern,
+};
+
+/// A two dimensional pattern that is sampled using (u, v) coordinates.
+pub trait UvPattern {
+    /// Get the color of the pattern at the given (u, v) coordinates.
+    ///
+    /// # Arguments
+    ///
+    /// * `u` - The horizontal coordinate in the range [0, 1]
+    /// * `v` - The vertical coordinate in the range [0, 1]
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color;
+}
+
+/// A two dimensional checker pattern.
+///
+/// The [0, 1] x [0, 1] uv square is divided into `width` x `height`
+/// squares that alternate between the colors `a` and `b`.
+#[derive(Debug, PartialEq)]
+pub struct UvCheckers {
+    width: usize,
+    height: usize,
+    a: Color,
+    b: Color,
+}
+
+/// Create a new two dimensional checker pattern.
+///
+/// # Arguments
+///
+/// * `width` - The number of squares in `u` direction
+/// * `height` - The number of squares in `v` direction
+/// * `a` - The color of the first square (at (0, 0))
+/// * `b` - The alternating color
+///
+/// # Examples
+///
+/// ```
+/// use sugar_ray::{
+///     canvas::color::Color,
+///     patterns::uv::{uv_checkers, UvPattern},
+/// };
+///
+/// let black = Color::new(0.0, 0.0, 0.0);
+/// let white = Color::new(1.0, 1.0, 1.0);
+/// let checkers = uv_checkers(2, 2, black, white);
+///
+/// assert_eq!(white, checkers.uv_pattern_at(0.5, 0.0));
+/// ```
+pub fn uv_checkers(width: usize, height: usize, a: Color, b: Color) -> UvCheckers {
+    UvCheckers { width, height, a, b }
+}
+
+impl UvPattern for UvCheckers {
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        let u2 = (u * self.width as f64).floor() as i64;
+        let v2 = (v * self.height as f64).floor() as i64;
+
+        if (u2 + v2).rem_euclid(2) == 0 {
+            self.a
+        } else {
+            self.b
+        }
+    }
+}
+
+/// A two dimensional pattern that samples an image.
+///
+/// The (u, v) coordinate (0, 0) is the bottom left corner of
+/// the image and (1, 1) is the top right corner.
+#[derive(Debug, PartialEq)]
+pub struct ImagePattern {
+    canvas: Canvas,
+}
+
+impl ImagePattern {
+    /// Create a new image pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `canvas` - The image to sample
+    ///
+    /// # Panics
+    ///
+    /// Panics if the canvas is empty, i.e. has no pixels to sample.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     canvas::{Canvas, color::Color},
+    ///     patterns::uv::{ImagePattern, UvPattern},
+    /// };
+    ///
+    /// let canvas = Canvas::from_ppm("P3\n1 2\n255\n255 0 0\n0 0 255\n").unwrap();
+    /// let pattern = ImagePattern::new(canvas);
+    ///
+    /// assert_eq!(Color::new(0.0, 0.0, 1.0), pattern.uv_pattern_at(0.0, 0.0));
+    /// assert_eq!(Color::new(1.0, 0.0, 0.0), pattern.uv_pattern_at(0.0, 1.0));
+    /// ```
+    pub fn new(canvas: Canvas) -> Self {
+        assert!(canvas.width() > 0 && canvas.height() > 0,
+                "an image pattern requires a non-empty canvas, got {}x{}", canvas.width(), canvas.height());
+
+        Self { canvas }
+    }
+
+    /// Get the image.
+    pub fn canvas(&self) -> &Canvas {
+        &self.canvas
+    }
+}
+
+impl UvPattern for ImagePattern {
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        // flip v over so it matches the image layout, with y at the top
+        let v = 1.0 - v;
+
+        let x = u * (self.canvas.width() - 1) as f64;
+        let y = v * (self.canvas.height() - 1) as f64;
+
+        // u and v outside of [0, 1] are clamped to the border pixels
+        // (the cast already saturates negative values to 0).
+        let x = (x.round() as usize).min(self.canvas.width() - 1);
+        let y = (y.round() as usize).min(self.canvas.height() - 1);
+
+        self.canvas.pixel_at(x, y)
+    }
+}
+
+/// Combines a [`UvPattern`] with a uv mapping function.
+///
+/// The mapping (e.g. [`spherical_map`]) converts a three dimensional point
+/// into (u, v) coordinates which are then used to sample the uv pattern.
+pub struct TextureMap<P: UvPattern> {
+    pattern: P,
+    mapping: fn(&Point) -> (f64, f64),
+}
+
+impl<P: UvPattern> TextureMap<P> {
+    /// Create a new texture map.
+    ///
+    /// # Arguments
+    ///
+    /// * `pattern` - The uv pattern to sample
+    /// * `mapping` - The function that maps a point to (u, v) coordinates
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     canvas::color::Color,
+    ///     math::point::Point,
+    ///     patterns::{Pattern, uv::{uv_checkers, spherical_map, TextureMap}},
+    /// };
+    ///
+    /// let black = Color::new(0.0, 0.0, 0.0);
+    /// let white = Color::new(1.0, 1.0, 1.0);
+    /// let texture = TextureMap::new(uv_checkers(16, 8, black, white), spherical_map);
+    ///
+    /// assert_eq!(black, texture.pattern_at(&Point::new(0.0, 0.0, -1.0)));
+    /// ```
+    pub fn new(pattern: P, mapping: fn(&Point) -> (f64, f64)) -> Self {
+        Self { pattern, mapping }
+    }
+
+    /// Get the uv pattern.
+    pub fn uv_pattern(&self) -> &P {
+        &self.pattern
+    }
+}
+
+impl<P: UvPattern> Pattern for TextureMap<P> {
+    fn pattern_at(&self, point: &Point) -> Color {
+        let (u, v) = (self.mapping)(point);
+        self.pattern.uv_pattern_at(u, v)
+    }
+}
+
+/// Map a point on the surface of a unit sphere to (u, v) coordinates.
+///
+/// The longitude (around the y axis) is mapped to `u` and the latitude
+/// to `v`, where both values are in the range [0, 1]. The south pole has
+/// a `v` of 0 and the north pole a `v` of 1.
+///
+/// # Arguments
+///
+/// * `point` - A point on the sphere (in object space)
+///
+/// # Examples
+///
+/// ```
+/// use sugar_ray::{
+///     math::point::Point,
+///     patterns::uv::spherical_map,
+/// };
+///
+/// assert_eq!((0.25, 0.5), spherical_map(&Point::new(1.0, 0.0, 0.0)));
+/// ```
+pub fn spherical_map(point: &Point) -> (f64, f64) {
+    // The azimuthal angle theta goes from -PI to PI and
+    // increases clockwise when viewed from above.
+    let theta = point.x().atan2(point.z());
+
+    // The polar angle phi goes from 0 (north pole) to PI (south pole).
+    let radius = (*point - Point::origin()).mag();
+    let phi = (point.y() / radius).acos();
+
+    // raw_u goes from -0.5 to 0.5
+    let raw_u = theta / (2.0 * std::f64::consts::PI);
+
+    // Fix the direction of u, so that it increases counter-clockwise
+    // when viewed from above.
+    let u = 1.0 - (raw_u + 0.5);
+
+    // Subtract from 1, so that v is 1 at the north pole.
+    let v = 1.0 - phi / std::f64::consts::PI;
+
+    (u, v)
+}
+
+/// Map a point on a plane (in object space) to (u, v) coordinates.
+///
+/// The plane is assumed to be the xz plane. The `u` coordinate
+/// repeats along the x axis and `v` along the z axis every unit,
+/// i.e. both values are in the range [0, 1).
+///
+/// # Arguments
+///
+/// * `point` - A point on the plane (in object space)
+///
+/// # Examples
+///
+/// ```
+/// use sugar_ray::{
+///     math::point::Point,
+///     patterns::uv::planar_map,
+/// };
+///
+/// assert_eq!((0.25, 0.75), planar_map(&Point::new(1.25, 0.0, -0.25)));
+/// ```
+pub fn planar_map(point: &Point) -> (f64, f64) {
+    (point.x().rem_euclid(1.0), point.z().rem_euclid(1.0))
+}
+
+/// Map a point on a cylinder (in object space) to (u, v) coordinates.
+///
+/// The cylinder is assumed to be centered around the y axis with a
+/// radius of 1. The angle around the y axis is mapped to `u` and the
+/// `v` coordinate repeats along the y axis every unit.
+///
+/// # Arguments
+///
+/// * `point` - A point on the cylinder (in object space)
+///
+/// # Examples
+///
+/// ```
+/// use sugar_ray::{
+///     math::point::Point,
+///     patterns::uv::cylindrical_map,
+/// };
+///
+/// assert_eq!((0.25, 0.5), cylindrical_map(&Point::new(1.0, 0.5, 0.0)));
+/// ```
+pub fn cylindrical_map(point: &Point) -> (f64, f64) {
+    // Compute u the same way as for a spherical mapping.
+    let theta = point.x().atan2(point.z());
+    let raw_u = theta / (2.0 * std::f64::consts::PI);
+    let u = 1.0 - (raw_u + 0.5);
+
+    (u, point.y().rem_euclid(1.0))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        math::point::Point,
+        canvas::{Canvas, color::Color},
+        patterns::{Pattern, uv::*},
+    };
+
+    #[test]
+    fn using_a_spherical_mapping_on_a_3d_point() {
+        assert_eq!((0.0, 0.5), spherical_map(&Point::new(0.0, 0.0, -1.0)));
+        assert_eq!((0.25, 0.5), spherical_map(&Point::new(1.0, 0.0, 0.0)));
+        assert_eq!((0.5, 0.5), spherical_map(&Point::new(0.0, 0.0, 1.0)));
+        assert_eq!((0.75, 0.5), spherical_map(&Point::new(-1.0, 0.0, 0.0)));
+        assert_eq!((0.5, 1.0), spherical_map(&Point::new(0.0, 1.0, 0.0)));
+        assert_eq!((0.5, 0.0), spherical_map(&Point::new(0.0, -1.0, 0.0)));
+    }
+
+    #[test]
+    fn using_a_spherical_mapping_off_the_equator() {
+        let (u, v) = spherical_map(&Point::new(2.0_f64.sqrt() / 2.0, 2.0_f64.sqrt() / 2.0, 0.0));
+
+        assert_eq!(0.25, u);
+        assert!((0.75 - v).abs() < 1e-10);
+    }
+
+    fn assert_uv_eq(expected: (f64, f64), actual: (f64, f64)) {
+        assert!((expected.0 - actual.0).abs() < 1e-10 && (expected.1 - actual.1).abs() < 1e-10,
+                "expected {:?}, got {:?}", expected, actual);
+    }
+
+    #[test]
+    fn using_a_planar_mapping_on_a_3d_point() {
+        assert_uv_eq((0.25, 0.5), planar_map(&Point::new(0.25, 0.0, 0.5)));
+        assert_uv_eq((0.25, 0.75), planar_map(&Point::new(0.25, 0.0, -0.25)));
+        assert_uv_eq((0.25, 0.75), planar_map(&Point::new(0.25, 0.5, -0.25)));
+        assert_uv_eq((0.25, 0.5), planar_map(&Point::new(1.25, 0.0, 0.5)));
+        assert_uv_eq((0.25, 0.25), planar_map(&Point::new(0.25, 0.0, -1.75)));
+        assert_uv_eq((0.0, 0.0), planar_map(&Point::new(1.0, 0.0, -1.0)));
+        assert_uv_eq((0.0, 0.0), planar_map(&Point::new(0.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn planar_mapping_wraps_negative_coordinates() {
+        assert_uv_eq((0.75, 0.5), planar_map(&Point::new(-0.25, 0.0, -2.5)));
+        assert_uv_eq((0.5, 0.9), planar_map(&Point::new(-3.5, 0.0, -0.1)));
+    }
+
+    #[test]
+    fn using_a_cylindrical_mapping_on_a_3d_point() {
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+
+        assert_uv_eq((0.0, 0.0), cylindrical_map(&Point::new(0.0, 0.0, -1.0)));
+        assert_uv_eq((0.0, 0.5), cylindrical_map(&Point::new(0.0, 0.5, -1.0)));
+        assert_uv_eq((0.0, 0.0), cylindrical_map(&Point::new(0.0, 1.0, -1.0)));
+        assert_uv_eq((0.125, 0.5), cylindrical_map(&Point::new(h, 0.5, -h)));
+        assert_uv_eq((0.25, 0.5), cylindrical_map(&Point::new(1.0, 0.5, 0.0)));
+        assert_uv_eq((0.375, 0.5), cylindrical_map(&Point::new(h, 0.5, h)));
+        assert_uv_eq((0.5, 0.75), cylindrical_map(&Point::new(0.0, -0.25, 1.0)));
+        assert_uv_eq((0.625, 0.5), cylindrical_map(&Point::new(-h, 0.5, h)));
+        assert_uv_eq((0.75, 0.25), cylindrical_map(&Point::new(-1.0, 1.25, 0.0)));
+        assert_uv_eq((0.875, 0.5), cylindrical_map(&Point::new(-h, 0.5, -h)));
+    }
+
+    #[test]
+    fn checker_pattern_in_2d() {
+        let black = Color::new(0.0, 0.0, 0.0);
+        let white = Color::new(1.0, 1.0, 1.0);
+        let checkers = uv_checkers(2, 2, black, white);
+
+        assert_eq!(black, checkers.uv_pattern_at(0.0, 0.0));
+        assert_eq!(white, checkers.uv_pattern_at(0.5, 0.0));
+        assert_eq!(white, checkers.uv_pattern_at(0.0, 0.5));
+        assert_eq!(black, checkers.uv_pattern_at(0.5, 0.5));
+        assert_eq!(black, checkers.uv_pattern_at(1.0, 1.0));
+        assert_eq!(white, checkers.uv_pattern_at(0.49, 0.99));
+        assert_eq!(black, checkers.uv_pattern_at(0.99, 0.99));
+    }
+
+    #[test]
+    fn using_a_texture_map_pattern_with_a_spherical_map() {
+        let black = Color::new(0.0, 0.0, 0.0);
+        let white = Color::new(1.0, 1.0, 1.0);
+        let texture = TextureMap::new(uv_checkers(16, 8, black, white), spherical_map);
+
+        assert_eq!(white, texture.pattern_at(&Point::new(0.4315, 0.4670, 0.7719)));
+        assert_eq!(black, texture.pattern_at(&Point::new(-0.9654, 0.2552, -0.0534)));
+        assert_eq!(white, texture.pattern_at(&Point::new(0.1039, 0.7090, 0.6975)));
+        assert_eq!(black, texture.pattern_at(&Point::new(-0.4986, -0.7856, -0.3663)));
+        assert_eq!(black, texture.pattern_at(&Point::new(-0.0317, -0.9395, 0.3411)));
+        assert_eq!(black, texture.pattern_at(&Point::new(0.4809, -0.7721, 0.4154)));
+        assert_eq!(black, texture.pattern_at(&Point::new(0.0285, -0.9612, -0.2745)));
+        assert_eq!(white, texture.pattern_at(&Point::new(-0.5734, -0.2162, -0.7903)));
+        assert_eq!(black, texture.pattern_at(&Point::new(0.7688, -0.1470, 0.6223)));
+        assert_eq!(black, texture.pattern_at(&Point::new(-0.7652, 0.2175, 0.6060)));
+    }
+
+    #[test]
+    fn checker_pattern_in_2d_using_an_image() {
+        let ppm = "P3\n10 10\n10\n\
+                   0 0 0  1 1 1  2 2 2  3 3 3  4 4 4  5 5 5  6 6 6  7 7 7  8 8 8  9 9 9\n\
+                   1 1 1  2 2 2  3 3 3  4 4 4  5 5 5  6 6 6  7 7 7  8 8 8  9 9 9  0 0 0\n\
+                   2 2 2  3 3 3  4 4 4  5 5 5  6 6 6  7 7 7  8 8 8  9 9 9  0 0 0  1 1 1\n\
+                   3 3 3  4 4 4  5 5 5  6 6 6  7 7 7  8 8 8  9 9 9  0 0 0  1 1 1  2 2 2\n\
+                   4 4 4  5 5 5  6 6 6  7 7 7  8 8 8  9 9 9  0 0 0  1 1 1  2 2 2  3 3 3\n\
+                   5 5 5  6 6 6  7 7 7  8 8 8  9 9 9  0 0 0  1 1 1  2 2 2  3 3 3  4 4 4\n\
+                   6 6 6  7 7 7  8 8 8  9 9 9  0 0 0  1 1 1  2 2 2  3 3 3  4 4 4  5 5 5\n\
+                   7 7 7  8 8 8  9 9 9  0 0 0  1 1 1  2 2 2  3 3 3  4 4 4  5 5 5  6 6 6\n\
+                   8 8 8  9 9 9  0 0 0  1 1 1  2 2 2  3 3 3  4 4 4  5 5 5  6 6 6  7 7 7\n\
+                   9 9 9  0 0 0  1 1 1  2 2 2  3 3 3  4 4 4  5 5 5  6 6 6  7 7 7  8 8 8\n";
+        let pattern = ImagePattern::new(Canvas::from_ppm(ppm).unwrap());
+
+        assert_eq!(Color::new(0.9, 0.9, 0.9), pattern.uv_pattern_at(0.0, 0.0));
+        assert_eq!(Color::new(0.2, 0.2, 0.2), pattern.uv_pattern_at(0.3, 0.0));
+        assert_eq!(Color::new(0.1, 0.1, 0.1), pattern.uv_pattern_at(0.6, 0.3));
+        assert_eq!(Color::new(0.9, 0.9, 0.9), pattern.uv_pattern_at(1.0, 1.0));
+    }
+
+    #[test]
+    fn sampling_a_two_color_image() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let blue = Color::new(0.0, 0.0, 1.0);
+        // top row is red, bottom row is blue
+        let ppm = "P3\n2 2\n255\n255 0 0 255 0 0\n0 0 255 0 0 255\n";
+        let pattern = ImagePattern::new(Canvas::from_ppm(ppm).unwrap());
+
+        assert_eq!(blue, pattern.uv_pattern_at(0.0, 0.0));
+        assert_eq!(blue, pattern.uv_pattern_at(1.0, 0.2));
+        assert_eq!(red, pattern.uv_pattern_at(0.0, 1.0));
+        assert_eq!(red, pattern.uv_pattern_at(0.7, 0.8));
+
+        let texture = TextureMap::new(pattern, spherical_map);
+        assert_eq!(red, texture.pattern_at(&Point::new(0.0, 1.0, 0.0)));
+        assert_eq!(blue, texture.pattern_at(&Point::new(0.0, -1.0, 0.0)));
+    }
+
+    #[test]
+    fn sampling_outside_of_the_image_uses_the_border_pixels() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let blue = Color::new(0.0, 0.0, 1.0);
+        let ppm = "P3\n2 2\n255\n255 0 0 255 0 0\n0 0 255 0 0 255\n";
+        let pattern = ImagePattern::new(Canvas::from_ppm(ppm).unwrap());
+
+        assert_eq!(blue, pattern.uv_pattern_at(1.5, -0.5));
+        assert_eq!(red, pattern.uv_pattern_at(-0.5, 1.5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn an_image_pattern_requires_a_non_empty_canvas() {
+        ImagePattern::new(Canvas::new(0, 3));
+    }
+}
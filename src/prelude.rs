@@ -0,0 +1,33 @@
+//! Re-exports of the most commonly used types and functions.
+//!
+//! ```
+//! use sugar_ray::prelude::*;
+//!
+//! let mut s = Sphere::new();
+//! s.set_transform(translation(0.0, 0.0, 2.0) * scaling(2.0, 2.0, 2.0));
+//! s.set_material_color(Color::new(1.0, 0.2, 1.0));
+//!
+//! let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+//! let xs = r.intersect_sphere(&s).unwrap();
+//!
+//! assert_eq!(5.0, xs.hit().unwrap().t());
+//! ```
+
+pub use crate::{
+    math::{
+        point::Point,
+        vector::Vector,
+        matrix::{Matrix, transformation::*},
+    },
+    canvas::{Canvas, color::Color},
+    ppm::Ppm,
+    ray::{
+        Ray,
+        intersection::{Intersection, Intersections},
+    },
+    shapes::{Shape, Sphere, group::Group},
+    materials::Material,
+    light::{Light, PointLight, SpotLight},
+    world::World,
+    camera::Camera,
+};
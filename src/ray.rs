@@ -1,7 +1,8 @@
 pub mod intersection;
+pub mod computations;
 
 use crate::{
-    shapes::Sphere,
+    shapes::{Shape, Sphere},
     math::{
         matrix::Matrix,
         point::Point,
@@ -47,6 +48,18 @@ impl Ray {
         &self.direction
     }
     
+    /// Set a new origin.
+    ///
+    /// This allows to reuse a ray, e.g. when casting multiple samples.
+    pub fn set_origin(&mut self, p: Point) {
+        self.origin = p;
+    }
+    
+    /// Set a new direction.
+    pub fn set_direction(&mut self, v: Vector) {
+        self.direction = v;
+    }
+    
     /// Computes the point at the given distance `t` along the ray.
     ///
     /// # Arguments
@@ -70,6 +83,46 @@ impl Ray {
         self.origin + self.direction * t
     }
 
+    /// Shorthand for [`Ray::position`].
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - The distance from the origin
+    ///
+    /// # Examples
+    /// ```
+    /// use sugar_ray::ray::Ray;
+    /// use sugar_ray::math::{point::Point, vector::Vector};
+    ///
+    /// let r: Ray = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::new(4.0, 5.0, 6.0));
+    ///
+    /// assert_eq!(r.position(1.5), r.at(1.5));
+    /// ```
+    pub fn at(&self, t: f64) -> Point {
+        self.position(t)
+    }
+
+    /// Computes the points for multiple distances `ts` along the ray.
+    ///
+    /// The points are returned in the same order as the given distances.
+    ///
+    /// # Arguments
+    ///
+    /// * `ts` - The distances from the origin
+    ///
+    /// # Examples
+    /// ```
+    /// use sugar_ray::ray::Ray;
+    /// use sugar_ray::math::{point::Point, vector::Vector};
+    ///
+    /// let r: Ray = Ray::new(Point::new(2.0, 3.0, 4.0), Vector::new(1.0, 0.0, 0.0));
+    ///
+    /// assert_eq!(vec![Point::new(2.0, 3.0, 4.0), Point::new(3.0, 3.0, 4.0)], r.positions(&[0.0, 1.0]));
+    /// ```
+    pub fn positions(&self, ts: &[f64]) -> Vec<Point> {
+        ts.iter().map(|t| self.position(*t)).collect()
+    }
+
     /// Calculates the distances at which a specific ray intersects the given sphere.
     ///
     /// This function returns always two values `t1` and `t2` as a tuple as an 
@@ -191,28 +244,47 @@ impl Ray {
     /// assert_eq!(true, xs.is_none());
     /// ```
     pub fn intersect_sphere<'a>(&self, sphere: &'a Sphere) -> Option<Intersections<'a, Sphere>> {
-        let tray = self.transform(&sphere.get_transform().inverse().unwrap());
-
-        // We assume that every sphere has its origin at p(0,0,0).
-        let sphere_to_ray = *tray.origin() - Point::new(0.0, 0.0, 0.0);
-
-        let a = tray.direction().dot(&tray.direction());
-        let b = 2.0 * tray.direction().dot(&sphere_to_ray);
-        let c = sphere_to_ray.dot(&sphere_to_ray) - 1.0;
-
-        let discriminant = b * b  - 4.0 * a * c;
-        
-        // If the discriminant is negative, then the ray misses
-        // and no intersections occure between the sphere and the ray.
-        if discriminant < 0.0 { return None; }
-
-        let t1 = (-b - discriminant.sqrt()) / (2.0 * a);
-        let t2 = (-b + discriminant.sqrt()) / (2.0 * a);
+        let inverse = sphere.inverse_transform()?;
+        let tray = self.transform(&inverse);
+        let (t1, t2) = sphere.local_ts(&tray)?;
 
         Some(Intersections::new(vec![Intersection::new(t1, sphere),
                                      Intersection::new(t2, sphere)]))
     }
     
+    /// Intersect the ray with multiple spheres at once.
+    ///
+    /// Returns the intersections with all spheres, merged into one
+    /// collection and sorted in ascending order.
+    ///
+    /// # Arguments
+    ///
+    /// * `spheres` - The spheres to intersect
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     ray::Ray,
+    ///     shapes::Sphere,
+    ///     math::{point::Point, vector::Vector, matrix::transformation::translation},
+    /// };
+    ///
+    /// let mut s = Sphere::new();
+    /// s.set_transform(translation(0.0, 0.0, 3.0));
+    /// let spheres = vec![s, Sphere::new()];
+    /// let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+    /// let xs = r.intersect_many(&spheres);
+    ///
+    /// assert_eq!(4, xs.len());
+    /// assert!(xs[0].is_same_object(&spheres[1]));
+    /// ```
+    pub fn intersect_many<'a>(&self, spheres: &'a [Sphere]) -> Intersections<'a, Sphere> {
+        // The intersections of every sphere are sorted already,
+        // so they only have to be merged.
+        Intersections::merge(spheres.iter().filter_map(|s| self.intersect_sphere(s)).collect())
+    }
+    
     /// Transform a ray.
     ///
     /// Applies the given transformation matrix to the ray.
@@ -264,7 +336,42 @@ impl Ray {
     /// assert_eq!(Vector::new(0.0, 3.0, 0.0), *r2.direction());
     /// ```
     pub fn transform(&self, m: &Matrix) -> Self {
-        Self { origin: m.mul_point(&self.origin()), direction: m.mul_vec(&self.direction()) } 
+        Self { origin: m.mul_point(self.origin()), direction: m.mul_vec(self.direction()) } 
+    }
+    
+    /// Transform a ray from world space into the object space of a shape.
+    ///
+    /// Returns the transformed ray together with the inverse of the shape's
+    /// transformation, so the inverse doesn't have to be calculated again
+    /// (e.g. to transform normals). Returns None if the transformation
+    /// isn't invertible.
+    ///
+    /// # Arguments
+    ///
+    /// * `shape_transform` - The transformation of the shape (object to world space)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     ray::Ray,
+    ///     math::{
+    ///         point::Point,
+    ///         vector::Vector,
+    ///         matrix::transformation::translation,
+    ///     },
+    /// };
+    ///
+    /// let r = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::new(0.0, 1.0, 0.0));
+    /// let (r2, inv) = r.to_object_space(&translation(3.0, 4.0, 5.0)).unwrap();
+    ///
+    /// assert_eq!(Point::new(-2.0, -2.0, -2.0), *r2.origin());
+    /// assert_eq!(translation(-3.0, -4.0, -5.0), inv);
+    /// ```
+    pub fn to_object_space(&self, shape_transform: &Matrix) -> Option<(Self, Matrix)> {
+        let inv = shape_transform.inverse()?;
+        
+        Some((self.transform(&inv), inv))
     }
 }
 
@@ -276,6 +383,7 @@ mod test {
         math::{
         point::Point,
         vector::Vector,
+        matrix::{Matrix, transformation::*},
     }};
 
     #[test]
@@ -294,6 +402,23 @@ mod test {
         assert_eq!(Point::new(1.0,3.0,4.0), r.position(-1.0));
         assert_eq!(Point::new(4.5,3.0,4.0), r.position(2.5));
     }
+
+    #[test]
+    fn at_matches_position() {
+        let r = Ray::new(Point::new(2.0,3.0,4.0), Vector::new(1.0,0.0,0.0));
+
+        assert_eq!(r.position(0.0), r.at(0.0));
+        assert_eq!(r.position(-1.0), r.at(-1.0));
+        assert_eq!(r.position(2.5), r.at(2.5));
+    }
+
+    #[test]
+    fn computing_multiple_points_from_distances() {
+        let r = Ray::new(Point::new(2.0,3.0,4.0), Vector::new(1.0,0.0,0.0));
+        let ps = r.positions(&[0.0, 1.0, 2.5]);
+
+        assert_eq!(vec![Point::new(2.0,3.0,4.0), Point::new(3.0,3.0,4.0), Point::new(4.5,3.0,4.0)], ps);
+    }
     
     #[test]
     fn a_ray_intersects_a_sphere_at_two_points() {
@@ -321,7 +446,7 @@ mod test {
         let s = Sphere::new();
         let xs = r.intersect_sphere(&s);
 
-        assert_eq!(true, xs.is_none());
+        assert!(xs.is_none());
     }
 
     #[test]
@@ -343,4 +468,57 @@ mod test {
         assert_eq!(-6.0, xs[0].t());
         assert_eq!(-4.0, xs[1].t());
     }
+
+    #[test]
+    fn transforming_a_ray_into_object_space() {
+        let r = Ray::new(Point::new(1.0,2.0,3.0), Vector::new(0.0,1.0,0.0));
+        let m = scaling(2.0, 3.0, 4.0).translate(1.0, -2.0, 0.5);
+        let (tray, inv) = r.to_object_space(&m).unwrap();
+
+        assert_eq!(r.transform(&m.inverse().unwrap()), tray);
+        assert_eq!(m.inverse().unwrap(), inv);
+    }
+
+    #[test]
+    fn transforming_a_ray_with_a_non_invertible_matrix() {
+        let r = Ray::new(Point::new(1.0,2.0,3.0), Vector::new(0.0,1.0,0.0));
+
+        assert!(r.to_object_space(&Matrix::new(4, 4)).is_none());
+    }
+
+    #[test]
+    fn intersecting_a_ray_with_many_spheres() {
+        let mut near = Sphere::new();
+        near.set_transform(translation(0.0, 0.0, -2.0) * scaling(0.5, 0.5, 0.5));
+        let middle = Sphere::new();
+        let mut far = Sphere::new();
+        far.set_transform(translation(0.0, 0.0, 4.0));
+        let spheres = [far, near, middle];
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = r.intersect_many(&spheres);
+
+        let ts: Vec<f64> = xs.iter().map(|i| i.t()).collect();
+        assert_eq!(vec![2.5, 3.5, 4.0, 6.0, 8.0, 10.0], ts);
+        assert!(xs[0].is_same_object(&spheres[1]));
+        assert!(xs[2].is_same_object(&spheres[2]));
+        assert!(xs[4].is_same_object(&spheres[0]));
+    }
+
+    #[test]
+    fn intersecting_a_ray_with_no_spheres() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert!(r.intersect_many(&[]).is_empty());
+    }
+
+    #[test]
+    fn changing_the_origin_and_direction_of_a_ray() {
+        let mut r = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::new(4.0, 5.0, 6.0));
+        r.set_origin(Point::new(0.0, 0.0, -5.0));
+        r.set_direction(Vector::new(0.0, 1.0, 0.0));
+
+        assert_eq!(Point::new(0.0, 0.0, -5.0), *r.origin());
+        assert_eq!(Vector::new(0.0, 1.0, 0.0), *r.direction());
+        assert_eq!(Point::new(0.0, 2.0, -5.0), r.position(2.0));
+    }
 }
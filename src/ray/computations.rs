@@ -0,0 +1,378 @@
+use crate::{
+    shapes::Shape,
+    math::{
+        EPSILON,
+        point::Point,
+        vector::Vector,
+    },
+    ray::{
+        Ray,
+        intersection::{Intersection, Intersections},
+    },
+};
+
+/// Precomputed state of an intersection that is required
+/// to shade the intersected point.
+///
+/// # Properties
+///
+/// * `t` - The `t` value of the intersection
+/// * `obj` - The intersected object
+/// * `point` - The point of intersection (in world space)
+/// * `eyev` - Vector pointing back to the eye (camera)
+/// * `normalv` - The surface normal, always pointing to the side of the eye
+/// * `inside` - True if the intersection occured inside of the object
+/// * `over_point` - The point slightly moved above the surface
+/// * `under_point` - The point slightly moved below the surface
+/// * `reflectv` - The direction of the reflected ray
+/// * `n1` - Refractive index of the material the ray is passing from
+/// * `n2` - Refractive index of the material the ray is passing to
+#[derive(Debug)]
+pub struct Comps<'a, T: ?Sized> {
+    t: f64,
+    obj: &'a T,
+    point: Point,
+    eyev: Vector,
+    normalv: Vector,
+    inside: bool,
+    over_point: Point,
+    under_point: Point,
+    reflectv: Vector,
+    n1: f64,
+    n2: f64,
+}
+
+impl<'a, T: ?Sized> Comps<'a, T> {
+    /// Get the `t` value of the intersection.
+    pub fn t(&self) -> f64 {
+        self.t
+    }
+
+    /// Get the intersected object.
+    pub fn obj(&self) -> &'a T {
+        self.obj
+    }
+
+    /// Get the point of intersection.
+    pub fn point(&self) -> &Point {
+        &self.point
+    }
+
+    /// Get the eye vector.
+    pub fn eyev(&self) -> &Vector {
+        &self.eyev
+    }
+
+    /// Get the surface normal.
+    pub fn normalv(&self) -> &Vector {
+        &self.normalv
+    }
+
+    /// Check if the intersection occured inside of the object.
+    pub fn inside(&self) -> bool {
+        self.inside
+    }
+
+    /// Get the point slightly above the surface.
+    ///
+    /// Use this point as origin for shadow and reflection rays.
+    pub fn over_point(&self) -> &Point {
+        &self.over_point
+    }
+
+    /// Get the point slightly below the surface.
+    ///
+    /// Use this point as origin for refraction rays.
+    pub fn under_point(&self) -> &Point {
+        &self.under_point
+    }
+
+    /// Get the reflection vector.
+    pub fn reflectv(&self) -> &Vector {
+        &self.reflectv
+    }
+
+    /// Get the refractive index of the material being exited.
+    pub fn n1(&self) -> f64 {
+        self.n1
+    }
+
+    /// Get the refractive index of the material being entered.
+    pub fn n2(&self) -> f64 {
+        self.n2
+    }
+
+    /// Approximate the fraction of light that is reflected (Schlick).
+    ///
+    /// The reflectance is 1.0 in case of total internal reflection and
+    /// increases the more the eye vector and the normal diverge (Fresnel
+    /// effect).
+    pub fn schlick(&self) -> f64 {
+        let mut cos = self.eyev.dot(&self.normalv);
+
+        if self.n1 > self.n2 {
+            let n = self.n1 / self.n2;
+            let sin2_t = n * n * (1.0 - cos * cos);
+
+            // Total internal reflection
+            if sin2_t > 1.0 {
+                return 1.0;
+            }
+
+            cos = (1.0 - sin2_t).sqrt();
+        }
+
+        let r0 = ((self.n1 - self.n2) / (self.n1 + self.n2)).powi(2);
+        r0 + (1.0 - r0) * (1.0 - cos).powi(5)
+    }
+}
+
+impl<'a, T: Shape + ?Sized> Intersection<'a, T> {
+    /// Precompute the state required for shading the intersection.
+    ///
+    /// To find the refractive indices `n1` and `n2` all intersections
+    /// of the ray are walked in order while keeping track of the
+    /// objects the ray is currently inside of.
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - The ray that caused the intersection
+    /// * `xs` - All intersections of the ray (including this one)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     shapes::Sphere,
+    ///     ray::{Ray, intersection::{Intersection, Intersections}},
+    ///     math::{point::Point, vector::Vector},
+    /// };
+    ///
+    /// let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+    /// let s = Sphere::new();
+    /// let i = Intersection::new(4.0, &s);
+    /// let comps = i.prepare_computations(&r, &Intersections::new(vec![i]));
+    ///
+    /// assert_eq!(Point::new(0.0, 0.0, -1.0), *comps.point());
+    /// assert_eq!(Vector::new(0.0, 0.0, -1.0), *comps.eyev());
+    /// assert_eq!(Vector::new(0.0, 0.0, -1.0), *comps.normalv());
+    /// ```
+    pub fn prepare_computations(&self, ray: &Ray, xs: &Intersections<'a, T>) -> Comps<'a, T> {
+        let point = ray.position(self.t());
+        let eyev = -*ray.direction();
+        let mut normalv = self.obj().normal_at(&point);
+        let mut inside = false;
+
+        // If the normal points away from the eye the hit occured
+        // inside of the object.
+        if normalv.dot(&eyev) < 0.0 {
+            inside = true;
+            normalv = -normalv;
+        }
+
+        let reflectv = ray.direction().reflect(&normalv);
+        let (n1, n2) = self.refractive_indices(xs);
+
+        Comps {
+            t: self.t(),
+            obj: self.obj(),
+            point,
+            eyev,
+            normalv,
+            inside,
+            over_point: point + normalv * EPSILON,
+            under_point: point - normalv * EPSILON,
+            reflectv,
+            n1,
+            n2,
+        }
+    }
+
+    /// Find the refractive indices on both sides of the intersection.
+    fn refractive_indices(&self, xs: &Intersections<'a, T>) -> (f64, f64) {
+        let mut containers: Vec<&T> = Vec::new();
+        let mut n1 = 1.0;
+        let mut n2 = 1.0;
+
+        for i in 0..xs.len() {
+            let is_hit = xs[i].t() == self.t() && xs[i].is_same_object(self.obj());
+
+            if is_hit {
+                n1 = containers.last().map_or(1.0, |o| o.material().refractive_index());
+            }
+
+            // Entering an object adds it to the containers, leaving it removes it.
+            match containers.iter().position(|o| xs[i].is_same_object(o)) {
+                Some(pos) => { containers.remove(pos); },
+                None => containers.push(xs[i].obj()),
+            }
+
+            if is_hit {
+                n2 = containers.last().map_or(1.0, |o| o.material().refractive_index());
+                break;
+            }
+        }
+
+        (n1, n2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        shapes::Sphere,
+        materials::Material,
+        ray::{
+            Ray,
+            intersection::{Intersection, Intersections},
+        },
+        math::{
+            EPSILON,
+            point::Point,
+            vector::Vector,
+            matrix::{Matrix, transformation::*},
+        },
+    };
+
+    fn glass_sphere(transform: Matrix, refractive_index: f64) -> Sphere {
+        let mut m = Material::default();
+        m.set_transparency(1.0);
+        m.set_refractive_index(refractive_index);
+
+        let mut s = Sphere::new();
+        s.set_transform(transform);
+        s.set_material(m);
+        s
+    }
+
+    #[test]
+    fn the_hit_when_an_intersection_occurs_on_the_outside() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let s = Sphere::new();
+        let i = Intersection::new(4.0, &s);
+        let comps = i.prepare_computations(&r, &Intersections::new(vec![i]));
+
+        assert_eq!(4.0, comps.t());
+        assert!(comps.obj() == &s);
+        assert!(!comps.inside());
+    }
+
+    #[test]
+    fn the_hit_when_an_intersection_occurs_on_the_inside() {
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        let s = Sphere::new();
+        let i = Intersection::new(1.0, &s);
+        let comps = i.prepare_computations(&r, &Intersections::new(vec![i]));
+
+        assert_eq!(Point::new(0.0, 0.0, 1.0), *comps.point());
+        assert_eq!(Vector::new(0.0, 0.0, -1.0), *comps.eyev());
+        assert!(comps.inside());
+        // The normal would have been (0, 0, 1) but is inverted.
+        assert_eq!(Vector::new(0.0, 0.0, -1.0), *comps.normalv());
+    }
+
+    #[test]
+    fn the_hit_should_offset_the_point() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut s = Sphere::new();
+        s.set_transform(translation(0.0, 0.0, 1.0));
+        let i = Intersection::new(5.0, &s);
+        let comps = i.prepare_computations(&r, &Intersections::new(vec![i]));
+
+        assert!(comps.over_point().z() < -EPSILON / 2.0);
+        assert!(comps.point().z() > comps.over_point().z());
+    }
+
+    #[test]
+    fn the_under_point_is_offset_below_the_surface() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let s = glass_sphere(translation(0.0, 0.0, 1.0), 1.5);
+        let i = Intersection::new(5.0, &s);
+        let comps = i.prepare_computations(&r, &Intersections::new(vec![i]));
+
+        assert!(comps.under_point().z() > EPSILON / 2.0);
+        assert!(comps.point().z() < comps.under_point().z());
+    }
+
+    #[test]
+    fn precomputing_the_reflection_vector() {
+        // The ray hits the sphere at 45 degrees and is reflected straight up.
+        let r = Ray::new(Point::new(0.0, 2.0_f64.sqrt() / 2.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let s = Sphere::new();
+        let i = Intersection::new(5.0 - 2.0_f64.sqrt() / 2.0, &s);
+        let comps = i.prepare_computations(&r, &Intersections::new(vec![i]));
+        let expected = Vector::new(0.0, 1.0, 0.0);
+
+        assert!((expected - *comps.reflectv()).mag() < EPSILON);
+    }
+
+    #[test]
+    fn finding_n1_and_n2_at_various_intersections() {
+        let a = glass_sphere(scaling(2.0, 2.0, 2.0), 1.5);
+        let b = glass_sphere(translation(0.0, 0.0, -0.25), 2.0);
+        let c = glass_sphere(translation(0.0, 0.0, 0.25), 2.5);
+        let r = Ray::new(Point::new(0.0, 0.0, -4.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = Intersections::new(vec![Intersection::new(2.0, &a),
+                                         Intersection::new(2.75, &b),
+                                         Intersection::new(3.25, &c),
+                                         Intersection::new(4.75, &b),
+                                         Intersection::new(5.25, &c),
+                                         Intersection::new(6.0, &a)]);
+        let expected = [(1.0, 1.5), (1.5, 2.0), (2.0, 2.5), (2.5, 2.5), (2.5, 1.5), (1.5, 1.0)];
+
+        for (i, (n1, n2)) in expected.iter().enumerate() {
+            let comps = xs[i].prepare_computations(&r, &xs);
+
+            assert_eq!(*n1, comps.n1());
+            assert_eq!(*n2, comps.n2());
+        }
+    }
+
+    #[test]
+    fn equal_but_distinct_objects_are_tracked_separately() {
+        let a = glass_sphere(Matrix::identity(), 1.5);
+        let b = glass_sphere(Matrix::identity(), 1.5);
+        let r = Ray::new(Point::new(0.0, 0.0, -4.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = Intersections::new(vec![Intersection::new(3.0, &a),
+                                         Intersection::new(3.0, &b),
+                                         Intersection::new(5.0, &a),
+                                         Intersection::new(5.0, &b)]);
+        // Leaving `a` still leaves the ray inside of `b`.
+        let comps = xs[2].prepare_computations(&r, &xs);
+
+        assert_eq!(1.5, comps.n1());
+        assert_eq!(1.5, comps.n2());
+    }
+
+    #[test]
+    fn the_schlick_approximation_under_total_internal_reflection() {
+        let s = glass_sphere(Matrix::identity(), 1.5);
+        let r = Ray::new(Point::new(0.0, 0.0, 2.0_f64.sqrt() / 2.0), Vector::new(0.0, 1.0, 0.0));
+        let xs = Intersections::new(vec![Intersection::new(-(2.0_f64.sqrt()) / 2.0, &s),
+                                         Intersection::new(2.0_f64.sqrt() / 2.0, &s)]);
+        let comps = xs[1].prepare_computations(&r, &xs);
+
+        assert_eq!(1.0, comps.schlick());
+    }
+
+    #[test]
+    fn the_schlick_approximation_with_a_perpendicular_viewing_angle() {
+        let s = glass_sphere(Matrix::identity(), 1.5);
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 1.0, 0.0));
+        let xs = Intersections::new(vec![Intersection::new(-1.0, &s),
+                                         Intersection::new(1.0, &s)]);
+        let comps = xs[1].prepare_computations(&r, &xs);
+
+        assert!((0.04 - comps.schlick()).abs() < EPSILON);
+    }
+
+    #[test]
+    fn the_schlick_approximation_with_small_angle_and_n2_greater_than_n1() {
+        let s = glass_sphere(Matrix::identity(), 1.5);
+        let r = Ray::new(Point::new(0.0, 0.99, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = Intersections::new(vec![Intersection::new(1.8589, &s)]);
+        let comps = xs[0].prepare_computations(&r, &xs);
+
+        assert!((0.48873 - comps.schlick()).abs() < 0.0001);
+    }
+}
@@ -1,11 +1,13 @@
+use std::{cmp::{self, Reverse}, collections::BinaryHeap};
+
 /// Represents a specific intersection between a ray and an object.
-#[derive(Debug, PartialEq, Clone)]
-pub struct Intersection<'a, T> {
+#[derive(Debug, PartialEq)]
+pub struct Intersection<'a, T: ?Sized> {
     t: f64,  // A t value wher Origin + t * Direction = Point
     obj: &'a T, // A reference to the intersected object
 }
 
-impl<'a, T> Intersection<'a, T> {
+impl<'a, T: ?Sized> Intersection<'a, T> {
     /// Creates a new intersection.
     ///
     /// # Arguments
@@ -41,19 +43,56 @@ impl<'a, T> Intersection<'a, T> {
     pub fn obj(&self) -> &'a T {
         self.obj
     }
+    
+    /// Check if the intersection refers to the given object.
+    ///
+    /// Objects are compared by identity (i.e. by their address),
+    /// not by value, so two equal but distinct objects are
+    /// considered different.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The object to compare against
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{shapes::Sphere, ray::intersection::Intersection};
+    ///
+    /// let s1 = Sphere::new();
+    /// let s2 = Sphere::new();
+    /// let i = Intersection::new(1.0, &s1);
+    ///
+    /// assert!(i.is_same_object(&s1));
+    /// assert!(!i.is_same_object(&s2));
+    /// ```
+    pub fn is_same_object(&self, other: &T) -> bool {
+        // Only compare the addresses (and not the vtables of trait objects).
+        std::ptr::eq(self.obj as *const T as *const (), other as *const T as *const ())
+    }
 }
 
+// An intersection only holds a reference to the object, so it can
+// be copied regardless of whether the object itself is copyable.
+impl<'a, T: ?Sized> Clone for Intersection<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T: ?Sized> Copy for Intersection<'a, T> {}
+
 /// Represents a collection of Intersection(s).
 #[derive(Debug, PartialEq)]
-pub struct Intersections<'a, T> {
+pub struct Intersections<'a, T: ?Sized> {
     v: Vec<Intersection<'a, T>>,
 }
 
-impl<'a, T> Intersections<'a, T> {
+impl<'a, T: ?Sized> Intersections<'a, T> {
     /// Create a new collection of Intersection(s).
     ///
     /// The intersections are sorted by their `t` values
-    /// in ascending order.
+    /// in ascending order (see [`sort_intersections`]).
     ///
     /// # Arguments
     ///
@@ -78,7 +117,7 @@ impl<'a, T> Intersections<'a, T> {
     /// assert_eq!(2.0, xs[1].t());
     /// ```
     pub fn new(mut args: Vec<Intersection<'a, T>>) -> Self {
-        args.sort_by(|a, b| a.t().partial_cmp(&b.t()).unwrap());
+        sort_intersections(&mut args);
 
         Self { v: args }
     }
@@ -86,6 +125,127 @@ impl<'a, T> Intersections<'a, T> {
     pub fn len(&self) -> usize {
         self.v.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.v.is_empty()
+    }
+    
+    /// Merge several (already sorted) collections of intersections into one.
+    ///
+    /// Each collection is sorted already, so instead of sorting all
+    /// intersections again they are combined using a k-way merge, i.e.
+    /// in `O(n log k)` for `n` intersections in `k` collections. The order
+    /// is the same as if all intersections were passed to [`Intersections::new`]
+    /// in the order of `lists`.
+    ///
+    /// # Arguments
+    ///
+    /// * `lists` - The collections to merge
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     shapes::Sphere,
+    ///     ray::intersection::{Intersection, Intersections},
+    /// };
+    ///
+    /// let s = Sphere::new();
+    /// let a = Intersections::new(vec![Intersection::new(1.0, &s), Intersection::new(4.0, &s)]);
+    /// let b = Intersections::new(vec![Intersection::new(2.0, &s), Intersection::new(3.0, &s)]);
+    ///
+    /// let xs = Intersections::merge(vec![a, b]);
+    /// let ts: Vec<f64> = xs.iter().map(|i| i.t()).collect();
+    ///
+    /// assert_eq!(vec![1.0, 2.0, 3.0, 4.0], ts);
+    /// ```
+    pub fn merge(lists: Vec<Intersections<'a, T>>) -> Self {
+        let total = lists.iter().map(|l| l.len()).sum();
+        let mut v = Vec::with_capacity(total);
+        let mut iters: Vec<_> = lists.into_iter().map(|l| l.v.into_iter()).collect();
+
+        // The heap holds the next (smallest) intersection of every list.
+        let mut heap = BinaryHeap::with_capacity(iters.len());
+        for (list, it) in iters.iter_mut().enumerate() {
+            if let Some(i) = it.next() {
+                heap.push(Reverse(MergeEntry { t: i.t(), list, i }));
+            }
+        }
+
+        while let Some(Reverse(entry)) = heap.pop() {
+            v.push(entry.i);
+
+            if let Some(i) = iters[entry.list].next() {
+                heap.push(Reverse(MergeEntry { t: i.t(), list: entry.list, i }));
+            }
+        }
+
+        Self { v }
+    }
+    
+    /// Merge intersections with (nearly) the same `t` value and object.
+    ///
+    /// A ray that grazes an object tangentially hits it twice at the
+    /// same `t`. This collapses such intersections into one. Only
+    /// intersections that refer to the same object are merged.
+    ///
+    /// # Arguments
+    ///
+    /// * `epsilon` - Intersections whose `t` differ by less are merged
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     shapes::Sphere,
+    ///     ray::intersection::{Intersection, Intersections},
+    /// };
+    ///
+    /// let s = Sphere::new();
+    /// let mut xs = Intersections::new(vec![Intersection::new(5.0, &s), Intersection::new(5.0, &s)]);
+    /// xs.dedup(0.00001);
+    ///
+    /// assert_eq!(1, xs.len());
+    /// ```
+    pub fn dedup(&mut self, epsilon: f64) {
+        // The intersections are sorted, so duplicates are always adjacent.
+        self.v.dedup_by(|a, b| (a.t() - b.t()).abs() < epsilon && a.is_same_object(b.obj()));
+    }
+    
+    /// Get an iterator over the intersections (in ascending order).
+    pub fn iter(&self) -> std::slice::Iter<'_, Intersection<'a, T>> {
+        self.v.iter()
+    }
+    
+    /// Get all intersections whose `t` value lies within the given range.
+    ///
+    /// Both bounds are inclusive. The intersections are returned
+    /// in ascending order.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The smallest `t` value to include
+    /// * `max` - The largest `t` value to include
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     shapes::Sphere,
+    ///     ray::intersection::{Intersection, Intersections},
+    /// };
+    ///
+    /// let s = Sphere::new();
+    /// let xs = Intersections::new(vec![Intersection::new(1.0, &s), Intersection::new(3.0, &s)]);
+    ///
+    /// assert_eq!(1, xs.hits_in_range(2.0, 5.0).len());
+    /// assert_eq!(3.0, xs.hits_in_range(2.0, 5.0)[0].t());
+    /// ```
+    pub fn hits_in_range(&self, min: f64, max: f64) -> Vec<&Intersection<'a, T>> {
+        self.v.iter()
+            .filter(|i| (min..=max).contains(&i.t()))
+            .collect()
+    }
     
     /// Returns the hit from a collection of intersection records.
     ///
@@ -137,7 +297,7 @@ impl<'a, T> Intersections<'a, T> {
     }
 }
 
-impl<'a, T> std::ops::Index<usize> for Intersections<'a, T> {
+impl<'a, T: ?Sized> std::ops::Index<usize> for Intersections<'a, T> {
     type Output = Intersection<'a, T>;
 
     fn index(&self, i: usize) -> &Intersection<'a, T> {
@@ -146,11 +306,111 @@ impl<'a, T> std::ops::Index<usize> for Intersections<'a, T> {
 
 }
 
+impl<'a, T: ?Sized> std::iter::FromIterator<Intersection<'a, T>> for Intersections<'a, T> {
+    /// Collect intersections and sort them (see [`Intersections::new`]).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     shapes::Sphere,
+    ///     ray::intersection::{Intersection, Intersections},
+    /// };
+    ///
+    /// let s = Sphere::new();
+    /// let xs: Intersections<Sphere> = [3.0, 1.0, 2.0].iter()
+    ///     .map(|t| Intersection::new(*t, &s))
+    ///     .collect();
+    ///
+    /// assert_eq!(1.0, xs[0].t());
+    /// ```
+    fn from_iter<I: IntoIterator<Item = Intersection<'a, T>>>(iter: I) -> Self {
+        Intersections::new(iter.into_iter().collect())
+    }
+}
+
+/// Sort the given intersections by their `t` values in ascending order.
+///
+/// Intersections with a `t` value of `NaN` are moved to the end. The sort
+/// is stable, i.e. intersections with equal `t` values keep their order.
+///
+/// # Arguments
+///
+/// * `v` - The intersections to sort
+///
+/// # Examples
+///
+/// ```
+/// use sugar_ray::{
+///     shapes::Sphere,
+///     ray::intersection::{Intersection, sort_intersections},
+/// };
+///
+/// let s = Sphere::new();
+/// let mut v = vec![Intersection::new(f64::NAN, &s),
+///                  Intersection::new(2.0, &s),
+///                  Intersection::new(-1.0, &s)];
+/// sort_intersections(&mut v);
+///
+/// assert_eq!(-1.0, v[0].t());
+/// assert_eq!(2.0, v[1].t());
+/// assert!(v[2].t().is_nan());
+/// ```
+pub fn sort_intersections<T: ?Sized>(v: &mut [Intersection<'_, T>]) {
+    v.sort_by(|a, b| cmp_t(a.t(), b.t()));
+}
+
+/// Compare two `t` values, treating `NaN` as larger than any number.
+fn cmp_t(a: f64, b: f64) -> cmp::Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (false, false) => a.partial_cmp(&b).unwrap(),
+        (a_nan, b_nan) => a_nan.cmp(&b_nan),
+    }
+}
+
+/// The head of a list during [`Intersections::merge`].
+///
+/// Entries are ordered by `t` and then by the index of their list,
+/// so intersections with equal `t` values keep the order of the lists.
+struct MergeEntry<'a, T: ?Sized> {
+    t: f64,
+    list: usize,
+    i: Intersection<'a, T>,
+}
+
+impl<'a, T: ?Sized> PartialEq for MergeEntry<'a, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl<'a, T: ?Sized> Eq for MergeEntry<'a, T> {}
+
+impl<'a, T: ?Sized> PartialOrd for MergeEntry<'a, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, T: ?Sized> Ord for MergeEntry<'a, T> {
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        cmp_t(self.t, other.t).then(self.list.cmp(&other.list))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{
         shapes::Sphere,
-        ray::intersection::{Intersection, Intersections},
+        ray::{
+            Ray,
+            intersection::{Intersection, Intersections, sort_intersections},
+        },
+        math::{
+            EPSILON,
+            point::Point,
+            vector::Vector,
+        },
     };
 
     #[test]
@@ -174,4 +434,116 @@ mod test {
 
         assert_eq!(Intersection::new(2.0, &s), *xs.hit().unwrap());
     }
+
+    #[test]
+    fn intersections_compare_objects_by_identity() {
+        let s1 = Sphere::new();
+        let s2 = Sphere::new();
+        let i1 = Intersection::new(1.0, &s1);
+        let i2 = Intersection::new(2.0, &s1);
+
+        assert_eq!(s1, s2);
+        assert!(i1.is_same_object(i2.obj()));
+        assert!(!i1.is_same_object(&s2));
+    }
+
+    #[test]
+    fn deduplicating_a_tangent_hit() {
+        let s = Sphere::new();
+        let r = Ray::new(Point::new(0.0, 1.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut xs = r.intersect_sphere(&s).unwrap();
+        assert_eq!(2, xs.len());
+
+        xs.dedup(EPSILON);
+
+        assert_eq!(1, xs.len());
+        assert_eq!(5.0, xs[0].t());
+    }
+
+    #[test]
+    fn deduplicating_keeps_distinct_hits_and_objects() {
+        let s1 = Sphere::new();
+        let s2 = Sphere::new();
+        let mut xs = Intersections::new(vec![Intersection::new(1.0, &s1),
+                                             Intersection::new(1.0, &s2),
+                                             Intersection::new(2.0, &s1),
+                                             Intersection::new(2.000001, &s1),
+                                             Intersection::new(3.0, &s1)]);
+        xs.dedup(EPSILON);
+
+        assert_eq!(4, xs.len());
+        assert!(xs[0].is_same_object(&s1));
+        assert!(xs[1].is_same_object(&s2));
+        assert_eq!(2.0, xs[2].t());
+        assert_eq!(3.0, xs[3].t());
+    }
+
+    #[test]
+    fn sorting_a_shuffled_vec_of_intersections() {
+        let s = Sphere::new();
+        let mut v = vec![Intersection::new(5.0, &s),
+                         Intersection::new(-3.0, &s),
+                         Intersection::new(f64::NAN, &s),
+                         Intersection::new(7.0, &s),
+                         Intersection::new(0.0, &s),
+                         Intersection::new(2.5, &s)];
+        sort_intersections(&mut v);
+
+        let ts: Vec<f64> = v.iter().take(5).map(|i| i.t()).collect();
+        assert_eq!(vec![-3.0, 0.0, 2.5, 5.0, 7.0], ts);
+        assert!(v[5].t().is_nan());
+    }
+
+    #[test]
+    fn creating_intersections_with_a_nan_doesnt_panic() {
+        let s = Sphere::new();
+        let xs = Intersections::new(vec![Intersection::new(f64::NAN, &s), Intersection::new(1.0, &s)]);
+
+        assert_eq!(1.0, xs.hit().unwrap().t());
+    }
+
+    #[test]
+    fn filtering_intersections_by_a_range_of_t() {
+        let s = Sphere::new();
+        let xs = Intersections::new(vec![Intersection::new(7.0, &s),
+                                         Intersection::new(1.0, &s),
+                                         Intersection::new(4.0, &s)]);
+        let hits = xs.hits_in_range(2.0, 6.0);
+
+        assert_eq!(1, hits.len());
+        assert_eq!(4.0, hits[0].t());
+        assert_eq!(3, xs.hits_in_range(1.0, 7.0).len());
+        assert!(xs.hits_in_range(8.0, 9.0).is_empty());
+    }
+
+    #[test]
+    fn merging_sorted_intersections_keeps_them_sorted() {
+        let s = Sphere::new();
+        let a = Intersections::new(vec![Intersection::new(-1.0, &s), Intersection::new(5.0, &s)]);
+        let b = Intersections::new(vec![Intersection::new(f64::NAN, &s), Intersection::new(2.0, &s)]);
+        let c = Intersections::new(vec![]);
+        let d = Intersections::new(vec![Intersection::new(0.5, &s), Intersection::new(7.0, &s), Intersection::new(2.0, &s)]);
+
+        let xs = Intersections::merge(vec![a, b, c, d]);
+
+        assert_eq!(7, xs.len());
+        let ts: Vec<f64> = xs.iter().take(6).map(|i| i.t()).collect();
+        assert_eq!(vec![-1.0, 0.5, 2.0, 2.0, 5.0, 7.0], ts);
+        assert!(xs[6].t().is_nan());
+    }
+
+    #[test]
+    fn collecting_an_iterator_into_sorted_intersections() {
+        let spheres = [Sphere::new(), Sphere::new()];
+        let xs: Intersections<Sphere> = spheres.iter()
+            .enumerate()
+            .flat_map(|(i, s)| vec![Intersection::new(5.0 - i as f64, s), Intersection::new(-(i as f64), s)])
+            .collect();
+
+        assert_eq!(4, xs.len());
+        assert_eq!(-1.0, xs[0].t());
+        assert_eq!(0.0, xs[1].t());
+        assert_eq!(4.0, xs[2].t());
+        assert_eq!(5.0, xs[3].t());
+    }
 }
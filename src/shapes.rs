@@ -1,16 +1,163 @@
+pub mod bounds;
+pub mod group;
+#[cfg(test)]
+pub(crate) mod test_shape;
+
 use crate::math::{
+    EPSILON,
     matrix::Matrix,
     point::Point,
     vector::Vector,
 };
 use crate::materials::Material;
 use crate::canvas::color::Color;
+use crate::ray::{
+    Ray,
+    intersection::Intersection,
+};
+use crate::world::stats::Stats;
+use self::bounds::BoundingBox;
+
+use std::{borrow::Cow, fmt};
+
+/// The common interface of all shapes.
+///
+/// Every shape is defined in its own "object space" and placed
+/// in the world by its transformation. Implementors only have to
+/// deal with rays that are already transformed into object space
+/// (`local_intersect`), the conversion is done by `intersect`.
+pub trait Shape: fmt::Debug {
+    /// Get the transformation (object space to world space) of the shape.
+    fn transform(&self) -> &Matrix;
+
+    /// Get the material of the shape.
+    fn material(&self) -> &Material;
+
+    /// Intersect the shape with a ray given in object space.
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_, dyn Shape>>;
+
+    /// Calculate the surface normal at a point given in object space.
+    fn local_normal_at(&self, point: &Point) -> Vector;
+
+    /// Get the bounds of the shape in object space.
+    fn bounds(&self) -> BoundingBox;
+
+    /// Get the bounds of the shape in world space.
+    ///
+    /// All eight corners of the object space bounds are transformed
+    /// (by the shape's own and its parents' transformation) and the
+    /// result is the axis aligned box that contains all of them.
+    fn world_bounds(&self) -> BoundingBox {
+        self.bounds().transform(&self.parent_transform().mul(self.transform()))
+    }
+
+    /// Subdivide the shape into a bounding volume hierarchy.
+    ///
+    /// Only composite shapes (e.g. [`Group`](group::Group)) can be
+    /// subdivided, for all other shapes this does nothing.
+    ///
+    /// # Arguments
+    ///
+    /// * `_threshold` - The smallest number of children that is subdivided
+    fn divide(&mut self, _threshold: usize) {}
+
+    /// Get the combined transformation of all groups the shape is part of.
+    ///
+    /// This is the identity matrix for shapes that aren't part of a group.
+    fn parent_transform(&self) -> &Matrix;
+
+    /// Assign the combined transformation of all groups the shape is part of.
+    ///
+    /// This is done by [`Group`](group::Group) whenever the shape is added
+    /// to a group or the transformation of one of its parents changes.
+    fn set_parent_transform(&mut self, m: Matrix);
+
+    /// Convert a point from world space into the object space of the shape.
+    ///
+    /// The point is first converted into the space of the shape's parents
+    /// and then into the shape's own object space.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - A point in world space
+    fn world_to_object(&self, point: &Point) -> Point {
+        let parent_point = self.parent_transform().inverse().unwrap().mul_point(point);
+
+        self.transform().inverse().unwrap().mul_point(&parent_point)
+    }
+
+    /// Convert a normal from the object space of the shape into world space.
+    ///
+    /// The normal is first converted into the space of the shape's parents
+    /// and then into world space. The result is normalized.
+    ///
+    /// # Arguments
+    ///
+    /// * `normal` - A normal in object space
+    fn normal_to_world(&self, normal: &Vector) -> Vector {
+        let mut n = self.transform().inverse().unwrap().transpose().mul_vec(normal);
+        n.norm();
+
+        let mut n = self.parent_transform().inverse().unwrap().transpose().mul_vec(&n);
+        n.norm();
+        n
+    }
+
+    /// Calculate the surface normal at a point given in world space.
+    ///
+    /// In debug builds this panics if the resulting normal isn't of unit
+    /// length (within [`EPSILON`]), e.g. because a degenerate transformation
+    /// or a zero local normal produced a `NaN`. Release builds skip the check
+    /// and return the normal as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - A point (on the surface) in world space
+    fn normal_at(&self, point: &Point) -> Vector {
+        let n = self.normal_to_world(&self.local_normal_at(&self.world_to_object(point)));
+        debug_assert!((n.mag() - 1.0).abs() <= EPSILON, "normal {:?} isn't of unit length", n);
+        n
+    }
+
+    /// Get the inverse of the shape's transformation.
+    ///
+    /// Returns None if the transformation isn't invertible. By default
+    /// the inverse is calculated on every call, shapes that cache their
+    /// inverse should return a borrowed one instead.
+    fn inverse_transform(&self) -> Option<Cow<'_, Matrix>> {
+        self.transform().inverse().map(Cow::Owned)
+    }
+
+    /// Intersect the shape with a ray given in world space.
+    ///
+    /// The ray is transformed into object space (using
+    /// [`Shape::inverse_transform`]) before it's passed to
+    /// `local_intersect`. A shape with a non-invertible transformation
+    /// can't be hit.
+    fn intersect(&self, ray: &Ray) -> Vec<Intersection<'_, dyn Shape>> {
+        match self.inverse_transform() {
+            Some(inverse) => self.local_intersect(&ray.transform(&inverse)),
+            None => Vec::new(),
+        }
+    }
+
+    /// Intersect the shape with a ray and count the tests in `stats`.
+    ///
+    /// A shape counts as one intersection test. Shapes that contain
+    /// other shapes (e.g. groups) don't count themselves but each
+    /// child that is actually tested.
+    fn intersect_counted(&self, ray: &Ray, stats: &Stats) -> Vec<Intersection<'_, dyn Shape>> {
+        stats.add_intersection_tests(1);
+        self.intersect(ray)
+    }
+}
 
 /// A Sphere
 ///
 /// A sphere is always located at the origin with an radius of 1
 /// to ease the calculation of intersections with rays (this can
-/// be seen as kind of a "object space").
+/// be seen as kind of a "object space"). If preferred the radius
+/// can be set explicitly using the `set_radius` method.
 ///
 /// To adjust the location, size and rotations one can use
 /// transformations (those transformations can be seen as 
@@ -24,10 +171,20 @@ use crate::canvas::color::Color;
 /// A sphere has also a material assigned to it. When a sphere
 /// is created a default material is used but it can be changed
 /// using the `set_material` method.
-#[derive(Debug, PartialEq)]
+///
+/// The inverse of the transformation is cached, because it's
+/// needed for every intersection and normal calculation (see
+/// [`Shape::inverse_transform`]). Two
+/// spheres are equal if their radius, transformations and material
+/// are equal; the cached inverses aren't compared.
+#[derive(Debug)]
 pub struct Sphere {
     transform: Matrix,
+    inverse: Option<Matrix>, // cached inverse of the transformation
+    parent_transform: Matrix,
+    parent_inverse: Option<Matrix>, // cached inverse of the parent transformation
     material: Material,
+    radius: f64,
 }
 
 impl Sphere {
@@ -47,9 +204,39 @@ impl Sphere {
     ///                                  vec![0.0,0.0,1.0,0.0],
     ///                                  vec![0.0,0.0,0.0,1.0]]).unwrap(), *s.get_transform());
     /// assert_eq!(Material::default(), *s.get_material());
+    /// assert_eq!(1.0, s.radius());
     /// ```
     pub fn new() -> Self {
-        Self { transform: Matrix::identity(), material: Material::default() } 
+        Self {
+            transform: Matrix::identity(),
+            inverse: Some(Matrix::identity()),
+            parent_transform: Matrix::identity(),
+            parent_inverse: Some(Matrix::identity()),
+            material: Material::default(),
+            radius: 1.0,
+        }
+    }
+    
+    /// Return the radius of the sphere (in object space).
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+    
+    /// Set a sphere's radius.
+    ///
+    /// A sphere with radius `r` behaves like a unit sphere
+    /// scaled by `r`. The transformation is applied on top.
+    ///
+    /// # Arguments
+    ///
+    /// * `r` - The new radius
+    ///
+    /// # Panics
+    ///
+    /// Panics if `r` isn't a finite, positive number.
+    pub fn set_radius(&mut self, r: f64) {
+        assert!(r.is_finite() && r > 0.0, "the radius of a sphere must be finite and positive, got {}", r);
+        self.radius = r;
     }
     
     /// Return the assigned transfromation matrix.
@@ -76,6 +263,7 @@ impl Sphere {
     /// assert_eq!(translation(2.0, 3.0, 4.0), *s.get_transform());
     /// ```
     pub fn set_transform(&mut self, m: Matrix) {
+        self.inverse = m.inverse();
         self.transform = m;
     }
     
@@ -102,37 +290,198 @@ impl Sphere {
     ///
     /// * `world_p` - A point (in world space)
     pub fn normal_at(&self, world_p: Point) -> Vector {
-        // First the world point has to be translated into a object point by
-        // multiplying it with the inversed transfromation matrix.
-        // OP * TMATRIX = WP <=> WP * INV(TMATRIX) = OP
-        let object_point  = self.transform.inverse().unwrap().mul_point(&world_p);
+        // The world point is translated into a object point, the normal
+        // is calculated in object space and then translated back into world
+        // space (see `Shape::normal_at`).
+        Shape::normal_at(self, &world_p)
+    }
+
+    /// Intersect the sphere with a ray and also return the hit points in object space.
+    ///
+    /// Returns `(t, object_point)` pairs in ascending order of `t`. The object
+    /// space point is useful e.g. for texture mapping. Like
+    /// [`Shape::world_to_object`] the ray is converted into the space of the
+    /// sphere's parents first. A ray that misses the sphere (or a sphere with
+    /// a non-invertible transformation) yields an empty vector.
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - The ray (in world space)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     shapes::Sphere,
+    ///     ray::Ray,
+    ///     math::{point::Point, vector::Vector, matrix::transformation::translation},
+    /// };
+    ///
+    /// let mut s = Sphere::new();
+    /// s.set_transform(translation(0.0, 0.0, 5.0));
+    /// let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+    /// let hits = s.intersect_with_object_points(&r);
+    ///
+    /// assert_eq!((4.0, Point::new(0.0, 0.0, -1.0)), hits[0]);
+    /// assert_eq!((6.0, Point::new(0.0, 0.0, 1.0)), hits[1]);
+    /// ```
+    pub fn intersect_with_object_points(&self, ray: &Ray) -> Vec<(f64, Point)> {
+        let (parent_inverse, inverse) = match (self.parent_inverse.as_ref(), self.inverse.as_ref()) {
+            (Some(parent_inverse), Some(inverse)) => (parent_inverse, inverse),
+            _ => return Vec::new(),
+        };
+        let tray = ray.transform(parent_inverse).transform(inverse);
+
+        match self.local_ts(&tray) {
+            Some((t1, t2)) => vec![(t1, tray.position(t1)), (t2, tray.position(t2))],
+            None => Vec::new(),
+        }
+    }
+
+    /// Find the hit of a ray together with the hit point and the surface normal.
+    ///
+    /// Returns `(t, point, normal)` for the intersection with the lowest
+    /// nonnegative `t`, where `point` is the hit point and `normal` the
+    /// surface normal there (both in world space). This saves converting
+    /// the hit point back into object space to calculate the normal.
+    /// The transformations of the sphere's parents are taken into account,
+    /// i.e. the result matches [`Shape::normal_at`] for grouped spheres too.
+    /// Returns None if the ray doesn't hit the sphere.
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - The ray (in world space)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     shapes::Sphere,
+    ///     ray::Ray,
+    ///     math::{point::Point, vector::Vector},
+    /// };
+    ///
+    /// let s = Sphere::new();
+    /// let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+    /// let (t, point, normal) = s.hit_details(&r).unwrap();
+    ///
+    /// assert_eq!(4.0, t);
+    /// assert_eq!(Point::new(0.0, 0.0, -1.0), point);
+    /// assert_eq!(Vector::new(0.0, 0.0, -1.0), normal);
+    /// ```
+    pub fn hit_details(&self, ray: &Ray) -> Option<(f64, Point, Vector)> {
+        let (t, object_point) = self.intersect_with_object_points(ray)
+            .into_iter()
+            .find(|(t, _)| *t >= 0.0)?;
+
+        Some((t, ray.position(t), self.normal_to_world(&self.local_normal_at(&object_point))))
+    }
+
+    /// Find the `t` values where a ray (in object space) hits the sphere.
+    ///
+    /// Returns None if the ray misses the sphere.
+    pub(crate) fn local_ts(&self, ray: &Ray) -> Option<(f64, f64)> {
+        // We assume that every sphere has its origin at p(0,0,0).
+        let sphere_to_ray = *ray.origin() - Point::origin();
+
+        let a = ray.direction().dot(ray.direction());
+        let b = 2.0 * ray.direction().dot(&sphere_to_ray);
+        let c = sphere_to_ray.dot(&sphere_to_ray) - self.radius * self.radius;
+
+        let discriminant = b * b  - 4.0 * a * c;
+        
+        // If the discriminant is negative, then the ray misses
+        // and no intersections occure between the sphere and the ray.
+        if discriminant < 0.0 { return None; }
+
+        let t1 = (-b - discriminant.sqrt()) / (2.0 * a);
+        let t2 = (-b + discriminant.sqrt()) / (2.0 * a);
+
+        Some((t1, t2))
+    }
+}
+
+impl Shape for Sphere {
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn inverse_transform(&self) -> Option<Cow<'_, Matrix>> {
+        self.inverse.as_ref().map(Cow::Borrowed)
+    }
 
-        // Then we calculate the (surface normal) which is just the vector from the
-        // origin in object space (0, 0, 0) to the calculated object point.
-        let object_normal = object_point - Point::new(0.0, 0.0, 0.0);
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_, dyn Shape>> {
+        match self.local_ts(ray) {
+            Some((t1, t2)) => vec![Intersection::new(t1, self), Intersection::new(t2, self)],
+            None => Vec::new(),
+        }
+    }
+
+    fn local_normal_at(&self, point: &Point) -> Vector {
+        *point - Point::origin()
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let r = self.radius;
+        BoundingBox::new(Point::new(-r, -r, -r), Point::new(r, r, r))
+    }
+
+    fn parent_transform(&self) -> &Matrix {
+        &self.parent_transform
+    }
+
+    fn set_parent_transform(&mut self, m: Matrix) {
+        self.parent_inverse = m.inverse();
+        self.parent_transform = m;
+    }
+
+    fn world_to_object(&self, point: &Point) -> Point {
+        let parent_point = self.parent_inverse.as_ref().unwrap().mul_point(point);
+
+        self.inverse.as_ref().unwrap().mul_point(&parent_point)
+    }
+
+    fn normal_to_world(&self, normal: &Vector) -> Vector {
+        let mut n = self.inverse.as_ref().unwrap().transpose().mul_vec(normal);
+        n.norm();
+
+        let mut n = self.parent_inverse.as_ref().unwrap().transpose().mul_vec(&n);
+        n.norm();
+        n
+    }
+}
 
-        // Now this vector has to be translated from object space back to world space.
-        // We can't just multiply the vector by the transformation matrix or the normal
-        // won't be preserved! Instead we have to multiply it by the transposed, inversed
-        // transformation matrix.
-        let mut world_normal = self.transform.inverse().unwrap().transpose().mul_vec(&object_normal);
+impl PartialEq for Sphere {
+    fn eq(&self, other: &Self) -> bool {
+        // The cached inverses are derived from the transformations,
+        // so they don't take part in the comparison.
+        self.radius == other.radius &&
+        self.transform == other.transform &&
+        self.parent_transform == other.parent_transform &&
+        self.material == other.material
+    }
+}
 
-        world_normal.norm(); // normalize the resulting vector
-        world_normal
+impl Default for Sphere {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 #[cfg(test)]
 mod test {
     use crate::{
-        shapes::Sphere,
+        shapes::{Shape, Sphere, group::Group},
+        ray::{Ray, intersection::Intersections},
+        canvas::color::Color,
         math::{
             point::Point, 
             vector::Vector,
-            matrix::{
-                Matrix,
-                transformation::{translation, scaling, rotation_rad_z},
-            },
+            matrix::transformation::{translation, scaling, rotation_rad_y, rotation_rad_z},
         },
     };
 
@@ -167,11 +516,12 @@ mod test {
     #[test]
     fn the_normal_is_a_normalized_vector() {
         let s = Sphere::new();
-        let mut n = s.normal_at(Point::new(3.0_f64.sqrt()/3.0, 3.0_f64.sqrt()/3.0, 3.0_f64.sqrt()/3.0));
+        let n = s.normal_at(Point::new(3.0_f64.sqrt()/3.0, 3.0_f64.sqrt()/3.0, 3.0_f64.sqrt()/3.0));
         assert_eq!(n, n.norm_cpy());
     }
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn computing_the_normal_on_a_translated_sphere() {
         let mut s = Sphere::new();
         s.set_transform(translation(0.0, 1.0, 0.0));
@@ -187,6 +537,238 @@ mod test {
         let n = s.normal_at(Point::new(0.0, 2.0_f64.sqrt()/2.0, -2.0_f64.sqrt()/2.0));
         assert_eq!(Vector::new(0.0, 0.9701425001453319, -0.24253562503633294), n);
     }
-}
 
+    #[test]
+    fn a_sphere_with_a_radius_equals_a_scaled_unit_sphere() {
+        let mut s1 = Sphere::new();
+        s1.set_radius(2.0);
+        let mut s2 = Sphere::new();
+        s2.set_transform(scaling(2.0, 2.0, 2.0));
+
+        let rays = [
+            Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0)),
+            Ray::new(Point::new(1.0, 1.5, -5.0), Vector::new(0.0, 0.0, 1.0)),
+            Ray::new(Point::new(-3.0, 0.5, 0.0), Vector::new(1.0, 0.2, 0.1)),
+        ];
+
+        for r in rays.iter() {
+            let xs1 = r.intersect_sphere(&s1).unwrap();
+            let xs2 = r.intersect_sphere(&s2).unwrap();
+
+            assert!((xs1[0].t() - xs2[0].t()).abs() < 1e-10);
+            assert!((xs1[1].t() - xs2[1].t()).abs() < 1e-10);
+            assert_eq!(s1.normal_at(r.position(xs1[0].t())), s2.normal_at(r.position(xs2[0].t())));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn a_sphere_rejects_a_zero_radius() {
+        Sphere::new().set_radius(0.0);
+    }
 
+    #[test]
+    #[should_panic]
+    fn a_sphere_rejects_a_negative_radius() {
+        Sphere::new().set_radius(-1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn a_sphere_rejects_a_non_finite_radius() {
+        Sphere::new().set_radius(f64::NAN);
+    }
+
+    #[test]
+    fn a_ray_misses_a_sphere_outside_of_its_radius() {
+        let mut s = Sphere::new();
+        s.set_radius(2.0);
+        let r = Ray::new(Point::new(0.0, 2.5, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert!(r.intersect_sphere(&s).is_none());
+        assert!(s.intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn the_bounds_of_a_sphere_depend_on_its_radius() {
+        let mut s = Sphere::new();
+        s.set_radius(3.0);
+
+        assert_eq!(Point::new(-3.0, -3.0, -3.0), *s.bounds().min());
+        assert_eq!(Point::new(3.0, 3.0, 3.0), *s.bounds().max());
+    }
+
+    #[test]
+    fn spheres_compare_equal_regardless_of_how_the_inverse_was_computed() {
+        let r = rotation_rad_z(std::f64::consts::PI / 3.0);
+        let t = translation(1.0, -2.0, 3.0);
+        let sc = scaling(2.0, 0.5, 3.0);
+
+        let mut s1 = Sphere::new();
+        s1.set_transform(t.clone() * r.clone() * sc.clone());
+
+        // Same transformation, but the inverse is built from the inverses
+        // of the individual transformations (in reverse order).
+        let mut s2 = Sphere::new();
+        s2.set_transform(t.clone() * r.clone() * sc.clone());
+        s2.inverse = Some(sc.inverse().unwrap() * r.inverse().unwrap() * t.inverse().unwrap());
+
+        assert_eq!(s1, s2);
+    }
+
+    #[test]
+    fn spheres_with_different_materials_or_transforms_are_not_equal() {
+        let mut s1 = Sphere::new();
+        s1.set_material_color(Color::new(1.0, 0.0, 0.0));
+        let mut s2 = Sphere::new();
+        s2.set_transform(translation(0.0, 1.0, 0.0));
+
+        assert_ne!(Sphere::new(), s1);
+        assert_ne!(Sphere::new(), s2);
+        assert_eq!(Sphere::new(), Sphere::default());
+    }
+
+    #[test]
+    fn setting_the_transform_updates_the_cached_inverse() {
+        let mut s = Sphere::new();
+        s.set_transform(translation(2.0, 3.0, 4.0));
+
+        assert_eq!(translation(-2.0, -3.0, -4.0), *s.inverse_transform().unwrap());
+
+        s.set_transform(scaling(0.0, 1.0, 1.0));
+        assert!(s.inverse_transform().is_none());
+    }
+
+    #[test]
+    fn the_object_space_hit_points_lie_on_the_unit_sphere() {
+        let mut s = Sphere::new();
+        s.set_transform(translation(1.0, 2.0, 3.0) * rotation_rad_z(0.7) * scaling(2.0, 3.0, 0.5));
+        let r = Ray::new(Point::new(-5.0, 0.0, 0.0), (Point::new(1.0, 2.0, 3.0) - Point::new(-5.0, 0.0, 0.0)).norm_cpy());
+        let hits = s.intersect_with_object_points(&r);
+
+        assert_eq!(2, hits.len());
+        assert!(hits[0].0 < hits[1].0);
+        for (t, p) in hits.iter() {
+            assert!(((*p - Point::origin()).mag() - 1.0).abs() < 0.00001);
+            // The object space point is the world space hit point transformed back.
+            assert!(s.get_transform().mul_point(p).approx_eq(&r.position(*t), 0.00001));
+        }
+    }
+
+    #[test]
+    fn the_object_space_hit_points_of_a_grouped_sphere() {
+        let mut s = Sphere::new();
+        s.set_transform(scaling(2.0, 2.0, 2.0));
+        s.set_parent_transform(translation(0.0, 0.0, 5.0));
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        let hits = s.intersect_with_object_points(&r);
+
+        assert_eq!(2, hits.len());
+        assert_eq!((3.0, Point::new(0.0, 0.0, -1.0)), hits[0]);
+        assert_eq!((7.0, Point::new(0.0, 0.0, 1.0)), hits[1]);
+        for (t, p) in hits.iter() {
+            assert!(s.world_to_object(&r.position(*t)).approx_eq(p, 0.00001));
+        }
+    }
+
+    #[test]
+    fn a_missing_ray_has_no_object_space_hit_points() {
+        let s = Sphere::new();
+        let r = Ray::new(Point::new(0.0, 2.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert!(s.intersect_with_object_points(&r).is_empty());
+    }
+
+    #[test]
+    fn the_normal_of_a_validly_transformed_sphere_passes_the_unit_length_check() {
+        let mut s = Sphere::new();
+        s.set_transform(scaling(1.0, 0.001, 250.0) * rotation_rad_z(0.3) * translation(1.0, -2.0, 3.0));
+        let p = Point::new(1.0, 2.0, -3.0);
+
+        let n = Shape::normal_at(&s, &p);
+        assert!((n.mag() - 1.0).abs() < 0.00001);
+    }
+
+    #[test]
+    fn the_world_bounds_of_a_translated_sphere() {
+        let mut s = Sphere::new();
+        s.set_transform(translation(2.0, 0.0, 0.0));
+        let b = s.world_bounds();
+
+        assert_eq!(Point::new(1.0, -1.0, -1.0), *b.min());
+        assert_eq!(Point::new(3.0, 1.0, 1.0), *b.max());
+    }
+
+    #[test]
+    fn the_hit_details_match_the_manual_computation() {
+        let mut s = Sphere::new();
+        s.set_transform(translation(0.5, -0.3, 1.0) * scaling(1.0, 2.0, 0.5));
+        let origin = Point::new(0.0, 0.0, -5.0);
+
+        for target in [Point::new(0.0, 0.0, 1.0), Point::new(0.4, 0.8, 1.0), Point::new(-0.2, -1.5, 1.0)].iter() {
+            let r = Ray::new(origin, (*target - origin).norm_cpy());
+
+            // The sequence used in the sphere example
+            let xs = r.intersect_sphere(&s).unwrap();
+            let hit = xs.hit().unwrap();
+            let p = r.position(hit.t());
+            let n = hit.obj().normal_at(p);
+
+            let (t, point, normal) = s.hit_details(&r).unwrap();
+            assert!((hit.t() - t).abs() < 0.00001);
+            assert!(p.approx_eq(&point, 0.00001));
+            assert!(n.approx_eq(&normal, 0.00001));
+        }
+    }
+
+    #[test]
+    fn the_hit_details_of_a_grouped_sphere() {
+        let mut s = Sphere::new();
+        s.set_transform(translation(0.5, 0.0, 0.0) * scaling(1.0, 2.0, 0.5));
+        let mut g = Group::new();
+        g.set_transform(translation(0.0, 1.0, 3.0) * rotation_rad_y(0.4) * scaling(2.0, 2.0, 2.0));
+        g.add_child(Box::new(s));
+        let s = g.children()[0].as_ref();
+        let origin = Point::new(0.0, 0.0, -5.0);
+        let r = Ray::new(origin, (Point::new(0.3, 1.2, 3.0) - origin).norm_cpy());
+
+        let xs = Intersections::new(g.intersect(&r));
+        let hit = xs.hit().unwrap();
+        let p = r.position(hit.t());
+        let n = s.normal_at(&p);
+
+        // Compare with a copy of the child, which has the group's transformation as parent.
+        let mut c = Sphere::new();
+        c.set_transform(s.transform().clone());
+        c.set_parent_transform(s.parent_transform().clone());
+        let (t, point, normal) = c.hit_details(&r).unwrap();
+        assert!((hit.t() - t).abs() < 0.00001);
+        assert!(p.approx_eq(&point, 0.00001));
+        assert!(n.approx_eq(&normal, 0.00001));
+    }
+
+    #[test]
+    fn there_are_no_hit_details_without_a_hit() {
+        let s = Sphere::new();
+
+        assert!(s.hit_details(&Ray::new(Point::new(0.0, 2.0, -5.0), Vector::new(0.0, 0.0, 1.0))).is_none());
+        assert!(s.hit_details(&Ray::new(Point::new(0.0, 0.0, 5.0), Vector::new(0.0, 0.0, 1.0))).is_none());
+        // From inside the sphere only the intersection in front of the ray counts.
+        assert_eq!(1.0, s.hit_details(&Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0))).unwrap().0);
+    }
+
+    #[test]
+    fn a_sphere_intersects_with_its_cached_inverse() {
+        let mut s = Sphere::new();
+        s.set_transform(scaling(2.0, 2.0, 2.0));
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert!(matches!(s.inverse_transform(), Some(std::borrow::Cow::Borrowed(_))));
+        let ts: Vec<f64> = s.intersect(&r).iter().map(|i| i.t()).collect();
+        assert_eq!(vec![3.0, 7.0], ts);
+
+        s.set_transform(scaling(0.0, 1.0, 1.0));
+        assert!(s.inverse_transform().is_none());
+        assert!(s.intersect(&r).is_empty());
+    }
+}
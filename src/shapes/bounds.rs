@@ -0,0 +1,338 @@
+use crate::{
+    math::{
+        EPSILON,
+        matrix::Matrix,
+        point::Point,
+    },
+    ray::Ray,
+};
+
+/// An axis aligned bounding box (AABB).
+///
+/// The box is described by its minimum and maximum corner. A new
+/// box created by [`BoundingBox::empty`] doesn't contain any point
+/// until points or other boxes are added to it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    min: Point,
+    max: Point,
+}
+
+impl BoundingBox {
+    /// Create a new bounding box.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The corner with the smallest coordinates
+    /// * `max` - The corner with the largest coordinates
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{shapes::bounds::BoundingBox, math::point::Point};
+    ///
+    /// let b = BoundingBox::new(Point::new(-1.0, -2.0, -3.0), Point::new(3.0, 2.0, 1.0));
+    ///
+    /// assert_eq!(Point::new(-1.0, -2.0, -3.0), *b.min());
+    /// assert_eq!(Point::new(3.0, 2.0, 1.0), *b.max());
+    /// ```
+    pub fn new(min: Point, max: Point) -> Self {
+        Self { min, max }
+    }
+
+    /// Create an empty bounding box.
+    ///
+    /// The minimum is at positive and the maximum at negative infinity,
+    /// so that adding the first point makes the box contain just that point.
+    pub fn empty() -> Self {
+        Self {
+            min: Point::new(f64::INFINITY, f64::INFINITY, f64::INFINITY),
+            max: Point::new(f64::NEG_INFINITY, f64::NEG_INFINITY, f64::NEG_INFINITY),
+        }
+    }
+
+    /// Get the corner with the smallest coordinates.
+    pub fn min(&self) -> &Point {
+        &self.min
+    }
+
+    /// Get the corner with the largest coordinates.
+    pub fn max(&self) -> &Point {
+        &self.max
+    }
+
+    /// Grow the box so that it contains the given point.
+    pub fn add_point(&mut self, p: &Point) {
+        self.min = self.min.min(p);
+        self.max = self.max.max(p);
+    }
+
+    /// Grow the box so that it contains the other box.
+    pub fn merge(&mut self, other: &BoundingBox) {
+        self.add_point(&other.min);
+        self.add_point(&other.max);
+    }
+
+    /// Check if the given point lies within the box (including its surface).
+    pub fn contains_point(&self, p: &Point) -> bool {
+        self.min.x() <= p.x() && p.x() <= self.max.x() &&
+        self.min.y() <= p.y() && p.y() <= self.max.y() &&
+        self.min.z() <= p.z() && p.z() <= self.max.z()
+    }
+
+    /// Check if the other box lies completely within this box.
+    pub fn contains_box(&self, other: &BoundingBox) -> bool {
+        self.contains_point(&other.min) && self.contains_point(&other.max)
+    }
+
+    /// Split the box into two halves along its longest axis.
+    ///
+    /// Returns the `(left, right)` halves, i.e. the half with the smaller
+    /// and the half with the larger coordinates. If two axes are equally
+    /// long, x is preferred over y and y over z.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{shapes::bounds::BoundingBox, math::point::Point};
+    ///
+    /// let b = BoundingBox::new(Point::new(-1.0, -2.0, -3.0), Point::new(9.0, 5.5, 3.0));
+    /// let (left, right) = b.split();
+    ///
+    /// assert_eq!(Point::new(4.0, 5.5, 3.0), *left.max());
+    /// assert_eq!(Point::new(4.0, -2.0, -3.0), *right.min());
+    /// ```
+    pub fn split(&self) -> (BoundingBox, BoundingBox) {
+        let dx = self.max.x() - self.min.x();
+        let dy = self.max.y() - self.min.y();
+        let dz = self.max.z() - self.min.z();
+        let greatest = dx.max(dy).max(dz);
+
+        let (mut x0, mut y0, mut z0) = (self.min.x(), self.min.y(), self.min.z());
+        let (mut x1, mut y1, mut z1) = (self.max.x(), self.max.y(), self.max.z());
+
+        if greatest == dx {
+            x0 += dx / 2.0;
+            x1 = x0;
+        } else if greatest == dy {
+            y0 += dy / 2.0;
+            y1 = y0;
+        } else {
+            z0 += dz / 2.0;
+            z1 = z0;
+        }
+
+        let mid_min = Point::new(x0, y0, z0);
+        let mid_max = Point::new(x1, y1, z1);
+
+        (BoundingBox::new(self.min, mid_max), BoundingBox::new(mid_min, self.max))
+    }
+
+    /// Transform the box by the given matrix.
+    ///
+    /// All eight corners are transformed and the result is the
+    /// axis aligned box that contains all of them.
+    pub fn transform(&self, m: &Matrix) -> BoundingBox {
+        let corners = [
+            self.min,
+            Point::new(self.min.x(), self.min.y(), self.max.z()),
+            Point::new(self.min.x(), self.max.y(), self.min.z()),
+            Point::new(self.min.x(), self.max.y(), self.max.z()),
+            Point::new(self.max.x(), self.min.y(), self.min.z()),
+            Point::new(self.max.x(), self.min.y(), self.max.z()),
+            Point::new(self.max.x(), self.max.y(), self.min.z()),
+            self.max,
+        ];
+
+        let mut b = BoundingBox::empty();
+        for c in m.apply_to_points(&corners).iter() {
+            b.add_point(c);
+        }
+        b
+    }
+
+    /// Check if the given ray intersects the box.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     shapes::bounds::BoundingBox,
+    ///     ray::Ray,
+    ///     math::{point::Point, vector::Vector},
+    /// };
+    ///
+    /// let b = BoundingBox::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0));
+    ///
+    /// assert!(b.intersects(&Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0))));
+    /// assert!(!b.intersects(&Ray::new(Point::new(2.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0))));
+    /// ```
+    pub fn intersects(&self, ray: &Ray) -> bool {
+        let (o, d) = (ray.origin(), ray.direction());
+        let (xtmin, xtmax) = check_axis(o.x(), d.x(), self.min.x(), self.max.x());
+        let (ytmin, ytmax) = check_axis(o.y(), d.y(), self.min.y(), self.max.y());
+        let (ztmin, ztmax) = check_axis(o.z(), d.z(), self.min.z(), self.max.z());
+
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+
+        tmin <= tmax
+    }
+}
+
+impl Default for BoundingBox {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+/// Find the `t` values where the ray enters and leaves the slab
+/// between `min` and `max` on a single axis.
+fn check_axis(origin: f64, direction: f64, min: f64, max: f64) -> (f64, f64) {
+    let tmin_numerator = min - origin;
+    let tmax_numerator = max - origin;
+
+    let (tmin, tmax) = if direction.abs() >= EPSILON {
+        (tmin_numerator / direction, tmax_numerator / direction)
+    } else {
+        (tmin_numerator * f64::INFINITY, tmax_numerator * f64::INFINITY)
+    };
+
+    if tmin > tmax { (tmax, tmin) } else { (tmin, tmax) }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        shapes::bounds::BoundingBox,
+        ray::Ray,
+        math::{
+            point::Point,
+            vector::Vector,
+            matrix::transformation::*,
+        },
+    };
+
+    #[test]
+    fn adding_points_to_an_empty_bounding_box() {
+        let mut b = BoundingBox::empty();
+        b.add_point(&Point::new(-5.0, 2.0, 0.0));
+        b.add_point(&Point::new(7.0, 0.0, -3.0));
+
+        assert_eq!(Point::new(-5.0, 0.0, -3.0), *b.min());
+        assert_eq!(Point::new(7.0, 2.0, 0.0), *b.max());
+    }
+
+    #[test]
+    fn merging_one_bounding_box_into_another() {
+        let mut b1 = BoundingBox::new(Point::new(-5.0, -2.0, 0.0), Point::new(7.0, 4.0, 4.0));
+        let b2 = BoundingBox::new(Point::new(8.0, -7.0, -2.0), Point::new(14.0, 2.0, 8.0));
+        b1.merge(&b2);
+
+        assert_eq!(Point::new(-5.0, -7.0, -2.0), *b1.min());
+        assert_eq!(Point::new(14.0, 4.0, 8.0), *b1.max());
+    }
+
+    #[test]
+    fn checking_if_a_box_contains_a_point() {
+        let b = BoundingBox::new(Point::new(5.0, -2.0, 0.0), Point::new(11.0, 4.0, 7.0));
+
+        assert!(b.contains_point(&Point::new(5.0, -2.0, 0.0)));
+        assert!(b.contains_point(&Point::new(8.0, 1.0, 3.0)));
+        assert!(!b.contains_point(&Point::new(3.0, 0.0, 3.0)));
+        assert!(!b.contains_point(&Point::new(8.0, 1.0, 8.0)));
+    }
+
+    #[test]
+    fn transforming_a_bounding_box() {
+        let b = BoundingBox::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0));
+        let t = rotation_rad_x(std::f64::consts::PI / 4.0) * rotation_rad_y(std::f64::consts::PI / 4.0);
+        let b2 = b.transform(&t);
+
+        assert!((b2.min().x() + 2.0_f64.sqrt()).abs() < 0.0001);
+        assert!((b2.min().y() + 1.70710).abs() < 0.0001);
+        assert!((b2.min().z() + 1.70710).abs() < 0.0001);
+        assert!((b2.max().x() - 2.0_f64.sqrt()).abs() < 0.0001);
+        assert!((b2.max().y() - 1.70710).abs() < 0.0001);
+        assert!((b2.max().z() - 1.70710).abs() < 0.0001);
+    }
+
+    #[test]
+    fn intersecting_a_ray_with_a_bounding_box() {
+        let b = BoundingBox::new(Point::new(5.0, -2.0, 0.0), Point::new(11.0, 4.0, 7.0));
+        let cases = [
+            (Point::new(15.0, 1.0, 2.0), Vector::new(-1.0, 0.0, 0.0), true),
+            (Point::new(-5.0, -1.0, 4.0), Vector::new(1.0, 0.0, 0.0), true),
+            (Point::new(7.0, 6.0, 5.0), Vector::new(0.0, -1.0, 0.0), true),
+            (Point::new(9.0, -5.0, 6.0), Vector::new(0.0, 1.0, 0.0), true),
+            (Point::new(8.0, 2.0, 12.0), Vector::new(0.0, 0.0, -1.0), true),
+            (Point::new(6.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0), true),
+            (Point::new(8.0, 1.0, 3.5), Vector::new(0.0, 0.0, 1.0), true),
+            (Point::new(9.0, -1.0, -8.0), Vector::new(2.0, 4.0, 6.0), false),
+            (Point::new(8.0, 3.0, -4.0), Vector::new(6.0, 2.0, 4.0), false),
+            (Point::new(9.0, -1.0, -2.0), Vector::new(4.0, 6.0, 2.0), false),
+            (Point::new(4.0, 0.0, 9.0), Vector::new(0.0, 0.0, -1.0), false),
+            (Point::new(8.0, 6.0, -1.0), Vector::new(0.0, -1.0, 0.0), false),
+            (Point::new(12.0, 5.0, 4.0), Vector::new(-1.0, 0.0, 0.0), false),
+        ];
+
+        for (origin, direction, result) in cases.iter() {
+            let r = Ray::new(*origin, direction.norm_cpy());
+            assert_eq!(*result, b.intersects(&r), "{:?}", r);
+        }
+    }
+
+    #[test]
+    fn checking_if_a_box_contains_another_box() {
+        let b = BoundingBox::new(Point::new(5.0, -2.0, 0.0), Point::new(11.0, 4.0, 7.0));
+
+        assert!(b.contains_box(&BoundingBox::new(Point::new(5.0, -2.0, 0.0), Point::new(11.0, 4.0, 7.0))));
+        assert!(b.contains_box(&BoundingBox::new(Point::new(6.0, -1.0, 1.0), Point::new(10.0, 3.0, 6.0))));
+        assert!(!b.contains_box(&BoundingBox::new(Point::new(4.0, -3.0, -1.0), Point::new(10.0, 3.0, 6.0))));
+        assert!(!b.contains_box(&BoundingBox::new(Point::new(6.0, -1.0, 1.0), Point::new(12.0, 5.0, 8.0))));
+    }
+
+    #[test]
+    fn splitting_a_perfect_cube() {
+        let b = BoundingBox::new(Point::new(-1.0, -4.0, -5.0), Point::new(9.0, 6.0, 5.0));
+        let (left, right) = b.split();
+
+        assert_eq!(Point::new(-1.0, -4.0, -5.0), *left.min());
+        assert_eq!(Point::new(4.0, 6.0, 5.0), *left.max());
+        assert_eq!(Point::new(4.0, -4.0, -5.0), *right.min());
+        assert_eq!(Point::new(9.0, 6.0, 5.0), *right.max());
+    }
+
+    #[test]
+    fn splitting_an_x_wide_box() {
+        let b = BoundingBox::new(Point::new(-1.0, -2.0, -3.0), Point::new(9.0, 5.5, 3.0));
+        let (left, right) = b.split();
+
+        assert_eq!(Point::new(-1.0, -2.0, -3.0), *left.min());
+        assert_eq!(Point::new(4.0, 5.5, 3.0), *left.max());
+        assert_eq!(Point::new(4.0, -2.0, -3.0), *right.min());
+        assert_eq!(Point::new(9.0, 5.5, 3.0), *right.max());
+    }
+
+    #[test]
+    fn splitting_a_y_wide_box() {
+        let b = BoundingBox::new(Point::new(-1.0, -2.0, -3.0), Point::new(5.0, 8.0, 3.0));
+        let (left, right) = b.split();
+
+        assert_eq!(Point::new(-1.0, -2.0, -3.0), *left.min());
+        assert_eq!(Point::new(5.0, 3.0, 3.0), *left.max());
+        assert_eq!(Point::new(-1.0, 3.0, -3.0), *right.min());
+        assert_eq!(Point::new(5.0, 8.0, 3.0), *right.max());
+    }
+
+    #[test]
+    fn splitting_a_z_wide_box() {
+        let b = BoundingBox::new(Point::new(-1.0, -2.0, -3.0), Point::new(5.0, 3.0, 7.0));
+        let (left, right) = b.split();
+
+        assert_eq!(Point::new(-1.0, -2.0, -3.0), *left.min());
+        assert_eq!(Point::new(5.0, 3.0, 2.0), *left.max());
+        assert_eq!(Point::new(-1.0, -2.0, 2.0), *right.min());
+        assert_eq!(Point::new(5.0, 3.0, 7.0), *right.max());
+    }
+}
@@ -0,0 +1,555 @@
+use crate::{
+    shapes::{
+        Shape,
+        bounds::BoundingBox,
+    },
+    materials::Material,
+    ray::{
+        Ray,
+        intersection::Intersection,
+    },
+    math::{
+        matrix::Matrix,
+        point::Point,
+        vector::Vector,
+    },
+    world::stats::Stats,
+};
+
+/// The children of a group.
+type Children = Vec<Box<dyn Shape>>;
+
+/// A Group is a collection of shapes that are transformed as a unit.
+///
+/// The transformation of a group is applied to all of its children,
+/// i.e. a ray is first converted into the group's object space and
+/// then into the object space of each child.
+///
+/// A group keeps track of the bounding box of all its children. A ray
+/// that misses this box can't hit any child, so the children aren't
+/// tested at all.
+///
+/// Every child knows the combined transformation of the groups it's
+/// part of (see [`Shape::parent_transform`]), so normals of nested
+/// children can be converted into world space.
+#[derive(Debug)]
+pub struct Group {
+    transform: Matrix,
+    parent_transform: Matrix,
+    material: Material,
+    children: Children,
+    bounds: BoundingBox,
+}
+
+impl Group {
+    /// Create a new, empty Group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{shapes::group::Group, math::matrix::Matrix};
+    ///
+    /// let g = Group::new();
+    ///
+    /// assert!(g.children().is_empty());
+    /// assert_eq!(Matrix::identity(), *g.get_transform());
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            transform: Matrix::identity(),
+            parent_transform: Matrix::identity(),
+            material: Material::default(),
+            children: Vec::new(),
+            bounds: BoundingBox::empty(),
+        }
+    }
+
+    /// Get the transformation matrix of the group.
+    pub fn get_transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    /// Assign a new transformation matrix to the group.
+    pub fn set_transform(&mut self, m: Matrix) {
+        self.transform = m;
+        self.update_children();
+    }
+
+    /// Get the children of the group.
+    pub fn children(&self) -> &Vec<Box<dyn Shape>> {
+        &self.children
+    }
+
+    /// Add a shape to the group.
+    ///
+    /// Returns a mutable reference to the group so that
+    /// calls can be chained.
+    ///
+    /// # Arguments
+    ///
+    /// * `child` - The shape to add
+    pub fn add_child(&mut self, mut child: Box<dyn Shape>) -> &mut Self {
+        self.bounds.merge(&child.bounds().transform(child.transform()));
+        child.set_parent_transform(self.children_parent_transform());
+        self.children.push(child);
+        self
+    }
+
+    /// Move the children that fit completely into the left or right half
+    /// of the group's bounds out of the group.
+    ///
+    /// Returns the `(left, right)` children, all other children stay
+    /// in the group. The bounds of the group don't change.
+    fn partition_children(&mut self) -> (Children, Children) {
+        let (left_bounds, right_bounds) = self.bounds.split();
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+
+        for child in std::mem::take(&mut self.children) {
+            let b = child.bounds().transform(child.transform());
+
+            if left_bounds.contains_box(&b) {
+                left.push(child);
+            } else if right_bounds.contains_box(&b) {
+                right.push(child);
+            } else {
+                self.children.push(child);
+            }
+        }
+
+        (left, right)
+    }
+
+    /// Add a new group containing the given children to the group.
+    fn make_subgroup(&mut self, children: Children) {
+        let mut sub = Group::new();
+        for child in children {
+            sub.add_child(child);
+        }
+        self.add_child(Box::new(sub));
+    }
+
+    /// The transformation from the object space of the group into world space.
+    fn children_parent_transform(&self) -> Matrix {
+        self.parent_transform.clone() * self.transform.clone()
+    }
+
+    /// Intersect the children with a ray given in the group's object space.
+    ///
+    /// If `stats` is given, the tests of the children are counted.
+    fn intersect_children(&self, ray: &Ray, stats: Option<&Stats>) -> Vec<Intersection<'_, dyn Shape>> {
+        // Skip all children if the ray doesn't even hit the group's bounds.
+        if !self.bounds.intersects(ray) {
+            return Vec::new();
+        }
+
+        self.children.iter()
+            .flat_map(|child| match stats {
+                Some(stats) => child.intersect_counted(ray, stats),
+                None => child.intersect(ray),
+            })
+            .collect()
+    }
+
+    /// Pass the changed transformation on to all children.
+    fn update_children(&mut self) {
+        let m = self.children_parent_transform();
+        for child in self.children.iter_mut() {
+            child.set_parent_transform(m.clone());
+        }
+    }
+}
+
+impl Default for Group {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shape for Group {
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_, dyn Shape>> {
+        self.intersect_children(ray, None)
+    }
+
+    fn intersect_counted(&self, ray: &Ray, stats: &Stats) -> Vec<Intersection<'_, dyn Shape>> {
+        match self.inverse_transform() {
+            Some(inverse) => self.intersect_children(&ray.transform(&inverse), Some(stats)),
+            None => Vec::new(),
+        }
+    }
+
+    fn local_normal_at(&self, _point: &Point) -> Vector {
+        unreachable!("a group has no surface, only its children have normals");
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.bounds
+    }
+
+    fn parent_transform(&self) -> &Matrix {
+        &self.parent_transform
+    }
+
+    fn set_parent_transform(&mut self, m: Matrix) {
+        self.parent_transform = m;
+        self.update_children();
+    }
+
+    /// Partition the children into nested subgroups.
+    ///
+    /// If the group has at least `threshold` children, the ones that
+    /// fit completely into one half of the group's bounds (split along
+    /// the longest axis) are moved into a new subgroup per half. Then
+    /// all children are subdivided recursively. A ray that misses the
+    /// bounds of a subgroup doesn't have to be tested against its children.
+    fn divide(&mut self, threshold: usize) {
+        let n = self.children.len();
+
+        if threshold <= n {
+            let (left, right) = self.partition_children();
+
+            if left.len() == n || right.len() == n {
+                // All children fit into the same half (e.g. they have empty
+                // bounds), so a subgroup wouldn't separate anything.
+                self.children = if left.is_empty() { right } else { left };
+            } else {
+                if !left.is_empty() {
+                    self.make_subgroup(left);
+                }
+                if !right.is_empty() {
+                    self.make_subgroup(right);
+                }
+            }
+        }
+
+        for child in self.children.iter_mut() {
+            child.divide(threshold);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        shapes::{
+            Shape,
+            Sphere,
+            bounds::BoundingBox,
+            group::Group,
+        },
+        materials::Material,
+        ray::{
+            Ray,
+            intersection::{Intersection, Intersections},
+        },
+        math::{
+            point::Point,
+            vector::Vector,
+            matrix::{Matrix, transformation::*},
+        },
+    };
+
+    use std::{cell::Cell, rc::Rc};
+
+    // A unit cube sized shape that counts how often it was intersected.
+    #[derive(Debug)]
+    struct CountingShape {
+        transform: Matrix,
+        parent_transform: Matrix,
+        material: Material,
+        count: Rc<Cell<usize>>,
+    }
+
+    impl Shape for CountingShape {
+        fn transform(&self) -> &Matrix {
+            &self.transform
+        }
+
+        fn material(&self) -> &Material {
+            &self.material
+        }
+
+        fn local_intersect(&self, _ray: &Ray) -> Vec<Intersection<'_, dyn Shape>> {
+            self.count.set(self.count.get() + 1);
+            Vec::new()
+        }
+
+        fn local_normal_at(&self, point: &Point) -> Vector {
+            *point - Point::new(0.0, 0.0, 0.0)
+        }
+
+        fn bounds(&self) -> BoundingBox {
+            BoundingBox::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0))
+        }
+
+        fn parent_transform(&self) -> &Matrix {
+            &self.parent_transform
+        }
+
+        fn set_parent_transform(&mut self, m: Matrix) {
+            self.parent_transform = m;
+        }
+    }
+
+    fn counting_shape(transform: Matrix) -> (Box<dyn Shape>, Rc<Cell<usize>>) {
+        let count = Rc::new(Cell::new(0));
+        let shape = CountingShape {
+            transform,
+            parent_transform: Matrix::identity(),
+            material: Material::default(),
+            count: count.clone(),
+        };
+        (Box::new(shape), count)
+    }
+
+    #[test]
+    fn intersecting_a_ray_with_an_empty_group() {
+        let g = Group::new();
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert!(g.local_intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn intersecting_a_ray_with_a_nonempty_group() {
+        let s1 = Sphere::new();
+        let mut s2 = Sphere::new();
+        s2.set_transform(translation(0.0, 0.0, -3.0));
+        let mut s3 = Sphere::new();
+        s3.set_transform(translation(5.0, 0.0, 0.0));
+        let mut g = Group::new();
+        g.add_child(Box::new(s1))
+         .add_child(Box::new(s2))
+         .add_child(Box::new(s3));
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = Intersections::new(g.local_intersect(&r));
+
+        assert_eq!(4, xs.len());
+        assert!(xs[0].is_same_object(g.children()[1].as_ref()));
+        assert!(xs[1].is_same_object(g.children()[1].as_ref()));
+        assert!(xs[2].is_same_object(g.children()[0].as_ref()));
+        assert!(xs[3].is_same_object(g.children()[0].as_ref()));
+    }
+
+    #[test]
+    fn intersecting_a_transformed_group() {
+        let mut s = Sphere::new();
+        s.set_transform(translation(5.0, 0.0, 0.0));
+        let mut g = Group::new();
+        g.set_transform(scaling(2.0, 2.0, 2.0));
+        g.add_child(Box::new(s));
+        let r = Ray::new(Point::new(10.0, 0.0, -10.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert_eq!(2, g.intersect(&r).len());
+    }
+
+    #[test]
+    fn a_group_has_a_bounding_box_that_contains_its_children() {
+        let mut s = Sphere::new();
+        s.set_transform(translation(2.0, 5.0, -3.0) * scaling(2.0, 2.0, 2.0));
+        let mut g = Group::new();
+        g.add_child(Box::new(s));
+
+        assert_eq!(Point::new(0.0, 3.0, -5.0), *g.bounds().min());
+        assert_eq!(Point::new(4.0, 7.0, -1.0), *g.bounds().max());
+    }
+
+    #[test]
+    fn a_ray_missing_the_bounds_doesnt_test_the_children() {
+        let (child, count) = counting_shape(Matrix::identity());
+        let mut g = Group::new();
+        g.add_child(child);
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 1.0, 0.0));
+
+        assert!(g.intersect(&r).is_empty());
+        assert_eq!(0, count.get());
+    }
+
+    #[test]
+    fn a_ray_hitting_the_bounds_tests_the_children() {
+        let (child, count) = counting_shape(Matrix::identity());
+        let mut g = Group::new();
+        g.add_child(child);
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        g.intersect(&r);
+        assert_eq!(1, count.get());
+    }
+
+    // g1 (rotated) contains g2 (scaled) contains a translated sphere.
+    fn nested_groups(g2_transform: Matrix) -> Group {
+        let mut s = Sphere::new();
+        s.set_transform(translation(5.0, 0.0, 0.0));
+        let mut g2 = Group::new();
+        g2.set_transform(g2_transform);
+        g2.add_child(Box::new(s));
+        let mut g1 = Group::new();
+        g1.set_transform(rotation_rad_y(std::f64::consts::PI / 2.0));
+        g1.add_child(Box::new(g2));
+        g1
+    }
+
+    fn assert_vector_eq(expected: Vector, actual: Vector) {
+        assert!((expected - actual).mag() < 0.0001, "expected {:?} but got {:?}", expected, actual);
+    }
+
+    #[test]
+    fn converting_a_point_from_world_to_object_space() {
+        let g1 = nested_groups(scaling(2.0, 2.0, 2.0));
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, -1.0));
+        let xs = g1.intersect(&r);
+        let s = xs[0].obj();
+
+        let p = s.world_to_object(&Point::new(-2.0, 0.0, -10.0));
+        assert!(Point::new(0.0, 0.0, -1.0).distance(&p) < 0.0001);
+    }
+
+    #[test]
+    fn converting_a_normal_from_object_to_world_space() {
+        let g1 = nested_groups(scaling(1.0, 2.0, 3.0));
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, -1.0));
+        let xs = g1.intersect(&r);
+        let s = xs[0].obj();
+
+        let v = 3.0_f64.sqrt() / 3.0;
+        let n = s.normal_to_world(&Vector::new(v, v, v));
+        assert_vector_eq(Vector::new(0.2857, 0.4286, -0.8571), n);
+    }
+
+    #[test]
+    fn finding_the_normal_on_a_child_object() {
+        let g1 = nested_groups(scaling(1.0, 2.0, 3.0));
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, -1.0));
+        let xs = g1.intersect(&r);
+        let s = xs[0].obj();
+
+        let n = s.normal_at(&Point::new(1.7321, 1.1547, -5.5774));
+        assert_vector_eq(Vector::new(0.2857, 0.4286, -0.8571), n);
+    }
+
+    #[test]
+    fn changing_the_transform_of_a_group_updates_its_children() {
+        let mut g = Group::new();
+        g.add_child(Box::new(Sphere::new()));
+        g.set_transform(translation(0.0, 3.0, 0.0));
+
+        assert_eq!(translation(0.0, 3.0, 0.0), *g.children()[0].parent_transform());
+        assert_eq!(Point::new(0.0, 0.0, 0.0), g.children()[0].world_to_object(&Point::new(0.0, 3.0, 0.0)));
+    }
+
+    #[test]
+    fn partitioning_a_groups_children() {
+        let mut s1 = Sphere::new();
+        s1.set_transform(translation(-2.0, 0.0, 0.0));
+        let mut s2 = Sphere::new();
+        s2.set_transform(translation(2.0, 0.0, 0.0));
+        let s3 = Sphere::new();
+        let mut g = Group::new();
+        g.add_child(Box::new(s1))
+         .add_child(Box::new(s2))
+         .add_child(Box::new(s3));
+        let (left, right) = g.partition_children();
+
+        assert_eq!(1, g.children().len());
+        assert_eq!(Matrix::identity(), *g.children()[0].transform());
+        assert_eq!(1, left.len());
+        assert_eq!(translation(-2.0, 0.0, 0.0), *left[0].transform());
+        assert_eq!(1, right.len());
+        assert_eq!(translation(2.0, 0.0, 0.0), *right[0].transform());
+    }
+
+    #[test]
+    fn creating_a_subgroup_from_a_list_of_children() {
+        let mut s1 = Sphere::new();
+        s1.set_transform(translation(-2.0, 0.0, 0.0));
+        let s2 = Sphere::new();
+        let mut g = Group::new();
+        g.set_transform(translation(0.0, 5.0, 0.0));
+        g.make_subgroup(vec![Box::new(s1), Box::new(s2)]);
+
+        assert_eq!(1, g.children().len());
+        let sub = &g.children()[0];
+        assert_eq!(Point::new(-3.0, -1.0, -1.0), *sub.bounds().min());
+        assert_eq!(Point::new(1.0, 1.0, 1.0), *sub.bounds().max());
+        assert_eq!(translation(0.0, 5.0, 0.0), *sub.parent_transform());
+    }
+
+    #[test]
+    fn subdividing_a_primitive_does_nothing() {
+        let mut s = Sphere::new();
+        s.divide(1);
+
+        assert_eq!(Sphere::new(), s);
+    }
+
+    #[test]
+    fn subdividing_a_group_partitions_its_children() {
+        let (s1, count1) = counting_shape(translation(-2.0, -2.0, 0.0));
+        let (s2, count2) = counting_shape(translation(-2.0, 2.0, 0.0));
+        let (s3, count3) = counting_shape(scaling(4.0, 4.0, 4.0));
+        let mut g = Group::new();
+        g.add_child(s1)
+         .add_child(s2)
+         .add_child(s3);
+        g.divide(1);
+
+        assert_eq!(2, g.children().len());
+        assert_eq!(scaling(4.0, 4.0, 4.0), *g.children()[0].transform());
+        let sub = &g.children()[1];
+        assert_eq!(Point::new(-3.0, -3.0, -1.0), *sub.bounds().min());
+        assert_eq!(Point::new(-1.0, 3.0, 1.0), *sub.bounds().max());
+
+        // s1 and s2 ended up in separate subgroups, so a ray that only
+        // passes s1 doesn't test s2 anymore.
+        let r = Ray::new(Point::new(-2.0, -2.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        g.intersect(&r);
+        assert_eq!(1, count1.get());
+        assert_eq!(0, count2.get());
+        assert_eq!(1, count3.get());
+    }
+
+    #[test]
+    fn subdividing_a_group_with_too_few_children() {
+        let (s1, _) = counting_shape(translation(-2.0, 0.0, 0.0));
+        let (s2, _) = counting_shape(translation(2.0, 1.0, 0.0));
+        let (s3, _) = counting_shape(translation(2.0, -1.0, 0.0));
+        let (s4, _) = counting_shape(Matrix::identity());
+        let mut sub = Group::new();
+        sub.add_child(s1)
+           .add_child(s2)
+           .add_child(s3);
+        let mut g = Group::new();
+        g.add_child(Box::new(sub))
+         .add_child(s4);
+        g.divide(3);
+
+        // g itself has less than 3 children and stays as it is, but
+        // its subgroup is divided into [s1] and [s2, s3].
+        assert_eq!(2, g.children().len());
+        assert_eq!(Matrix::identity(), *g.children()[1].transform());
+        let sub = &g.children()[0];
+        assert_eq!(Point::new(-3.0, -2.0, -1.0), *sub.bounds().min());
+        assert_eq!(Point::new(3.0, 2.0, 1.0), *sub.bounds().max());
+
+        let r = Ray::new(Point::new(-2.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert!(g.intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn subdividing_children_with_empty_bounds_terminates() {
+        let mut g = Group::new();
+        g.add_child(Box::new(Group::new()))
+         .add_child(Box::new(Group::new()));
+        g.divide(1);
+
+        assert_eq!(2, g.children().len());
+    }
+}
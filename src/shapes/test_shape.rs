@@ -0,0 +1,152 @@
+use crate::{
+    shapes::{
+        Shape,
+        bounds::BoundingBox,
+    },
+    materials::Material,
+    ray::{
+        Ray,
+        intersection::Intersection,
+    },
+    math::{
+        matrix::Matrix,
+        point::Point,
+        vector::Vector,
+    },
+};
+
+use std::cell::RefCell;
+
+/// A shape that is only used to test the [`Shape`] abstraction.
+///
+/// It never reports an intersection but remembers the (object space)
+/// ray it was last intersected with, so tests can check that the
+/// ray was transformed correctly.
+#[derive(Debug)]
+pub(crate) struct TestShape {
+    transform: Matrix,
+    parent_transform: Matrix,
+    material: Material,
+    saved_ray: RefCell<Option<Ray>>,
+}
+
+impl TestShape {
+    pub(crate) fn new() -> Self {
+        Self {
+            transform: Matrix::identity(),
+            parent_transform: Matrix::identity(),
+            material: Material::default(),
+            saved_ray: RefCell::new(None),
+        }
+    }
+
+    pub(crate) fn set_transform(&mut self, m: Matrix) {
+        self.transform = m;
+    }
+
+    /// Get the ray passed to the last call of `local_intersect`.
+    pub(crate) fn saved_ray(&self) -> Option<Ray> {
+        self.saved_ray.borrow().as_ref().map(|r| Ray::new(*r.origin(), *r.direction()))
+    }
+}
+
+impl Shape for TestShape {
+    fn transform(&self) -> &Matrix {
+        &self.transform
+    }
+
+    fn material(&self) -> &Material {
+        &self.material
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Vec<Intersection<'_, dyn Shape>> {
+        *self.saved_ray.borrow_mut() = Some(Ray::new(*ray.origin(), *ray.direction()));
+        Vec::new()
+    }
+
+    fn local_normal_at(&self, point: &Point) -> Vector {
+        Vector::new(point.x(), point.y(), point.z())
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0))
+    }
+
+    fn parent_transform(&self) -> &Matrix {
+        &self.parent_transform
+    }
+
+    fn set_parent_transform(&mut self, m: Matrix) {
+        self.parent_transform = m;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        shapes::{
+            Shape,
+            test_shape::TestShape,
+        },
+        materials::Material,
+        ray::Ray,
+        math::{
+            point::Point,
+            vector::Vector,
+            matrix::{Matrix, transformation::*},
+        },
+    };
+
+    #[test]
+    fn the_default_transformation_and_material() {
+        let s = TestShape::new();
+
+        assert_eq!(Matrix::identity(), *s.transform());
+        assert_eq!(Material::default(), *s.material());
+        assert!(s.saved_ray().is_none());
+    }
+
+    #[test]
+    fn intersecting_a_scaled_shape_with_a_ray() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut s = TestShape::new();
+        s.set_transform(scaling(2.0, 2.0, 2.0));
+        s.intersect(&r);
+        let saved = s.saved_ray().unwrap();
+
+        assert_eq!(Point::new(0.0, 0.0, -2.5), *saved.origin());
+        assert_eq!(Vector::new(0.0, 0.0, 0.5), *saved.direction());
+    }
+
+    #[test]
+    fn intersecting_a_translated_shape_with_a_ray() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut s = TestShape::new();
+        s.set_transform(translation(5.0, 0.0, 0.0));
+        s.intersect(&r);
+        let saved = s.saved_ray().unwrap();
+
+        assert_eq!(Point::new(-5.0, 0.0, -5.0), *saved.origin());
+        assert_eq!(Vector::new(0.0, 0.0, 1.0), *saved.direction());
+    }
+
+    #[test]
+    fn a_shape_with_a_singular_transformation_is_never_intersected() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut s = TestShape::new();
+        s.set_transform(scaling(0.0, 1.0, 1.0));
+
+        assert!(s.intersect(&r).is_empty());
+        assert!(s.saved_ray().is_none());
+    }
+
+    // The local normal at the origin is the zero vector which can't be normalized.
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "isn't of unit length")]
+    fn a_degenerate_normal_panics_in_debug_builds() {
+        let s = TestShape::new();
+
+        s.normal_at(&Point::new(0.0, 0.0, 0.0));
+    }
+}
@@ -0,0 +1,96 @@
+/// A small, seedable pseudo-random number generator (xorshift64*).
+///
+/// The generator isn't suitable for cryptography but fast and good
+/// enough for sampling (anti-aliasing, soft shadows, noise). Two
+/// generators created with the same seed produce the same sequence,
+/// which keeps renders reproducible.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Create a new generator from a seed.
+    ///
+    /// Every seed (including 0) is valid. The seed is scrambled
+    /// first, so similar seeds still yield unrelated sequences.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed of the generator
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::util::rng::Rng;
+    ///
+    /// let mut a = Rng::new(42);
+    /// let mut b = Rng::new(42);
+    ///
+    /// assert_eq!(a.next_f64(), b.next_f64());
+    /// ```
+    pub fn new(seed: u64) -> Self {
+        // SplitMix64 finalizer
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        // xorshift gets stuck at a state of 0.
+        Self { state: if z == 0 { 0x9E37_79B9_7F4A_7C15 } else { z } }
+    }
+
+    /// Get the next random 64 bit integer.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// Get the next random number in the range `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        // Use the upper 53 bits, i.e. the precision of a f64.
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::util::rng::Rng;
+
+    #[test]
+    fn the_same_seed_produces_the_same_sequence() {
+        let mut a = Rng::new(1234);
+        let mut b = Rng::new(1234);
+
+        for _ in 0..100 {
+            assert_eq!(a.next_f64(), b.next_f64());
+        }
+    }
+
+    #[test]
+    fn different_seeds_produce_different_sequences() {
+        let mut a = Rng::new(1);
+        let mut b = Rng::new(2);
+        let xs: Vec<f64> = (0..10).map(|_| a.next_f64()).collect();
+        let ys: Vec<f64> = (0..10).map(|_| b.next_f64()).collect();
+
+        assert_ne!(xs, ys);
+    }
+
+    #[test]
+    fn values_stay_in_the_unit_interval() {
+        let mut r = Rng::new(0);
+        let mut sum = 0.0;
+
+        for _ in 0..10_000 {
+            let x = r.next_f64();
+            assert!((0.0..1.0).contains(&x));
+            sum += x;
+        }
+
+        // The mean of uniformly distributed values should be close to 0.5.
+        assert!((sum / 10_000.0 - 0.5).abs() < 0.02);
+    }
+}
@@ -0,0 +1,841 @@
+pub mod stats;
+pub mod background;
+
+use crate::shapes::{Shape, bounds::BoundingBox};
+use crate::light::Light;
+use crate::materials::Material;
+use crate::canvas::color::Color;
+use crate::math::point::Point;
+use crate::ray::{
+    Ray,
+    intersection::Intersections,
+    computations::Comps,
+};
+use self::{stats::Stats, background::Background};
+
+/// A World is a collection of all objects in a scene.
+///
+/// It holds the objects (any [`Shape`], e.g. spheres or whole
+/// [`Group`](crate::shapes::group::Group)s) and the light sources
+/// that illuminate them. A new world is empty, i.e. it contains
+/// neither objects nor lights.
+///
+/// The `max_depth` limits how often reflected and refracted rays
+/// are cast recursively. It defaults to [`World::DEFAULT_MAX_DEPTH`].
+///
+/// Rays that don't hit any object see the [`Background`] of the
+/// world, which is black by default.
+///
+/// Every world keeps [`Stats`] about the work done while it's rendered.
+pub struct World {
+    objects: Vec<Box<dyn Shape>>,
+    lights: Vec<Box<dyn Light>>,
+    max_depth: usize,
+    background: Background,
+    stats: Stats,
+}
+
+impl World {
+    /// The default recursion depth for reflection and refraction.
+    pub const DEFAULT_MAX_DEPTH: usize = 5;
+
+    /// Create a new, empty World.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::world::World;
+    ///
+    /// let w = World::new();
+    ///
+    /// assert_eq!(0, w.objects().len());
+    /// assert_eq!(0, w.lights().len());
+    /// assert_eq!(World::DEFAULT_MAX_DEPTH, w.max_depth());
+    /// ```
+    pub fn new() -> Self {
+        Self {
+            objects: Vec::new(),
+            lights: Vec::new(),
+            max_depth: Self::DEFAULT_MAX_DEPTH,
+            background: Background::default(),
+            stats: Stats::new(),
+        }
+    }
+
+    /// Get the objects of the world.
+    pub fn objects(&self) -> &Vec<Box<dyn Shape>> {
+        &self.objects
+    }
+
+    /// Get the light sources of the world.
+    pub fn lights(&self) -> &Vec<Box<dyn Light>> {
+        &self.lights
+    }
+
+    /// Get the maximum recursion depth for reflection and refraction.
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// Get the background of the world.
+    pub fn background(&self) -> &Background {
+        &self.background
+    }
+
+    /// Set the background of the world.
+    pub fn set_background(&mut self, background: Background) -> &mut Self {
+        self.background = background;
+        self
+    }
+
+    /// Get the statistics collected since the world was created
+    /// (or the statistics were reset).
+    pub fn stats(&self) -> &Stats {
+        &self.stats
+    }
+
+    /// Set the maximum recursion depth for reflection and refraction.
+    ///
+    /// A depth of `0` disables reflection and refraction.
+    pub fn set_max_depth(&mut self, depth: usize) -> &mut Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Add an object to the world.
+    ///
+    /// Returns a mutable reference to the world so that
+    /// calls can be chained.
+    ///
+    /// # Arguments
+    ///
+    /// * `s` - The object to add
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     world::World,
+    ///     shapes::Sphere,
+    ///     light::PointLight,
+    ///     canvas::color::Color,
+    ///     math::point::Point,
+    /// };
+    ///
+    /// let mut w = World::new();
+    /// w.add_object(Box::new(Sphere::new()))
+    ///  .add_light(Box::new(PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(-10.0, 10.0, -10.0))));
+    ///
+    /// assert_eq!(1, w.objects().len());
+    /// assert_eq!(1, w.lights().len());
+    /// ```
+    pub fn add_object(&mut self, s: Box<dyn Shape>) -> &mut Self {
+        self.objects.push(s);
+        self
+    }
+
+    /// Add a light source to the world.
+    ///
+    /// Returns a mutable reference to the world so that
+    /// calls can be chained.
+    ///
+    /// Any [`Light`] can be used, e.g. a
+    /// [`PointLight`](crate::light::PointLight) or a
+    /// [`SpotLight`](crate::light::SpotLight).
+    ///
+    /// # Arguments
+    ///
+    /// * `l` - The light source to add
+    pub fn add_light(&mut self, l: Box<dyn Light>) -> &mut Self {
+        self.lights.push(l);
+        self
+    }
+
+    /// Intersect the world with the given ray.
+    ///
+    /// Returns the intersections with all objects of the world
+    /// sorted in ascending order. Each shape that is tested counts as
+    /// one intersection test (see [`World::stats`]). A group isn't counted
+    /// itself, only the children that are tested, i.e. children skipped
+    /// because the ray misses the group's bounds aren't counted.
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - The ray to intersect the world with
+    pub fn intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape> {
+        // The intersections of a single object are few, so they are
+        // sorted per object and then merged.
+        Intersections::merge(self.objects.iter()
+            .map(|obj| Intersections::new(obj.intersect_counted(ray, &self.stats)))
+            .collect())
+    }
+
+    /// Get the bounding box (in world space) that contains all objects.
+    ///
+    /// The bounds of an empty world are [`BoundingBox::empty`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     world::World,
+    ///     shapes::Sphere,
+    ///     math::{point::Point, matrix::transformation::translation},
+    /// };
+    ///
+    /// let mut s = Sphere::new();
+    /// s.set_transform(translation(2.0, 0.0, 0.0));
+    /// let mut w = World::new();
+    /// w.add_object(Box::new(Sphere::new()))
+    ///  .add_object(Box::new(s));
+    ///
+    /// assert_eq!(Point::new(-1.0, -1.0, -1.0), *w.bounds().min());
+    /// assert_eq!(Point::new(3.0, 1.0, 1.0), *w.bounds().max());
+    /// ```
+    pub fn bounds(&self) -> BoundingBox {
+        let mut b = BoundingBox::empty();
+        for obj in self.objects.iter() {
+            b.merge(&obj.world_bounds());
+        }
+        b
+    }
+
+    /// Check if the given point lies in the shadow of the light source.
+    ///
+    /// A point is in shadow if there is an object between
+    /// the point and the light source. Objects whose material
+    /// doesn't cast a shadow (see [`Material::casts_shadow`]) are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `light` - The light source
+    /// * `point` - The point to check
+    pub fn is_shadowed(&self, light: &dyn Light, point: &Point) -> bool {
+        let v = *light.position() - *point;
+        let distance = v.mag();
+        let r = Ray::new(*point, v.norm_cpy());
+
+        self.intersect(&r).iter()
+            .any(|i| i.t() >= 0.0 && i.t() < distance && i.obj().material().casts_shadow())
+    }
+
+    /// Calculate the color at the intersection described by `comps`.
+    ///
+    /// The surface color of all light sources (with respect to shadows)
+    /// is combined with the reflected and refracted color. If the
+    /// material is both reflective and transparent, reflection and
+    /// refraction are blended using the Schlick approximation.
+    ///
+    /// # Arguments
+    ///
+    /// * `comps` - The precomputed state of the intersection
+    /// * `remaining` - The number of recursive reflection/refraction rays left
+    pub fn shade_hit(&self, comps: &Comps<dyn Shape>, remaining: usize) -> Color {
+        let material = comps.obj().material();
+        let mut surface = Color::new(0.0, 0.0, 0.0);
+
+        for light in &self.lights {
+            let shadowed = self.is_shadowed(light.as_ref(), comps.over_point());
+
+            surface = surface + Material::lighting_shadowed(material,
+                                                            comps.obj(),
+                                                            light.as_ref(),
+                                                            comps.over_point(),
+                                                            comps.eyev(),
+                                                            comps.normalv(),
+                                                            shadowed);
+        }
+
+        let reflected = self.reflected_color(comps, remaining);
+        let refracted = self.refracted_color(comps, remaining);
+
+        if material.reflective() > 0.0 && material.transparency() > 0.0 {
+            let reflectance = comps.schlick();
+
+            surface + reflected * reflectance + refracted * (1.0 - reflectance)
+        } else {
+            surface + reflected + refracted
+        }
+    }
+
+    /// Calculate the color seen by the given ray using the world's `max_depth`.
+    ///
+    /// Returns the background color if the ray doesn't hit anything.
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - The ray cast into the world
+    pub fn trace(&self, ray: &Ray) -> Color {
+        self.color_at(ray, self.max_depth)
+    }
+
+    /// Calculate the color seen by the given ray.
+    ///
+    /// Returns the background color if the ray doesn't hit anything.
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - The ray cast into the world
+    /// * `remaining` - The number of recursive reflection/refraction rays left
+    pub fn color_at(&self, ray: &Ray, remaining: usize) -> Color {
+        let xs = self.intersect(ray);
+
+        match xs.hit() {
+            Some(hit) => self.shade_hit(&hit.prepare_computations(ray, &xs), remaining),
+            None => self.background.color_for(ray),
+        }
+    }
+
+    /// Calculate the color reflected by the surface described by `comps`.
+    ///
+    /// Returns black if the surface isn't reflective or if
+    /// no recursive rays are `remaining`.
+    pub fn reflected_color(&self, comps: &Comps<dyn Shape>, remaining: usize) -> Color {
+        let reflective = comps.obj().material().reflective();
+
+        if remaining == 0 || reflective == 0.0 {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+
+        let r = Ray::new(*comps.over_point(), *comps.reflectv());
+
+        self.color_at(&r, remaining - 1) * reflective
+    }
+
+    /// Calculate the color refracted by the surface described by `comps`.
+    ///
+    /// Returns black if the surface is opaque, in case of total internal
+    /// reflection or if no recursive rays are `remaining`.
+    pub fn refracted_color(&self, comps: &Comps<dyn Shape>, remaining: usize) -> Color {
+        let transparency = comps.obj().material().transparency();
+
+        if remaining == 0 || transparency == 0.0 {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+
+        let n_ratio = comps.n1() / comps.n2();
+
+        match (-*comps.eyev()).refract(comps.normalv(), n_ratio) {
+            Some(direction) => {
+                let r = Ray::new(*comps.under_point(), direction);
+                self.color_at(&r, remaining - 1) * transparency
+            },
+            // Total internal reflection
+            None => Color::new(0.0, 0.0, 0.0),
+        }
+    }
+}
+
+impl Default for World {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        world::{World, background::Background},
+        camera::Camera,
+        shapes::{Shape, Sphere, group::Group},
+        light::{PointLight, SpotLight},
+        materials::Material,
+        canvas::color::Color,
+        ray::{
+            Ray,
+            intersection::{Intersection, Intersections},
+        },
+        math::{
+            point::Point,
+            vector::Vector,
+            matrix::transformation::{radians, scaling, translation},
+        },
+    };
+
+    // The default world of "The Ray Tracer Challenge".
+    fn default_world() -> World {
+        let mut s1 = Sphere::new();
+        s1.set_material(Material::new(Color::new(0.8, 1.0, 0.6), 0.1, 0.7, 0.2, 200.0));
+        let mut s2 = Sphere::new();
+        s2.set_transform(scaling(0.5, 0.5, 0.5));
+
+        let mut w = World::new();
+        w.add_object(Box::new(s1))
+         .add_object(Box::new(s2))
+         .add_light(Box::new(PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(-10.0, 10.0, -10.0))));
+        w
+    }
+
+    // A huge sphere whose top touches the plane y = -1 at (0, -1, -2).
+    // At that point it can't be distinguished from a plane.
+    fn floor(material: Material) -> Sphere {
+        let mut s = Sphere::new();
+        s.set_transform(translation(0.0, -1001.0, -2.0) * scaling(1000.0, 1000.0, 1000.0));
+        s.set_material(material);
+        s
+    }
+
+    // A ray hitting the floor at (0, -1, -2) in an angle of 45 degrees.
+    fn ray_to_floor() -> Ray {
+        Ray::new(Point::new(0.0, 0.0, -3.0),
+                 Vector::new(0.0, -(2.0_f64.sqrt()) / 2.0, 2.0_f64.sqrt() / 2.0))
+    }
+
+    fn assert_color_eq(expected: Color, actual: Color) {
+        assert!((expected.r() - actual.r()).abs() < 0.0001 &&
+                (expected.g() - actual.g()).abs() < 0.0001 &&
+                (expected.b() - actual.b()).abs() < 0.0001,
+                "expected {:?} but got {:?}", expected, actual);
+    }
+
+    #[test]
+    fn creating_a_world() {
+        let w = World::new();
+
+        assert!(w.objects().is_empty());
+        assert!(w.lights().is_empty());
+    }
+
+    #[test]
+    fn building_a_world_by_chaining() {
+        let mut s2 = Sphere::new();
+        s2.set_transform(scaling(0.5, 0.5, 0.5));
+
+        let mut w = World::new();
+        w.add_object(Box::new(Sphere::new()))
+         .add_object(Box::new(s2))
+         .add_light(Box::new(PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(-10.0, 10.0, -10.0))));
+
+        assert_eq!(2, w.objects().len());
+        assert_eq!(1, w.lights().len());
+        assert_eq!(scaling(0.5, 0.5, 0.5), *w.objects()[1].transform());
+    }
+
+    #[test]
+    fn intersect_a_world_with_a_ray() {
+        let w = default_world();
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = w.intersect(&r);
+
+        assert_eq!(4, xs.len());
+        assert_eq!(4.0, xs[0].t());
+        assert_eq!(4.5, xs[1].t());
+        assert_eq!(5.5, xs[2].t());
+        assert_eq!(6.0, xs[3].t());
+    }
+
+    #[test]
+    fn intersecting_a_world_with_many_objects_yields_sorted_intersections() {
+        let mut w = World::new();
+        // Overlapping spheres along the z axis, added in a scrambled order.
+        for i in 0..500 {
+            let mut s = Sphere::new();
+            s.set_transform(translation(0.0, 0.0, ((i * 37) % 500) as f64 * 0.5));
+            w.add_object(Box::new(s));
+        }
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = w.intersect(&r);
+
+        assert_eq!(1000, xs.len());
+        assert!(xs.iter().zip(xs.iter().skip(1)).all(|(a, b)| a.t() <= b.t()));
+
+        let mut expected: Vec<f64> = w.objects().iter()
+            .flat_map(|s| s.intersect(&r))
+            .map(|i| i.t())
+            .collect();
+        expected.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let ts: Vec<f64> = xs.iter().map(|i| i.t()).collect();
+        assert_eq!(expected, ts);
+    }
+
+    #[test]
+    fn there_is_no_shadow_when_nothing_is_collinear_with_point_and_light() {
+        let w = default_world();
+        assert!(!w.is_shadowed(w.lights()[0].as_ref(), &Point::new(0.0, 10.0, 0.0)));
+    }
+
+    #[test]
+    fn the_shadow_when_an_object_is_between_the_point_and_the_light() {
+        let w = default_world();
+        assert!(w.is_shadowed(w.lights()[0].as_ref(), &Point::new(10.0, -10.0, 10.0)));
+    }
+
+    #[test]
+    fn there_is_no_shadow_when_an_object_is_behind_the_light_or_the_point() {
+        let w = default_world();
+        assert!(!w.is_shadowed(w.lights()[0].as_ref(), &Point::new(-20.0, 20.0, -20.0)));
+        assert!(!w.is_shadowed(w.lights()[0].as_ref(), &Point::new(-2.0, 2.0, -2.0)));
+    }
+
+    #[test]
+    fn shading_an_intersection() {
+        let w = default_world();
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = Intersections::new(vec![Intersection::new(4.0, w.objects()[0].as_ref())]);
+        let comps = xs[0].prepare_computations(&r, &xs);
+
+        assert_color_eq(Color::new(0.38066, 0.47583, 0.2855), w.shade_hit(&comps, 5));
+    }
+
+    fn world_with_blocker(casts_shadow: bool) -> World {
+        let mut m = Material::default();
+        m.set_casts_shadow(casts_shadow);
+        let mut blocker = Sphere::new();
+        blocker.set_material(m);
+
+        let mut w = World::new();
+        w.add_object(Box::new(blocker))
+         .add_light(Box::new(PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, -10.0))));
+        w
+    }
+
+    #[test]
+    fn an_object_that_casts_a_shadow_between_point_and_light() {
+        let w = world_with_blocker(true);
+
+        assert!(w.is_shadowed(w.lights()[0].as_ref(), &Point::new(0.0, 0.0, 10.0)));
+    }
+
+    #[test]
+    fn an_object_that_does_not_cast_a_shadow_between_point_and_light() {
+        let w = world_with_blocker(false);
+
+        assert!(!w.is_shadowed(w.lights()[0].as_ref(), &Point::new(0.0, 0.0, 10.0)));
+    }
+
+    #[test]
+    fn shade_hit_is_given_an_intersection_in_shadow() {
+        let mut s2 = Sphere::new();
+        s2.set_transform(translation(0.0, 0.0, 10.0));
+        let mut w = World::new();
+        w.add_object(Box::new(Sphere::new()))
+         .add_object(Box::new(s2))
+         .add_light(Box::new(PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, -10.0))));
+        let r = Ray::new(Point::new(0.0, 0.0, 5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = Intersections::new(vec![Intersection::new(4.0, w.objects()[1].as_ref())]);
+        let comps = xs[0].prepare_computations(&r, &xs);
+
+        assert_color_eq(Color::new(0.1, 0.1, 0.1), w.shade_hit(&comps, 5));
+    }
+
+    #[test]
+    fn the_color_when_a_ray_misses() {
+        let w = default_world();
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 1.0, 0.0));
+
+        assert_eq!(Color::new(0.0, 0.0, 0.0), w.color_at(&r, 5));
+    }
+
+    #[test]
+    fn the_color_when_a_ray_hits() {
+        let w = default_world();
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert_color_eq(Color::new(0.38066, 0.47583, 0.2855), w.color_at(&r, 5));
+    }
+
+    #[test]
+    fn the_reflected_color_for_a_nonreflective_material() {
+        let w = default_world();
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = Intersections::new(vec![Intersection::new(1.0, w.objects()[1].as_ref())]);
+        let comps = xs[0].prepare_computations(&r, &xs);
+
+        assert_eq!(Color::new(0.0, 0.0, 0.0), w.reflected_color(&comps, 5));
+    }
+
+    #[test]
+    fn the_reflected_color_for_a_reflective_material() {
+        let mut m = Material::default();
+        m.set_reflective(0.5);
+        let mut w = default_world();
+        w.add_object(Box::new(floor(m)));
+        let r = ray_to_floor();
+        let xs = w.intersect(&r);
+        let comps = xs.hit().unwrap().prepare_computations(&r, &xs);
+
+        assert!(xs.hit().unwrap().is_same_object(w.objects()[2].as_ref()));
+        assert_color_eq(Color::new(0.19033, 0.23791, 0.14274), w.reflected_color(&comps, 5));
+    }
+
+    #[test]
+    fn shade_hit_with_a_reflective_material() {
+        let mut m = Material::default();
+        m.set_reflective(0.5);
+        let mut w = default_world();
+        w.add_object(Box::new(floor(m)));
+        let r = ray_to_floor();
+        let xs = w.intersect(&r);
+        let comps = xs.hit().unwrap().prepare_computations(&r, &xs);
+
+        assert_color_eq(Color::new(0.87677, 0.92436, 0.82918), w.shade_hit(&comps, 5));
+    }
+
+    #[test]
+    fn the_refracted_color_with_an_opaque_surface() {
+        let w = default_world();
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = w.intersect(&r);
+        let comps = xs[0].prepare_computations(&r, &xs);
+
+        assert_eq!(Color::new(0.0, 0.0, 0.0), w.refracted_color(&comps, 5));
+    }
+
+    #[test]
+    fn the_refracted_color_under_total_internal_reflection() {
+        let mut m = Material::new(Color::new(0.8, 1.0, 0.6), 0.1, 0.7, 0.2, 200.0);
+        m.set_transparency(1.0);
+        m.set_refractive_index(1.5);
+        let mut s = Sphere::new();
+        s.set_material(m);
+        let mut w = World::new();
+        w.add_object(Box::new(s))
+         .add_light(Box::new(PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(-10.0, 10.0, -10.0))));
+        let r = Ray::new(Point::new(0.0, 0.0, 2.0_f64.sqrt() / 2.0), Vector::new(0.0, 1.0, 0.0));
+        let xs = w.intersect(&r);
+        // The ray is inside of the sphere, so look at the second intersection.
+        let comps = xs[1].prepare_computations(&r, &xs);
+
+        assert_eq!(Color::new(0.0, 0.0, 0.0), w.refracted_color(&comps, 5));
+    }
+
+    #[test]
+    fn shade_hit_with_a_transparent_material() {
+        let mut m = Material::default();
+        m.set_transparency(0.5);
+        m.set_refractive_index(1.5);
+        let mut ball = Sphere::new();
+        ball.set_material(Material::new(Color::new(1.0, 0.0, 0.0), 0.5, 0.9, 0.9, 200.0));
+        ball.set_transform(translation(0.0, -3.5, -0.5));
+        let mut w = default_world();
+        w.add_object(Box::new(floor(m)))
+         .add_object(Box::new(ball));
+        let r = ray_to_floor();
+        let xs = w.intersect(&r);
+        let comps = xs.hit().unwrap().prepare_computations(&r, &xs);
+
+        assert_color_eq(Color::new(0.93642, 0.68642, 0.68642), w.shade_hit(&comps, 5));
+    }
+
+    #[test]
+    fn shade_hit_with_a_reflective_transparent_material() {
+        let mut m = Material::default();
+        m.set_reflective(0.5);
+        m.set_transparency(0.5);
+        m.set_refractive_index(1.5);
+        let mut ball = Sphere::new();
+        ball.set_material(Material::new(Color::new(1.0, 0.0, 0.0), 0.5, 0.9, 0.9, 200.0));
+        ball.set_transform(translation(0.0, -3.5, -0.5));
+        let mut w = default_world();
+        w.add_object(Box::new(floor(m)))
+         .add_object(Box::new(ball));
+        let r = ray_to_floor();
+        let xs = w.intersect(&r);
+        let comps = xs.hit().unwrap().prepare_computations(&r, &xs);
+
+        assert_color_eq(Color::new(0.93391, 0.69643, 0.69243), w.shade_hit(&comps, 5));
+    }
+
+    #[test]
+    fn a_max_depth_of_zero_disables_reflection_and_refraction() {
+        let mut m = Material::default();
+        m.set_reflective(0.5);
+        m.set_transparency(0.5);
+        m.set_refractive_index(1.5);
+        let mut ball = Sphere::new();
+        ball.set_material(Material::new(Color::new(1.0, 0.0, 0.0), 0.5, 0.9, 0.9, 200.0));
+        ball.set_transform(translation(0.0, -3.5, -0.5));
+        let mut w = default_world();
+        w.add_object(Box::new(floor(m)))
+         .add_object(Box::new(ball));
+
+        let mut opaque = default_world();
+        opaque.add_object(Box::new(floor(Material::default())));
+
+        let r = ray_to_floor();
+
+        w.set_max_depth(0);
+        assert_color_eq(opaque.trace(&r), w.trace(&r));
+
+        w.set_max_depth(1);
+        let shallow = w.trace(&r);
+        assert!(shallow.r() > opaque.trace(&r).r());
+        assert_color_eq(Color::new(0.93391, 0.69643, 0.69243), shallow);
+    }
+
+    #[test]
+    fn color_at_with_mutually_reflective_surfaces_terminates() {
+        let mut m = Material::default();
+        m.set_reflective(1.0);
+        let mut lower = Sphere::new();
+        lower.set_transform(translation(0.0, -1001.0, 0.0) * scaling(1000.0, 1000.0, 1000.0));
+        lower.set_material(m);
+        let mut m = Material::default();
+        m.set_reflective(1.0);
+        let mut upper = Sphere::new();
+        upper.set_transform(translation(0.0, 1001.0, 0.0) * scaling(1000.0, 1000.0, 1000.0));
+        upper.set_material(m);
+
+        let mut w = World::new();
+        w.add_object(Box::new(lower))
+         .add_object(Box::new(upper))
+         .add_light(Box::new(PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(0.0, 0.0, 0.0))))
+         .set_max_depth(50);
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 1.0, 0.0));
+
+        let c = w.trace(&r);
+        assert!(c.r() > 0.0 && c.r().is_finite());
+    }
+
+    #[test]
+    fn the_bounds_of_a_world_contain_all_object_centers() {
+        let mut s1 = Sphere::new();
+        s1.set_transform(translation(4.0, 1.0, 0.0));
+        let mut s2 = Sphere::new();
+        s2.set_transform(translation(-2.0, 3.0, 2.0) * scaling(2.0, 2.0, 2.0));
+        let mut w = World::new();
+        w.add_object(Box::new(s1))
+         .add_object(Box::new(s2));
+        let b = w.bounds();
+
+        assert_eq!(Point::new(-4.0, 0.0, -1.0), *b.min());
+        assert_eq!(Point::new(5.0, 5.0, 4.0), *b.max());
+        for obj in w.objects() {
+            assert!(b.contains_point(&obj.transform().mul_point(&Point::new(0.0, 0.0, 0.0))));
+        }
+    }
+
+    #[test]
+    fn counting_the_intersection_tests_of_a_render() {
+        let mut w = World::new();
+        w.add_object(Box::new(Sphere::new()))
+         .add_object(Box::new(Sphere::new()))
+         .add_object(Box::new(Sphere::new()));
+        let mut c = Camera::new(8, 6, std::f64::consts::PI / 2.0);
+        c.set_transform(translation(0.0, 0.0, 5.0));
+
+        // Without lights, reflection or refraction only the camera rays are cast.
+        c.render(&w);
+        assert_eq!(8 * 6 * 3, w.stats().intersection_tests());
+
+        w.stats().reset();
+        assert_eq!(0, w.stats().intersection_tests());
+    }
+
+    #[test]
+    fn shadow_rays_count_as_intersection_tests() {
+        let w = default_world();
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        w.trace(&r);
+
+        // One camera ray and one shadow ray, each tested against both spheres.
+        assert_eq!(4, w.stats().intersection_tests());
+    }
+
+    #[test]
+    fn a_ray_that_misses_sees_the_background() {
+        let mut w = default_world();
+        w.set_background(Background::Gradient { top: Color::new(0.0, 0.0, 1.0), bottom: Color::new(1.0, 1.0, 1.0) });
+        let up = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 1.0, 0.0));
+        let down = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, -1.0, 0.0));
+
+        assert_eq!(Color::new(0.0, 0.0, 1.0), w.trace(&up));
+        assert_eq!(Color::new(1.0, 1.0, 1.0), w.trace(&down));
+        assert_eq!(Background::default(), *World::new().background());
+    }
+
+    #[test]
+    fn a_group_is_rendered_like_its_children() {
+        let mut s = Sphere::new();
+        s.set_transform(scaling(0.5, 0.5, 0.5));
+        let mut g = Group::new();
+        g.set_transform(translation(1.0, 0.0, 0.0));
+        g.add_child(Box::new(s));
+        let mut grouped = World::new();
+        grouped.add_object(Box::new(g))
+               .add_light(Box::new(PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(-10.0, 10.0, -10.0))));
+
+        let mut s = Sphere::new();
+        s.set_transform(translation(1.0, 0.0, 0.0) * scaling(0.5, 0.5, 0.5));
+        let mut flat = World::new();
+        flat.add_object(Box::new(s))
+            .add_light(Box::new(PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(-10.0, 10.0, -10.0))));
+
+        let r = Ray::new(Point::new(1.1, 0.1, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert!(grouped.trace(&r).r() > 0.0);
+        assert_color_eq(flat.trace(&r), grouped.trace(&r));
+    }
+
+    #[test]
+    fn a_divided_group_renders_the_same_image() {
+        let group = || {
+            let mut g = Group::new();
+            for i in 0..8 {
+                let mut s = Sphere::new();
+                s.set_transform(translation(i as f64 * 2.5 - 9.0, 0.0, 0.0));
+                g.add_child(Box::new(s));
+            }
+            g
+        };
+        let mut divided = group();
+        divided.divide(2);
+
+        let mut a = World::new();
+        a.add_object(Box::new(group()))
+         .add_light(Box::new(PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(-10.0, 10.0, -10.0))));
+        let mut b = World::new();
+        b.add_object(Box::new(divided))
+         .add_light(Box::new(PointLight::new(Color::new(1.0, 1.0, 1.0), Point::new(-10.0, 10.0, -10.0))));
+        let mut c = Camera::new(20, 10, std::f64::consts::PI / 2.0);
+        c.set_transform(translation(0.0, 0.0, -10.0));
+        let image = c.render(&a);
+
+        assert!((0..20).any(|x| image.pixel_at(x, 5).r() > 0.0));
+        assert_eq!(image, c.render(&b));
+    }
+
+    #[test]
+    fn dividing_a_group_lowers_the_intersection_tests() {
+        let group = || {
+            let mut g = Group::new();
+            for i in 0..8 {
+                let mut s = Sphere::new();
+                s.set_transform(translation(i as f64 * 2.5 - 9.0, 0.0, 0.0));
+                g.add_child(Box::new(s));
+            }
+            g
+        };
+        let mut divided = group();
+        divided.divide(2);
+        let mut a = World::new();
+        a.add_object(Box::new(group()));
+        let mut b = World::new();
+        b.add_object(Box::new(divided));
+        let r = Ray::new(Point::new(-9.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert_eq!(2, a.intersect(&r).len());
+        assert_eq!(2, b.intersect(&r).len());
+        // Every child of the undivided group is tested, the divided group
+        // skips the subgroups the ray misses.
+        assert_eq!(8, a.stats().intersection_tests());
+        assert!(b.stats().intersection_tests() < a.stats().intersection_tests());
+    }
+
+    #[test]
+    fn a_spot_light_illuminates_the_world() {
+        let spot = |direction: Vector| {
+            let mut w = World::new();
+            w.add_object(Box::new(Sphere::new()))
+             .add_light(Box::new(SpotLight::new(Point::new(0.0, 0.0, -10.0),
+                                                direction,
+                                                Color::new(1.0, 1.0, 1.0),
+                                                radians(10.0),
+                                                radians(20.0))));
+            w
+        };
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+
+        assert_color_eq(Color::new(1.9, 1.9, 1.9), spot(Vector::new(0.0, 0.0, 1.0)).trace(&r));
+        assert_color_eq(Color::new(0.1, 0.1, 0.1), spot(Vector::new(0.0, 1.0, 0.0)).trace(&r));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{
+    canvas::color::Color,
+    ray::Ray,
+};
+
+/// The color seen by rays that don't hit any object.
+///
+/// # Variants
+///
+/// * `Solid` - The same color in every direction
+/// * `Gradient` - A vertical gradient (sky) from `bottom` (looking straight
+///   down) to `top` (looking straight up)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Background {
+    Solid(Color),
+    Gradient { top: Color, bottom: Color },
+}
+
+impl Background {
+    /// Get the background color seen by the given ray.
+    ///
+    /// A gradient is interpolated linearly based on the y
+    /// component of the (normalized) ray direction.
+    ///
+    /// # Arguments
+    ///
+    /// * `ray` - The ray that doesn't hit any object
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use sugar_ray::{
+    ///     world::background::Background,
+    ///     canvas::color::Color,
+    ///     ray::Ray,
+    ///     math::{point::Point, vector::Vector},
+    /// };
+    ///
+    /// let b = Background::Gradient { top: Color::new(1.0, 1.0, 1.0), bottom: Color::new(0.0, 0.0, 0.0) };
+    /// let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+    ///
+    /// assert_eq!(Color::new(0.5, 0.5, 0.5), b.color_for(&r));
+    /// ```
+    pub fn color_for(&self, ray: &Ray) -> Color {
+        match self {
+            Background::Solid(color) => *color,
+            Background::Gradient { top, bottom } => {
+                // Map y from [-1, 1] to [0, 1].
+                let t = (ray.direction().norm_cpy().y() + 1.0) / 2.0;
+
+                *bottom * (1.0 - t) + *top * t
+            },
+        }
+    }
+}
+
+impl Default for Background {
+    /// A black background.
+    fn default() -> Self {
+        Background::Solid(Color::new(0.0, 0.0, 0.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        world::background::Background,
+        canvas::color::Color,
+        ray::Ray,
+        math::{point::Point, vector::Vector},
+    };
+
+    fn sky() -> Background {
+        Background::Gradient { top: Color::new(0.2, 0.4, 1.0), bottom: Color::new(1.0, 1.0, 1.0) }
+    }
+
+    #[test]
+    fn a_ray_pointing_straight_up_sees_the_top_color() {
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 1.0, 0.0));
+
+        assert_eq!(Color::new(0.2, 0.4, 1.0), sky().color_for(&r));
+    }
+
+    #[test]
+    fn a_ray_pointing_straight_down_sees_the_bottom_color() {
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+
+        assert_eq!(Color::new(1.0, 1.0, 1.0), sky().color_for(&r));
+    }
+
+    #[test]
+    fn a_solid_background_is_the_same_in_every_direction() {
+        let b = Background::Solid(Color::new(0.3, 0.2, 0.1));
+
+        for d in [Vector::new(0.0, 1.0, 0.0), Vector::new(1.0, 0.0, 0.0), Vector::new(0.0, -1.0, 0.0)].iter() {
+            assert_eq!(Color::new(0.3, 0.2, 0.1), b.color_for(&Ray::new(Point::new(0.0, 0.0, 0.0), *d)));
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Statistics collected while rendering a world.
+///
+/// The counters are atomic, so they can be updated through a shared
+/// reference (e.g. while rendering) and read at any time.
+///
+/// # Examples
+///
+/// ```
+/// use sugar_ray::world::stats::Stats;
+///
+/// let s = Stats::new();
+/// s.add_intersection_tests(3);
+/// assert_eq!(3, s.intersection_tests());
+///
+/// s.reset();
+/// assert_eq!(0, s.intersection_tests());
+/// ```
+#[derive(Debug, Default)]
+pub struct Stats {
+    intersection_tests: AtomicUsize,
+}
+
+impl Stats {
+    /// Create new statistics with all counters set to zero.
+    pub fn new() -> Self {
+        Self { intersection_tests: AtomicUsize::new(0) }
+    }
+
+    /// Get the number of ray-object intersection tests.
+    pub fn intersection_tests(&self) -> usize {
+        self.intersection_tests.load(Ordering::Relaxed)
+    }
+
+    /// Count `n` additional ray-object intersection tests.
+    pub fn add_intersection_tests(&self, n: usize) {
+        self.intersection_tests.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Set all counters back to zero.
+    pub fn reset(&self) {
+        self.intersection_tests.store(0, Ordering::Relaxed);
+    }
+}